@@ -0,0 +1,508 @@
+//! Gera os gráficos comparativos dos métodos de prevenção de colisão.
+//! Lê os CSVs do benchmark headless (`*.csv` e `*_metrics.csv`) e também os
+//! CSVs de FPS gravados pelas teclas 1/2/3 (colunas `test`, `time`, `fps`, `agents`).
+//! Cada cenário vira um conjunto de gráficos (PNG e SVG) com uma série por método
+//! e barras de erro com o desvio padrão entre as sementes.
+//! Execuções com mapa, planejador ou layout hexagonal diferentes são cenários diferentes.
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+//==============================================================================
+// REGISTROS DOS CSVs
+//==============================================================================
+
+/// Colunas que não existiam nos CSVs mais antigos (vazias quando faltam)
+#[derive(Debug, Default, Deserialize)]
+struct RunSetup {
+    #[serde(default)]
+    map: String,
+    #[serde(default)]
+    planner: String,
+    #[serde(default)]
+    hex: String,
+    /// Agentes criados; nos CSVs antigos `agents` já era o número criado
+    #[serde(default)]
+    spawned: Option<usize>,
+}
+
+impl RunSetup {
+    fn spawned(&self, agents: usize) -> usize {
+        self.spawned.unwrap_or(agents)
+    }
+}
+
+/// Uma linha do CSV de passos gerado por `navegacao --benchmark`.
+#[derive(Debug, Deserialize)]
+struct StepRecord {
+    scenario: String,
+    avoidance: String,
+    grid: String,
+    #[serde(flatten)]
+    setup: RunSetup,
+    seed: u64,
+    agents: usize,
+    step_ms: f64,
+    new_collisions: usize,
+}
+
+/// Uma linha do CSV `_metrics.csv`: um agente de uma execução.
+#[derive(Debug, Deserialize)]
+struct MetricsRecord {
+    scenario: String,
+    avoidance: String,
+    grid: String,
+    #[serde(flatten)]
+    setup: RunSetup,
+    seed: u64,
+    agents: usize,
+    reached_goal: bool,
+    time_to_goal: Option<f64>,
+}
+
+/// Uma linha do CSV de FPS do `BenchmarkManager`.
+#[derive(Debug, Deserialize)]
+struct FpsRecord {
+    test: String,
+    time: f64,
+    fps: f64,
+    agents: usize,
+}
+
+//==============================================================================
+// AGREGAÇÃO
+//==============================================================================
+
+/// Grandezas que viram gráficos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Metric {
+    StepTime,
+    Fps,
+    Collisions,
+    TimeToGoal,
+    ReachedGoal,
+}
+
+impl Metric {
+    fn file_suffix(&self) -> &'static str {
+        match self {
+            Metric::StepTime => "step_time",
+            Metric::Fps => "fps",
+            Metric::Collisions => "collisions",
+            Metric::TimeToGoal => "time_to_goal",
+            Metric::ReachedGoal => "reached_goal",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Metric::StepTime => "Tempo por passo da simulação",
+            Metric::Fps => "FPS",
+            Metric::Collisions => "Colisões por execução",
+            Metric::TimeToGoal => "Tempo médio até o destino",
+            Metric::ReachedGoal => "Agentes que chegaram ao destino",
+        }
+    }
+
+    fn y_desc(&self) -> &'static str {
+        match self {
+            Metric::StepTime => "Tempo por passo (ms)",
+            Metric::Fps => "Quadros por segundo",
+            Metric::Collisions => "Colisões",
+            Metric::TimeToGoal => "Tempo simulado (s)",
+            Metric::ReachedGoal => "Agentes (%)",
+        }
+    }
+}
+
+/// Amostras de cada ponto, organizadas como
+/// cenário -> métrica -> método -> número de agentes pedidos -> amostras (uma por semente).
+type Samples = BTreeMap<String, BTreeMap<Metric, BTreeMap<String, BTreeMap<usize, Vec<f64>>>>>;
+
+fn push_sample(
+    samples: &mut Samples,
+    scenario: &str,
+    metric: Metric,
+    method: &str,
+    agents: usize,
+    value: f64,
+) {
+    samples
+        .entry(scenario.to_string())
+        .or_default()
+        .entry(metric)
+        .or_default()
+        .entry(method.to_string())
+        .or_default()
+        .entry(agents)
+        .or_default()
+        .push(value);
+}
+
+/// Nome do cenário nos gráficos: inclui o tipo de grid, o mapa, o planejador
+/// e o layout hexagonal quando não são o padrão.
+fn scenario_key(scenario: &str, grid: &str, setup: &RunSetup) -> String {
+    let mut key = scenario.to_string();
+    if !grid.eq_ignore_ascii_case("cardinal") {
+        key = format!("{}_{}", key, grid.to_lowercase());
+    }
+    if !setup.hex.is_empty() {
+        key = format!("{}_{}", key, setup.hex);
+    }
+    if !setup.planner.is_empty() && setup.planner != "grid" {
+        key = format!("{}_{}", key, setup.planner);
+    }
+    if !setup.map.is_empty() && setup.map != "empty" {
+        key = format!("{}_{}", key, setup.map);
+    }
+    key
+}
+
+/// Média e desvio padrão amostral
+fn mean_std(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, var.sqrt())
+}
+
+/// Lê um CSV de passos: cada arquivo é uma execução (uma semente)
+fn load_steps(path: &Path, samples: &mut Samples) -> Result<(), Box<dyn Error>> {
+    let mut rdr = csv::Reader::from_path(path)?;
+    let records: Vec<StepRecord> = rdr.deserialize().collect::<Result<_, _>>()?;
+    let Some(first) = records.first() else {
+        return Ok(());
+    };
+
+    let scenario = scenario_key(&first.scenario, &first.grid, &first.setup);
+    let step_ms: Vec<f64> = records.iter().map(|r| r.step_ms).collect();
+    let collisions: usize = records.iter().map(|r| r.new_collisions).sum();
+
+    println!(
+        "  {} ({} seed {}, {} agentes, {} criados): {} passos",
+        path.display(),
+        first.avoidance,
+        first.seed,
+        first.agents,
+        first.setup.spawned(first.agents),
+        records.len()
+    );
+
+    push_sample(samples, &scenario, Metric::StepTime, &first.avoidance, first.agents, mean_std(&step_ms).0);
+    push_sample(samples, &scenario, Metric::Collisions, &first.avoidance, first.agents, collisions as f64);
+    Ok(())
+}
+
+/// Lê um CSV de métricas: resume os agentes de uma execução
+fn load_metrics(path: &Path, samples: &mut Samples) -> Result<(), Box<dyn Error>> {
+    let mut rdr = csv::Reader::from_path(path)?;
+    let records: Vec<MetricsRecord> = rdr.deserialize().collect::<Result<_, _>>()?;
+    let Some(first) = records.first() else {
+        return Ok(());
+    };
+
+    let scenario = scenario_key(&first.scenario, &first.grid, &first.setup);
+    let times: Vec<f64> = records.iter().filter_map(|r| r.time_to_goal).collect();
+    let reached = records.iter().filter(|r| r.reached_goal).count();
+
+    println!(
+        "  {} ({} seed {}, {} agentes, {} criados): {} chegaram",
+        path.display(),
+        first.avoidance,
+        first.seed,
+        first.agents,
+        first.setup.spawned(first.agents),
+        reached
+    );
+
+    if !times.is_empty() {
+        push_sample(samples, &scenario, Metric::TimeToGoal, &first.avoidance, first.agents, mean_std(&times).0);
+    }
+    push_sample(
+        samples,
+        &scenario,
+        Metric::ReachedGoal,
+        &first.avoidance,
+        first.agents,
+        100.0 * reached as f64 / records.len() as f64,
+    );
+    Ok(())
+}
+
+/// Lê um CSV de FPS. Não há semente nem método no arquivo: o método é o nome
+/// da pasta que o contém (ignorando `ENVIO`). O arquivo só recebe linhas no fim, então uma
+/// execução termina quando o tempo volta a zero ou o teste muda; a amostra é a média dos
+/// quadros de cada execução, como a média dos passos nos CSVs novos.
+fn load_fps(path: &Path, samples: &mut Samples) -> Result<(), Box<dyn Error>> {
+    let method = path
+        .ancestors()
+        .skip(1)
+        .filter_map(|p| p.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .find(|name| name != "ENVIO")
+        .unwrap_or_else(|| "legado".to_string());
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+
+    let mut count = 0;
+    let mut runs: Vec<(String, usize, Vec<f64>)> = Vec::new();
+    let mut last_time = f64::MAX;
+    for record in rdr.deserialize() {
+        let record: FpsRecord = record?;
+        let same_run = runs.last().is_some_and(|(test, _, _)| *test == record.test) && record.time >= last_time;
+        if !same_run {
+            runs.push((record.test.clone(), record.agents, Vec::new()));
+        }
+        if let Some((_, _, fps)) = runs.last_mut() {
+            fps.push(record.fps);
+        }
+        last_time = record.time;
+        count += 1;
+    }
+    for (test, agents, fps) in &runs {
+        push_sample(samples, test, Metric::Fps, &method, *agents, mean_std(fps).0);
+    }
+
+    println!("  {} ({}): {} quadros em {} execuções", path.display(), method, count, runs.len());
+    Ok(())
+}
+
+/// Decide o formato pelo cabeçalho do arquivo
+fn load_file(path: &Path, samples: &mut Samples) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+    let header = content.lines().next().unwrap_or_default();
+
+    if header.contains("time_to_goal") {
+        load_metrics(path, samples)
+    } else if header.contains("step_ms") {
+        load_steps(path, samples)
+    } else if header.contains("fps") {
+        load_fps(path, samples)
+    } else {
+        Err("cabeçalho desconhecido".into())
+    }
+}
+
+/// Expande pastas em seus arquivos `.csv`
+fn collect_inputs(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "csv"))
+                .collect();
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+//==============================================================================
+// GRÁFICOS
+//==============================================================================
+
+/// Um ponto do gráfico: média e desvio entre as amostras
+struct Point {
+    agents: f64,
+    mean: f64,
+    std: f64,
+    samples: usize,
+}
+
+const SERIES_COLORS: [RGBColor; 6] = [BLUE, RED, GREEN, MAGENTA, CYAN, BLACK];
+
+/// Uma série do gráfico: nome do método, cor fixa e pontos
+struct Series {
+    method: String,
+    color: RGBColor,
+    points: Vec<Point>,
+}
+
+/// Desenha um gráfico (uma métrica de um cenário) em qualquer backend do plotters.
+fn draw_chart<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    scenario: &str,
+    metric: Metric,
+    series: &[Series],
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+
+    let all_points = series.iter().flat_map(|s| s.points.iter());
+    let (min_x, max_x, max_y) = all_points.fold((f64::MAX, f64::MIN, 0.0f64), |acc, p| {
+        (acc.0.min(p.agents), acc.1.max(p.agents), acc.2.max(p.mean + p.std))
+    });
+    let pad_x = ((max_x - min_x) * 0.05).max(5.0);
+    let max_y = if max_y > 0.0 { max_y * 1.1 } else { 1.0 };
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(format!("{} - {}", metric.title(), scenario), ("sans-serif", 32))
+        .margin(15)
+        .x_label_area_size(45)
+        .y_label_area_size(70)
+        .build_cartesian_2d((min_x - pad_x)..(max_x + pad_x), 0.0..max_y)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Número de agentes")
+        .y_desc(metric.y_desc())
+        .draw()?;
+
+    for s in series {
+        let color = s.color;
+        let points = &s.points;
+
+        chart
+            .draw_series(LineSeries::new(points.iter().map(|p| (p.agents, p.mean)), color.stroke_width(2)))?
+            .label(s.method.as_str())
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+
+        chart.draw_series(points.iter().map(|p| {
+            ErrorBar::new_vertical(p.agents, p.mean - p.std, p.mean, p.mean + p.std, color.filled(), 8)
+        }))?;
+
+        chart.draw_series(points.iter().map(|p| Circle::new((p.agents, p.mean), 4, color.filled())))?;
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Gera os PNGs e SVGs de todos os cenários e métricas encontrados
+fn write_charts(samples: &Samples, out_dir: &Path) -> Result<usize, Box<dyn Error>> {
+    fs::create_dir_all(out_dir)?;
+    let mut written = 0;
+
+    // Cada método mantém a mesma cor em todos os gráficos
+    let methods: BTreeSet<&String> = samples
+        .values()
+        .flat_map(|metrics| metrics.values())
+        .flat_map(|methods| methods.keys())
+        .collect();
+    let color_of = |method: &String| {
+        let idx = methods.iter().position(|m| *m == method).unwrap_or(0);
+        SERIES_COLORS[idx % SERIES_COLORS.len()]
+    };
+
+    for (scenario, metrics) in samples {
+        for (&metric, methods) in metrics {
+            let series: Vec<Series> = methods
+                .iter()
+                .map(|(method, by_agents)| {
+                    let points = by_agents
+                        .iter()
+                        .map(|(&agents, values)| {
+                            let (mean, std) = mean_std(values);
+                            Point { agents: agents as f64, mean, std, samples: values.len() }
+                        })
+                        .collect();
+                    Series { method: method.clone(), color: color_of(method), points }
+                })
+                .collect();
+
+            for s in &series {
+                for p in &s.points {
+                    println!(
+                        "{:<20} {:<14} {:<10} {:>5} agentes: {:>10.4} ± {:<10.4} (n={})",
+                        scenario,
+                        metric.file_suffix(),
+                        s.method,
+                        p.agents,
+                        p.mean,
+                        p.std,
+                        p.samples
+                    );
+                }
+            }
+
+            let stem = format!("{}_{}", scenario, metric.file_suffix()).to_lowercase();
+
+            let png = out_dir.join(format!("{}.png", stem));
+            draw_chart(BitMapBackend::new(&png, (1280, 720)).into_drawing_area(), scenario, metric, &series)?;
+
+            let svg = out_dir.join(format!("{}.svg", stem));
+            draw_chart(SVGBackend::new(&svg, (1280, 720)).into_drawing_area(), scenario, metric, &series)?;
+
+            written += 2;
+        }
+    }
+
+    Ok(written)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let mut inputs = Vec::new();
+    let mut out_dir = PathBuf::from("charts");
+    let mut iter = args.iter().skip(1);
+
+    while let Some(arg) = iter.next() {
+        if arg == "--out" {
+            match iter.next() {
+                Some(dir) => out_dir = PathBuf::from(dir),
+                None => {
+                    eprintln!("Erro: faltou a pasta depois de --out");
+                    process::exit(1);
+                }
+            }
+        } else {
+            inputs.push(PathBuf::from(arg));
+        }
+    }
+
+    if inputs.is_empty() {
+        eprintln!("\nErro: nenhum CSV fornecido.");
+        eprintln!("Uso correto: {} <arquivos.csv ou pastas>... [--out <pasta>]", args[0]);
+        process::exit(1);
+    }
+
+    let files = match collect_inputs(&inputs) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Erro ao listar os arquivos de entrada: {}", e);
+            process::exit(1);
+        }
+    };
+
+    println!("Lendo {} arquivos...", files.len());
+    let mut samples = Samples::new();
+    for file in &files {
+        if let Err(e) = load_file(file, &mut samples) {
+            eprintln!("Ignorando '{}': {}", file.display(), e);
+        }
+    }
+
+    match write_charts(&samples, &out_dir) {
+        Ok(count) => println!("{} gráficos salvos em '{}'", count, out_dir.display()),
+        Err(e) => {
+            eprintln!("Ocorreu um erro ao gerar os gráficos: {}", e);
+            process::exit(1);
+        }
+    }
+}
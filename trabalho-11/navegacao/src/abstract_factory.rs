@@ -1,6 +1,8 @@
-use crate::agent_factory::{AgentFactory, BlueAgentFactory, RedAgentFactory};
+use crate::agent_factory::{
+    AgentFactory, BlueAgentFactory, CarrierAgentFactory, RedAgentFactory, ScoutAgentFactory,
+};
 use crate::grid::Grid;
-use crate::grid_factory::{GridFactory, RectangularGridFactory};
+use crate::grid_factory::GridFactory;
 
 /// Contrato principal (Trait) para a criação de um conjunto coerente de simulação.
 /// Qualquer simulação (4-dir, 8-dir, etc.) deve implementar este trait.
@@ -11,23 +13,39 @@ pub trait SimulationFactory {
     /// Cria a fábrica de agentes azuis.
     fn create_blue_agent_factory(&self) -> Box<dyn AgentFactory>;
 
-    /// Cria a fábrica de agentes azuis.
+    /// Cria a fábrica de agentes vermelhos.
     fn create_red_agent_factory(&self) -> Box<dyn AgentFactory>;
+
+    /// Cria a fábrica de batedores (pequenos e rápidos).
+    fn create_scout_agent_factory(&self) -> Box<dyn AgentFactory>;
+
+    /// Cria a fábrica de carregadores (grandes e lentos).
+    fn create_carrier_agent_factory(&self) -> Box<dyn AgentFactory>;
+
+    /// Fábrica pelo nome usado nos arquivos de cenário e no HUD
+    fn create_agent_factory(&self, name: &str) -> Option<Box<dyn AgentFactory>> {
+        match name {
+            "blue" => Some(self.create_blue_agent_factory()),
+            "red" => Some(self.create_red_agent_factory()),
+            "scout" => Some(self.create_scout_agent_factory()),
+            "carrier" => Some(self.create_carrier_agent_factory()),
+            _ => None,
+        }
+    }
 }
 
 /// Implementação Concreta: Simulação Padrão (4-Direções, Agentes Azuis).
 /// Esta fábrica cria um conjunto de componentes que trabalham juntos.
 pub struct CardinalSimulationFactory {
     // A Fábrica Abstrata mantém referências às Fábricas Concretas que irá usar.
-    grid_factory: RectangularGridFactory,
+    grid_factory: Box<dyn GridFactory>,
 }
 
 // O construtor é necessário para inicializar as fábricas internas.
 impl CardinalSimulationFactory {
-    pub fn new() -> Self {
-        Self {
-            grid_factory: RectangularGridFactory,
-        }
+    /// `grid_factory` cria o grid (`RectangularGridFactory` para o vazio ou um gerador de mapas)
+    pub fn new(grid_factory: Box<dyn GridFactory>) -> Self {
+        Self { grid_factory }
     }
 }
 
@@ -46,4 +64,12 @@ impl SimulationFactory for CardinalSimulationFactory {
         // Retorna a fábrica que cria Agentes Vermelhos
         Box::new(RedAgentFactory)
     }
+
+    fn create_scout_agent_factory(&self) -> Box<dyn AgentFactory> {
+        Box::new(ScoutAgentFactory)
+    }
+
+    fn create_carrier_agent_factory(&self) -> Box<dyn AgentFactory> {
+        Box::new(CarrierAgentFactory)
+    }
 }
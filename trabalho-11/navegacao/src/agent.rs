@@ -0,0 +1,364 @@
+use crate::GridMode;
+use crate::agent_decorator::{AgentComponent, AgentSnapshot};
+use crate::grid::Grid;
+use crate::energy::{LOW_ENERGY_FRACTION, Route};
+use crate::observer::{AgentEvent, Observer};
+use macroquad::prelude::*;
+
+pub const PHYSICAL_RADIUS: f32 = 8.0;
+pub const DETECTION_RADIUS: f32 = 18.0;
+pub const AGENT_SPEED: f32 = 150.0;
+pub const FUEL_CAPACITY: f32 = 2000.0;
+
+/// Características físicas de um tipo de agente. Cada `AgentFactory` cria
+/// agentes de um arquétipo; os valores ficam em cada instância de `Agent`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AgentArchetype {
+    pub name: &'static str,
+    pub color: Color,
+    pub radius: f32,
+    pub detection_radius: f32,
+    pub max_speed: f32,
+    /// Aceleração máxima (px/s²); infinita = muda de velocidade na hora
+    pub acceleration: f32,
+    pub fuel_capacity: f32,
+}
+
+impl AgentArchetype {
+    /// Agente padrão (o único que existia antes dos arquétipos)
+    pub fn standard(name: &'static str, color: Color) -> Self {
+        Self {
+            name,
+            color,
+            radius: PHYSICAL_RADIUS,
+            detection_radius: DETECTION_RADIUS,
+            max_speed: AGENT_SPEED,
+            acceleration: f32::INFINITY,
+            fuel_capacity: FUEL_CAPACITY,
+        }
+    }
+
+    /// Batedor: pequeno, rápido, enxerga longe e gasta o tanque pequeno depressa
+    pub fn scout() -> Self {
+        Self {
+            name: "scout",
+            color: SKYBLUE,
+            radius: 5.0,
+            detection_radius: 30.0,
+            max_speed: 240.0,
+            acceleration: 900.0,
+            fuel_capacity: 1200.0,
+        }
+    }
+
+    /// Carregador: grande, lento, demora a frear e tem tanque grande
+    pub fn carrier() -> Self {
+        Self {
+            name: "carrier",
+            color: DARKPURPLE,
+            radius: 12.0,
+            detection_radius: 26.0,
+            max_speed: 90.0,
+            acceleration: 150.0,
+            fuel_capacity: 4000.0,
+        }
+    }
+}
+
+/// Estado do agente base que muda durante a simulação (o resto vem do arquétipo)
+#[derive(Clone, Debug)]
+pub struct AgentState {
+    pos: Vec2,
+    velocity: Vec2,
+    current_waypoint: usize,
+    is_finished: bool,
+    fuel: f32,
+    charging: Option<Vec2>,
+    low_energy_alerted: bool,
+    out_of_energy_alerted: bool,
+    current_step_size: f32,
+    /// Caminho inteiro e paradas de recarga (só nos checkpoints)
+    route: Option<Route>,
+}
+
+impl AgentState {
+    pub fn is_finished(&self) -> bool {
+        self.is_finished
+    }
+
+    pub fn set_finished(&mut self, finished: bool) {
+        self.is_finished = finished;
+    }
+}
+
+pub struct Agent {
+    pub id: usize,
+    pub pos: Vec2,
+    path: Vec<Vec2>,
+    current_waypoint: usize,
+    speed: f32, // Max speed
+    velocity: Vec2, // Velocidade atual
+    pub is_finished: bool,
+    pub color: Color,
+    pub fuel: f32,
+    fuel_capacity: f32,
+    /// Waypoints (índices de `path`) que são estações onde o agente recarrega
+    charging_stops: Vec<usize>,
+    /// Parado até encher o tanque; `Some` com o ponto da estação em que espera
+    charging: Option<Vec2>,
+    low_energy_alerted: bool,
+    out_of_energy_alerted: bool,
+    observers: Vec<Box<dyn Observer>>,
+    current_step_size: f32,
+    radius: f32,
+    detection_radius: f32,
+    acceleration: f32,
+}
+
+impl Agent {
+    pub fn from_archetype(id: usize, start_pos: Vec2, path: Vec<Vec2>, archetype: &AgentArchetype) -> Self {
+        Self {
+            id,
+            pos: start_pos,
+            path,
+            current_waypoint: 0,
+            speed: archetype.max_speed,
+            velocity: Vec2::ZERO,
+            is_finished: false,
+            color: archetype.color,
+            fuel: archetype.fuel_capacity,
+            fuel_capacity: archetype.fuel_capacity,
+            charging_stops: Vec::new(),
+            charging: None,
+            low_energy_alerted: false,
+            out_of_energy_alerted: false,
+            observers: Vec::new(),
+            current_step_size: 0.0,
+            radius: archetype.radius,
+            detection_radius: archetype.detection_radius,
+            acceleration: archetype.acceleration,
+        }
+    }
+
+    /// Paradas de recarga planejadas por `energy::plan_route`
+    pub fn with_charging_stops(mut self, stops: Vec<usize>) -> Self {
+        self.charging_stops = stops;
+        self
+    }
+
+    /// Avisos de energia baixa, esgotada e de tanque cheio de novo
+    fn update_energy_events(&mut self) {
+        if self.fuel <= 0.0 && !self.out_of_energy_alerted {
+            self.out_of_energy_alerted = true;
+            self.notify_observers(AgentEvent::OutOfFuel);
+        }
+        if self.fuel < self.fuel_capacity * LOW_ENERGY_FRACTION && !self.low_energy_alerted {
+            self.low_energy_alerted = true;
+            self.notify_observers(AgentEvent::LowEnergy);
+        }
+        if self.fuel >= self.fuel_capacity && (self.low_energy_alerted || self.charging.is_some()) {
+            self.low_energy_alerted = false;
+            self.out_of_energy_alerted = false;
+            self.charging = None;
+            self.notify_observers(AgentEvent::Recharged);
+        }
+    }
+
+    fn notify_observers(&self, event: AgentEvent) {
+        for obs in &self.observers {
+            obs.on_notify(self.id, event.clone());
+        }
+    }
+}
+
+impl AgentComponent for Agent {
+    fn update(&mut self, dt: f32) {
+        self.current_step_size = self.speed * dt;
+
+        self.update_energy_events();
+
+        // Sem energia, espera encher o tanque onde está (o que só acontece em uma estação)
+        if self.fuel <= 0.0 && self.charging.is_none() {
+            self.charging = Some(self.pos);
+        }
+        if self.charging.is_some() {
+            self.velocity = Vec2::ZERO;
+            return;
+        }
+
+        // Verifica se chegou ao waypoint ATUAL (para avançar o index).
+        // Com aceleração limitada, um waypoint intermediário vale como alcançado dentro da
+        // distância de frenagem; senão o agente orbita em volta dele sem conseguir fazer a curva.
+        // O último waypoint e as paradas de recarga exigem chegar de fato.
+        if self.current_waypoint < self.path.len() {
+            let target = self.path[self.current_waypoint];
+            let must_stop = self.current_waypoint + 1 == self.path.len()
+                || self.charging_stops.contains(&self.current_waypoint);
+            let braking_distance = self.velocity.length_squared() / (2.0 * self.acceleration);
+            let reach = if must_stop { 10.0 } else { braking_distance.max(10.0) };
+            if self.pos.distance(target) < reach {
+                if self.charging_stops.contains(&self.current_waypoint) && self.fuel < self.fuel_capacity {
+                    self.charging = Some(target);
+                }
+                self.current_waypoint += 1;
+                if self.current_waypoint >= self.path.len() {
+                    self.is_finished = true;
+                    self.velocity = Vec2::ZERO;
+                    self.notify_observers(AgentEvent::Finished);
+                }
+            }
+        }
+    }
+
+    fn perceive(&mut self, _grid: &Grid, _grid_mode: GridMode, _dt: f32) {}
+
+    // Retorna o alvo desejado (A* puro), ignorando colisões locais
+    fn get_next_step_target(&self) -> Option<Vec2> {
+        if self.is_finished || self.fuel <= 0.0 {
+            return None;
+        }
+        // Recarregando: só se move para voltar ao centro da estação se foi empurrado
+        if let Some(station) = self.charging {
+            return (self.pos.distance(station) > 4.0).then_some(station);
+        }
+        if self.current_waypoint >= self.path.len() {
+            return None;
+        }
+        Some(self.path[self.current_waypoint])
+    }
+
+    fn set_pos(&mut self, pos: Vec2) {
+        self.pos = pos;
+    }
+
+    fn replace_route(&mut self, route: Route) -> Route {
+        let start = self.current_waypoint.min(self.path.len());
+        let previous = Route {
+            waypoints: self.path.split_off(start),
+            charging_stops: self.charging_stops.iter().filter(|&&s| s >= start).map(|s| s - start).collect(),
+        };
+        self.is_finished = route.waypoints.is_empty();
+        self.path = route.waypoints;
+        self.charging_stops = route.charging_stops;
+        self.current_waypoint = 0;
+        previous
+    }
+
+    fn get_planned_path(&self) -> (&[Vec2], usize) {
+        (&self.path, self.current_waypoint.min(self.path.len()))
+    }
+
+    // --- Implementação RVO ---
+    fn get_velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    fn set_velocity(&mut self, vel: Vec2) {
+        self.velocity = vel;
+    }
+
+    fn get_max_speed(&self) -> f32 {
+        self.speed
+    }
+
+    fn get_max_acceleration(&self) -> f32 {
+        self.acceleration
+    }
+    // -------------------------
+
+    fn get_color(&self) -> Color {
+        if self.fuel <= 0.0 { GRAY } else { self.color }
+    }
+
+    fn get_pos(&self) -> Vec2 { self.pos }
+    fn is_finished(&self) -> bool { self.is_finished }
+    fn get_id(&self) -> usize { self.id }
+    fn consume_fuel(&mut self, amount: f32) { self.fuel = (self.fuel - amount).clamp(0.0, self.fuel_capacity); }
+    fn restore_fuel(&mut self, amount: f32) { self.fuel += amount; }
+    fn get_fuel(&self) -> f32 { self.fuel }
+    fn get_fuel_capacity(&self) -> f32 { self.fuel_capacity }
+    fn add_observer(&mut self, observer: Box<dyn Observer>) { self.observers.push(observer); }
+    fn get_physical_radius(&self) -> f32 { self.radius }
+    fn get_detection_radius(&self) -> f32 { self.detection_radius }
+    fn notify(&self, event: AgentEvent) { self.notify_observers(event); }
+
+    fn snapshot(&self, with_route: bool) -> AgentSnapshot {
+        let route = with_route.then(|| Route { waypoints: self.path.clone(), charging_stops: self.charging_stops.clone() });
+        AgentSnapshot::new(AgentState {
+            pos: self.pos,
+            velocity: self.velocity,
+            current_waypoint: self.current_waypoint,
+            is_finished: self.is_finished,
+            fuel: self.fuel,
+            charging: self.charging,
+            low_energy_alerted: self.low_energy_alerted,
+            out_of_energy_alerted: self.out_of_energy_alerted,
+            current_step_size: self.current_step_size,
+            route,
+        })
+    }
+
+    fn restore(&mut self, snapshot: AgentSnapshot) {
+        let state = snapshot.agent;
+        if let Some(route) = state.route {
+            self.path = route.waypoints;
+            self.charging_stops = route.charging_stops;
+        }
+        self.pos = state.pos;
+        self.velocity = state.velocity;
+        self.current_waypoint = state.current_waypoint;
+        self.is_finished = state.is_finished;
+        self.fuel = state.fuel;
+        self.charging = state.charging;
+        self.low_energy_alerted = state.low_energy_alerted;
+        self.out_of_energy_alerted = state.out_of_energy_alerted;
+        self.current_step_size = state.current_step_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Guarda os avisos de energia recebidos
+    struct EnergyLog(Rc<RefCell<Vec<String>>>);
+
+    impl Observer for EnergyLog {
+        fn on_notify(&self, _agent_id: usize, event: AgentEvent) {
+            if matches!(event, AgentEvent::LowEnergy | AgentEvent::OutOfFuel | AgentEvent::Recharged) {
+                self.0.borrow_mut().push(format!("{:?}", event));
+            }
+        }
+    }
+
+    #[test]
+    fn energy_events_fire_once_per_crossing() {
+        let archetype = AgentArchetype { fuel_capacity: 100.0, ..AgentArchetype::carrier() };
+        let mut agent = Agent::from_archetype(0, Vec2::ZERO, vec![vec2(100.0, 0.0)], &archetype);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        agent.add_observer(Box::new(EnergyLog(Rc::clone(&events))));
+        // Atualiza várias vezes seguidas: o aviso só sai na primeira
+        let settle = |agent: &mut Agent| {
+            for _ in 0..3 {
+                agent.update(1.0 / 60.0);
+            }
+            events.borrow_mut().drain(..).collect::<Vec<_>>()
+        };
+
+        assert!(settle(&mut agent).is_empty());
+        agent.consume_fuel(85.0);
+        assert_eq!(settle(&mut agent), ["LowEnergy"]);
+        agent.consume_fuel(15.0);
+        assert_eq!(settle(&mut agent), ["OutOfFuel"]);
+        agent.restore_fuel(100.0);
+        assert_eq!(settle(&mut agent), ["Recharged"]);
+
+        // Segunda travessia: tudo de novo, uma vez cada
+        agent.consume_fuel(100.0);
+        assert_eq!(settle(&mut agent), ["OutOfFuel", "LowEnergy"]);
+        agent.restore_fuel(100.0);
+        assert_eq!(settle(&mut agent), ["Recharged"]);
+    }
+}
@@ -0,0 +1,541 @@
+use crate::GridMode;
+use crate::agent::AgentState;
+use crate::energy::Route;
+use crate::formation::{FOLLOWER_GAIN, Formation, FormationHandle};
+use crate::grid::Grid;
+use crate::observer::{AgentEvent, Observer};
+use macroquad::prelude::*;
+use std::cell::RefCell;
+
+/// Trait base para Agentes e Decorators.
+pub trait AgentComponent {
+    // --- Métodos Básicos ---
+    fn update(&mut self, dt: f32);
+    /// Olha o grid antes do passo (a formação mede o corredor e checa as vagas)
+    fn perceive(&mut self, grid: &Grid, grid_mode: GridMode, dt: f32);
+    fn get_color(&self) -> Color;
+    fn get_pos(&self) -> Vec2;
+    fn is_finished(&self) -> bool;
+
+    // --- Métodos para o Command Pattern ---
+    fn set_pos(&mut self, pos: Vec2);
+    fn get_id(&self) -> usize;
+    fn get_next_step_target(&self) -> Option<Vec2>;
+    /// Troca o caminho que falta percorrer e devolve o anterior (para o undo)
+    fn replace_route(&mut self, route: Route) -> Route;
+    /// Caminho planejado inteiro e o índice do waypoint atual (para desenhar o caminho)
+    fn get_planned_path(&self) -> (&[Vec2], usize);
+
+    // --- Métodos para Vetores de Velocidade ---
+    fn get_velocity(&self) -> Vec2;
+    fn set_velocity(&mut self, vel: Vec2);
+    fn get_max_speed(&self) -> f32;
+    fn get_max_acceleration(&self) -> f32;
+
+    // --- Métodos para o Observer e Estado ---
+    fn consume_fuel(&mut self, amount: f32);
+    fn restore_fuel(&mut self, amount: f32);
+    fn get_fuel(&self) -> f32;
+    fn get_fuel_capacity(&self) -> f32;
+    fn add_observer(&mut self, observer: Box<dyn Observer>);
+
+    // --- Métodos para Detecção de Colisão ---
+    fn get_physical_radius(&self) -> f32;
+    fn get_detection_radius(&self) -> f32;
+
+    // --- Método para receber notificações ---
+    fn notify(&self, event: AgentEvent);
+
+    // --- Método para pegar a cor do raio visual ---
+    fn get_detection_color(&self) -> Color {
+        Color::new(1.0, 1.0, 0.0, 0.3)
+    }
+
+    // --- Métodos para o undo e os checkpoints ---
+    /// Estado que muda durante a simulação, do agente e de cada decorator.
+    /// O caminho só entra com `with_route` (checkpoints); no undo de cada tick
+    /// quem troca o caminho é o `AssignRouteCommand`.
+    fn snapshot(&self, with_route: bool) -> AgentSnapshot;
+    /// Volta ao estado salvo (cada decorator tira a sua camada do fim da lista)
+    fn restore(&mut self, snapshot: AgentSnapshot);
+}
+
+/// Estado salvo de um agente: o do agente base e uma camada por decorator, de dentro para fora
+#[derive(Clone, Debug)]
+pub struct AgentSnapshot {
+    pub agent: AgentState,
+    layers: Vec<LayerState>,
+}
+
+impl AgentSnapshot {
+    pub fn new(agent: AgentState) -> Self {
+        Self { agent, layers: Vec::new() }
+    }
+
+    fn push(mut self, layer: LayerState) -> Self {
+        self.layers.push(layer);
+        self
+    }
+}
+
+/// Estado de cada decorator
+#[derive(Clone, Debug)]
+enum LayerState {
+    SpeedBoost((f32, f32)),
+    DirectionDeviate((f32, Vec2)),
+    VisualAlert((f32, Color)),
+    /// O líder guarda também o estado compartilhado da formação
+    Formation { target: Option<Vec2>, settled: bool, formation: Option<Formation> },
+}
+
+/// --- DECORATOR 1: SpeedBoostDecorator ---
+pub struct SpeedBoostDecorator {
+    pub component: Box<dyn AgentComponent>,
+    base_multiplier: f32,
+    state: RefCell<(f32, f32)>,
+}
+
+impl SpeedBoostDecorator {
+    pub fn new(component: Box<dyn AgentComponent>, base_multiplier: f32) -> Self {
+        Self {
+            component,
+            base_multiplier,
+            state: RefCell::new((0.0, base_multiplier)),
+        }
+    }
+}
+
+impl AgentComponent for SpeedBoostDecorator {
+    fn update(&mut self, dt: f32) {
+        let mut state = self.state.borrow_mut();
+
+        if state.0 > 0.0 {
+            state.0 -= dt;
+            if state.0 <= 0.0 {
+                state.1 = self.base_multiplier;
+            }
+        }
+
+        let current_multiplier = state.1;
+        let effective_dt = dt * current_multiplier;
+
+        self.component.update(effective_dt);
+    }
+
+    fn notify(&self, event: AgentEvent) {
+        if let AgentEvent::ProximityAlert(_) = event {
+            let mut state = self.state.borrow_mut();
+            if state.0 <= 0.0 {
+                let duration = rand::gen_range(0.2, 0.5);
+                let random_speed = rand::gen_range(0.5, 1.4);
+                *state = (duration, random_speed);
+            }
+        }
+        self.component.notify(event);
+    }
+
+    // Pass-throughs
+    fn perceive(&mut self, grid: &Grid, grid_mode: GridMode, dt: f32) { self.component.perceive(grid, grid_mode, dt); }
+    fn get_color(&self) -> Color { self.component.get_color() }
+    fn get_pos(&self) -> Vec2 { self.component.get_pos() }
+    fn is_finished(&self) -> bool { self.component.is_finished() }
+    fn set_pos(&mut self, pos: Vec2) { self.component.set_pos(pos); }
+    fn get_id(&self) -> usize { self.component.get_id() }
+    fn replace_route(&mut self, route: Route) -> Route { self.component.replace_route(route) }
+    fn get_planned_path(&self) -> (&[Vec2], usize) { self.component.get_planned_path() }
+    fn get_next_step_target(&self) -> Option<Vec2> { self.component.get_next_step_target() }
+    
+    // RVO pass-through
+    fn get_velocity(&self) -> Vec2 { self.component.get_velocity() }
+    fn set_velocity(&mut self, vel: Vec2) { self.component.set_velocity(vel); }
+    fn get_max_speed(&self) -> f32 { self.component.get_max_speed() * self.base_multiplier } // Ajusta max speed
+    fn get_max_acceleration(&self) -> f32 { self.component.get_max_acceleration() }
+
+    fn snapshot(&self, with_route: bool) -> AgentSnapshot {
+        self.component.snapshot(with_route).push(LayerState::SpeedBoost(*self.state.borrow()))
+    }
+
+    fn restore(&mut self, mut snapshot: AgentSnapshot) {
+        if let Some(LayerState::SpeedBoost(state)) = snapshot.layers.pop() {
+            *self.state.borrow_mut() = state;
+        }
+        self.component.restore(snapshot);
+    }
+
+    fn consume_fuel(&mut self, a: f32) { self.component.consume_fuel(a); }
+    fn restore_fuel(&mut self, a: f32) { self.component.restore_fuel(a); }
+    fn get_fuel(&self) -> f32 { self.component.get_fuel() }
+    fn get_fuel_capacity(&self) -> f32 { self.component.get_fuel_capacity() }
+    fn add_observer(&mut self, obs: Box<dyn Observer>) { self.component.add_observer(obs); }
+    fn get_physical_radius(&self) -> f32 { self.component.get_physical_radius() }
+    fn get_detection_radius(&self) -> f32 { self.component.get_detection_radius() }
+    fn get_detection_color(&self) -> Color { self.component.get_detection_color() }
+}
+
+/// --- DECORATOR 2: DirectionDeviateDecorator ---
+pub struct DirectionDeviateDecorator {
+    component: Box<dyn AgentComponent>,
+    state: RefCell<(f32, Vec2)>,
+}
+
+impl DirectionDeviateDecorator {
+    pub fn new(component: Box<dyn AgentComponent>) -> Self {
+        Self {
+            component,
+            state: RefCell::new((0.0, vec2(0.0, 0.0))),
+        }
+    }
+}
+
+impl AgentComponent for DirectionDeviateDecorator {
+    fn update(&mut self, dt: f32) {
+        let mut state = self.state.borrow_mut();
+        if state.0 > 0.0 {
+            state.0 -= dt;
+        }
+        self.component.update(dt);
+    }
+
+    fn notify(&self, event: AgentEvent) {
+        if let AgentEvent::ProximityAlert(_) = event {
+            let mut state = self.state.borrow_mut();
+            if state.0 <= 0.0 {
+                let duration = rand::gen_range(0.1, 0.3);
+                let jx = rand::gen_range(-2.0, 2.0);
+                let jy = rand::gen_range(-2.0, 2.0);
+                *state = (duration, vec2(jx, jy));
+            }
+        }
+        self.component.notify(event);
+    }
+
+    fn get_next_step_target(&self) -> Option<Vec2> {
+        self.component.get_next_step_target()
+    }
+
+    fn snapshot(&self, with_route: bool) -> AgentSnapshot {
+        self.component.snapshot(with_route).push(LayerState::DirectionDeviate(*self.state.borrow()))
+    }
+
+    fn restore(&mut self, mut snapshot: AgentSnapshot) {
+        if let Some(LayerState::DirectionDeviate(state)) = snapshot.layers.pop() {
+            *self.state.borrow_mut() = state;
+        }
+        self.component.restore(snapshot);
+    }
+
+    // Pass-throughs
+    fn perceive(&mut self, grid: &Grid, grid_mode: GridMode, dt: f32) { self.component.perceive(grid, grid_mode, dt); }
+    fn get_color(&self) -> Color { self.component.get_color() }
+    fn get_pos(&self) -> Vec2 { self.component.get_pos() }
+    fn is_finished(&self) -> bool { self.component.is_finished() }
+    fn set_pos(&mut self, pos: Vec2) { self.component.set_pos(pos); }
+    fn get_id(&self) -> usize { self.component.get_id() }
+    fn replace_route(&mut self, route: Route) -> Route { self.component.replace_route(route) }
+    fn get_planned_path(&self) -> (&[Vec2], usize) { self.component.get_planned_path() }
+    
+    // RVO pass-through
+    fn get_velocity(&self) -> Vec2 { self.component.get_velocity() }
+    fn set_velocity(&mut self, vel: Vec2) { self.component.set_velocity(vel); }
+    fn get_max_speed(&self) -> f32 { self.component.get_max_speed() }
+    fn get_max_acceleration(&self) -> f32 { self.component.get_max_acceleration() }
+
+    fn consume_fuel(&mut self, a: f32) { self.component.consume_fuel(a); }
+    fn restore_fuel(&mut self, a: f32) { self.component.restore_fuel(a); }
+    fn get_fuel(&self) -> f32 { self.component.get_fuel() }
+    fn get_fuel_capacity(&self) -> f32 { self.component.get_fuel_capacity() }
+    fn add_observer(&mut self, obs: Box<dyn Observer>) { self.component.add_observer(obs); }
+    fn get_physical_radius(&self) -> f32 { self.component.get_physical_radius() }
+    fn get_detection_radius(&self) -> f32 { self.component.get_detection_radius() }
+    fn get_detection_color(&self) -> Color { self.component.get_detection_color() }
+}
+
+/// --- DECORATOR 3: VisualAlertDecorator ---
+pub struct VisualAlertDecorator {
+    component: Box<dyn AgentComponent>,
+    state: RefCell<(f32, Color)>,
+}
+
+impl VisualAlertDecorator {
+    pub fn new(component: Box<dyn AgentComponent>) -> Self {
+        Self {
+            component,
+            state: RefCell::new((0.0, GREEN)),
+        }
+    }
+}
+
+impl AgentComponent for VisualAlertDecorator {
+    fn update(&mut self, dt: f32) {
+        let mut state = self.state.borrow_mut();
+        if state.0 > 0.0 {
+            state.0 -= dt;
+        }
+        self.component.update(dt);
+    }
+
+    fn notify(&self, event: AgentEvent) {
+        match event {
+            AgentEvent::CollisionHit(_) => {
+                *self.state.borrow_mut() = (0.5, RED);
+            }
+            AgentEvent::ProximityAlert(_) => {
+                let mut state = self.state.borrow_mut();
+                if state.1 != RED || state.0 <= 0.0 {
+                    *state = (0.1, ORANGE);
+                }
+            }
+            _ => {}
+        }
+        self.component.notify(event);
+    }
+
+    fn get_detection_color(&self) -> Color {
+        let state = self.state.borrow();
+        if state.0 > 0.0 {
+            state.1
+        } else {
+            Color::new(0.0, 1.0, 0.0, 0.2)
+        }
+    }
+
+    fn snapshot(&self, with_route: bool) -> AgentSnapshot {
+        self.component.snapshot(with_route).push(LayerState::VisualAlert(*self.state.borrow()))
+    }
+
+    fn restore(&mut self, mut snapshot: AgentSnapshot) {
+        if let Some(LayerState::VisualAlert(state)) = snapshot.layers.pop() {
+            *self.state.borrow_mut() = state;
+        }
+        self.component.restore(snapshot);
+    }
+
+    // Pass-throughs
+    fn perceive(&mut self, grid: &Grid, grid_mode: GridMode, dt: f32) { self.component.perceive(grid, grid_mode, dt); }
+    fn get_color(&self) -> Color { self.component.get_color() }
+    fn get_pos(&self) -> Vec2 { self.component.get_pos() }
+    fn is_finished(&self) -> bool { self.component.is_finished() }
+    fn set_pos(&mut self, pos: Vec2) { self.component.set_pos(pos); }
+    fn get_id(&self) -> usize { self.component.get_id() }
+    fn replace_route(&mut self, route: Route) -> Route { self.component.replace_route(route) }
+    fn get_planned_path(&self) -> (&[Vec2], usize) { self.component.get_planned_path() }
+    fn get_next_step_target(&self) -> Option<Vec2> { self.component.get_next_step_target() }
+    
+    // RVO pass-through
+    fn get_velocity(&self) -> Vec2 { self.component.get_velocity() }
+    fn set_velocity(&mut self, vel: Vec2) { self.component.set_velocity(vel); }
+    fn get_max_speed(&self) -> f32 { self.component.get_max_speed() }
+    fn get_max_acceleration(&self) -> f32 { self.component.get_max_acceleration() }
+
+    fn consume_fuel(&mut self, a: f32) { self.component.consume_fuel(a); }
+    fn restore_fuel(&mut self, a: f32) { self.component.restore_fuel(a); }
+    fn get_fuel(&self) -> f32 { self.component.get_fuel() }
+    fn get_fuel_capacity(&self) -> f32 { self.component.get_fuel_capacity() }
+    fn add_observer(&mut self, obs: Box<dyn Observer>) { self.component.add_observer(obs); }
+    fn get_physical_radius(&self) -> f32 { self.component.get_physical_radius() }
+    fn get_detection_radius(&self) -> f32 { self.component.get_detection_radius() }
+}
+/// --- DECORATOR 4: FormationDecorator ---
+/// Membro de uma formação. O líder (vaga 0) segue o próprio caminho A*, um pouco mais devagar;
+/// os seguidores trocam o alvo pela vaga em volta do líder (ou pelo rastro dele, quando a vaga
+/// está bloqueada ou o corredor só comporta fila indiana) e aceleram para voltar a ela depois
+/// dos desvios da prevenção de colisão. Quando o líder chega, cada seguidor vai para a vaga final.
+pub struct FormationDecorator {
+    component: Box<dyn AgentComponent>,
+    formation: FormationHandle,
+    slot: usize,
+    /// Alvo escolhido no último `perceive`
+    target: Option<Vec2>,
+    /// O seguidor já recebeu o caminho até a vaga final e anda sozinho
+    settled: bool,
+}
+
+impl FormationDecorator {
+    pub fn new(component: Box<dyn AgentComponent>, formation: FormationHandle, slot: usize) -> Self {
+        Self { component, formation, slot, target: None, settled: false }
+    }
+
+    fn is_leader(&self) -> bool {
+        self.slot == 0
+    }
+
+    fn follows_formation(&self) -> bool {
+        !self.is_leader() && !self.settled && self.component.get_fuel() > 0.0
+    }
+}
+
+impl AgentComponent for FormationDecorator {
+    fn perceive(&mut self, grid: &Grid, grid_mode: GridMode, dt: f32) {
+        self.component.perceive(grid, grid_mode, dt);
+
+        if self.is_leader() {
+            let pos = self.component.get_pos();
+            let velocity = self.component.get_velocity();
+            let heading = if velocity.length() > 1.0 {
+                velocity
+            } else {
+                self.component.get_next_step_target().map_or(Vec2::ZERO, |target| target - pos)
+            };
+            let mut formation = self.formation.borrow_mut();
+            formation.leader_finished = self.component.is_finished();
+            formation.update_leader(grid, grid_mode, pos, heading, velocity.length(), dt);
+            return;
+        }
+        if self.settled {
+            return;
+        }
+
+        let pos = self.component.get_pos();
+        if self.formation.borrow().leader_finished {
+            // Vaga final em volta de onde o líder parou: vira o caminho do próprio agente
+            let waypoints = self.formation.borrow().final_route(grid, grid_mode, self.slot, pos);
+            self.component.replace_route(Route::direct(waypoints));
+            self.settled = true;
+            self.target = None;
+            return;
+        }
+        let (target, lag) = self.formation.borrow().follower_target(grid, grid_mode, self.slot, pos);
+        // Sem alvo visível, continua indo para o último
+        if target.is_some() {
+            self.target = target;
+        }
+        self.formation.borrow_mut().report_lag(lag);
+    }
+
+    fn get_next_step_target(&self) -> Option<Vec2> {
+        if self.follows_formation() {
+            self.target
+        } else {
+            self.component.get_next_step_target()
+        }
+    }
+
+    fn get_max_speed(&self) -> f32 {
+        let max_speed = self.component.get_max_speed();
+        let formation = self.formation.borrow();
+        if self.is_leader() {
+            return max_speed * formation.leader_speed_factor();
+        }
+        match self.target {
+            // Acompanha o líder e acelera proporcionalmente à distância até a vaga
+            Some(target) if self.follows_formation() => {
+                let distance = self.component.get_pos().distance(target);
+                max_speed.min(formation.leader_speed + FOLLOWER_GAIN * distance)
+            }
+            _ => max_speed,
+        }
+    }
+
+    fn snapshot(&self, with_route: bool) -> AgentSnapshot {
+        // O seguidor troca de caminho sozinho ao se acomodar na vaga final, então guarda o caminho
+        // dos dois lados da troca (o de antes para o undo, o da vaga para o redo)
+        let with_route = with_route || !self.is_leader();
+        let formation = self.is_leader().then(|| self.formation.borrow().clone());
+        self.component
+            .snapshot(with_route)
+            .push(LayerState::Formation { target: self.target, settled: self.settled, formation })
+    }
+
+    fn restore(&mut self, mut snapshot: AgentSnapshot) {
+        if let Some(LayerState::Formation { target, settled, formation }) = snapshot.layers.pop() {
+            self.target = target;
+            self.settled = settled;
+            if let Some(formation) = formation {
+                *self.formation.borrow_mut() = formation;
+            }
+        }
+        self.component.restore(snapshot);
+    }
+
+    // Pass-throughs
+    fn update(&mut self, dt: f32) { self.component.update(dt); }
+    fn notify(&self, event: AgentEvent) { self.component.notify(event); }
+    fn get_color(&self) -> Color { self.component.get_color() }
+    fn get_pos(&self) -> Vec2 { self.component.get_pos() }
+    fn is_finished(&self) -> bool { self.component.is_finished() }
+    fn set_pos(&mut self, pos: Vec2) { self.component.set_pos(pos); }
+    fn get_id(&self) -> usize { self.component.get_id() }
+    fn replace_route(&mut self, route: Route) -> Route { self.component.replace_route(route) }
+    fn get_planned_path(&self) -> (&[Vec2], usize) { self.component.get_planned_path() }
+
+    // RVO pass-through
+    fn get_velocity(&self) -> Vec2 { self.component.get_velocity() }
+    fn set_velocity(&mut self, vel: Vec2) { self.component.set_velocity(vel); }
+    fn get_max_acceleration(&self) -> f32 { self.component.get_max_acceleration() }
+
+    fn consume_fuel(&mut self, a: f32) { self.component.consume_fuel(a); }
+    fn restore_fuel(&mut self, a: f32) { self.component.restore_fuel(a); }
+    fn get_fuel(&self) -> f32 { self.component.get_fuel() }
+    fn get_fuel_capacity(&self) -> f32 { self.component.get_fuel_capacity() }
+    fn add_observer(&mut self, obs: Box<dyn Observer>) { self.component.add_observer(obs); }
+    fn get_physical_radius(&self) -> f32 { self.component.get_physical_radius() }
+    fn get_detection_radius(&self) -> f32 { self.component.get_detection_radius() }
+    fn get_detection_color(&self) -> Color { self.component.get_detection_color() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{Agent, AgentArchetype};
+    use crate::collision_avoidance::NoAvoidanceStrategy;
+    use crate::command::CommandManager;
+    use crate::formation::FormationShape;
+    use crate::simulation;
+
+    fn settled(agent: &dyn AgentComponent) -> bool {
+        matches!(agent.snapshot(false).layers.last(), Some(LayerState::Formation { settled: true, .. }))
+    }
+
+    fn route(agent: &dyn AgentComponent) -> (Vec<Vec2>, usize) {
+        let (path, current) = agent.get_planned_path();
+        (path.to_vec(), current)
+    }
+
+    #[test]
+    fn undo_across_settle_restores_the_formation_route() {
+        let mut grid = Grid::new(24, 10);
+        let archetype = AgentArchetype::standard("formação", BLUE);
+        let formation = Formation::new(FormationShape::Line, 40.0, 3, archetype.radius);
+        // Líder no meio com caminho até o destino; os seguidores começam sem caminho próprio
+        let members = [
+            (vec2(50.0, 110.0), vec![vec2(250.0, 110.0)]),
+            (vec2(50.0, 70.0), Vec::new()),
+            (vec2(50.0, 150.0), Vec::new()),
+        ];
+        let mut agents: Vec<Box<dyn AgentComponent>> = members
+            .into_iter()
+            .enumerate()
+            .map(|(slot, (start, path))| {
+                let member = Box::new(Agent::from_archetype(slot, start, path, &archetype));
+                Box::new(FormationDecorator::new(member, formation.clone(), slot)) as Box<dyn AgentComponent>
+            })
+            .collect();
+        let mut manager = CommandManager::new();
+
+        // Passo a passo até os seguidores se acomodarem, guardando como estavam antes
+        let mut before = Vec::new();
+        for ticks in 0.. {
+            assert!(ticks < 2000, "os seguidores não se acomodaram");
+            before = agents.iter().map(|a| (a.get_pos(), route(a.as_ref()))).collect();
+            simulation::step(&mut agents, &mut grid, &mut manager, &mut NoAvoidanceStrategy, GridMode::Cardinal, 1.0 / 60.0);
+            if agents[1..].iter().all(|a| settled(a.as_ref())) {
+                break;
+            }
+        }
+        let after: Vec<_> = agents.iter().map(|a| (a.get_pos(), route(a.as_ref()))).collect();
+        assert!(after[1..].iter().all(|(_, (path, _))| !path.is_empty()));
+
+        manager.undo_last(&mut agents, &mut grid);
+        for (agent, expected) in agents.iter().zip(&before) {
+            assert!(!settled(agent.as_ref()));
+            assert_eq!(&(agent.get_pos(), route(agent.as_ref())), expected);
+        }
+        assert!(!formation.borrow().leader_finished);
+
+        manager.redo_last(&mut agents, &mut grid);
+        for (agent, expected) in agents.iter().zip(&after).skip(1) {
+            assert!(settled(agent.as_ref()));
+            assert_eq!(&(agent.get_pos(), route(agent.as_ref())), expected);
+        }
+    }
+}
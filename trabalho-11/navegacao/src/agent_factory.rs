@@ -0,0 +1,70 @@
+use crate::agent::{Agent, AgentArchetype};
+use macroquad::prelude::*;
+
+/// Contrato (Trait) para qualquer fábrica responsável por criar agentes.
+/// Cada fábrica produz um arquétipo: tamanho, velocidade, aceleração, alcance e tanque.
+pub trait AgentFactory {
+    /// Características dos agentes criados por esta fábrica.
+    fn archetype(&self) -> AgentArchetype;
+
+    /// Cria e retorna uma nova instância de Agent.
+    /// Recebe o 'id' necessário para o Command/Observer pattern.
+    fn create_agent(&self, start_pos: Vec2, path: Vec<Vec2>, id: usize) -> Agent {
+        Agent::from_archetype(id, start_pos, path, &self.archetype())
+    }
+}
+
+// --- Fábricas Concretas ---
+
+/// Fábrica para criar Agentes Azuis (arquétipo padrão).
+pub struct BlueAgentFactory;
+
+impl AgentFactory for BlueAgentFactory {
+    fn archetype(&self) -> AgentArchetype {
+        AgentArchetype::standard("blue", BLUE)
+    }
+}
+
+/// Fábrica para criar Agentes Vermelhos (arquétipo padrão).
+pub struct RedAgentFactory;
+
+impl AgentFactory for RedAgentFactory {
+    fn archetype(&self) -> AgentArchetype {
+        AgentArchetype::standard("red", BROWN)
+    }
+}
+
+/// Fábrica para criar Batedores: pequenos e rápidos.
+pub struct ScoutAgentFactory;
+
+impl AgentFactory for ScoutAgentFactory {
+    fn archetype(&self) -> AgentArchetype {
+        AgentArchetype::scout()
+    }
+}
+
+/// Fábrica para criar Carregadores: grandes e lentos.
+pub struct CarrierAgentFactory;
+
+impl AgentFactory for CarrierAgentFactory {
+    fn archetype(&self) -> AgentArchetype {
+        AgentArchetype::carrier()
+    }
+}
+
+/// Fábrica com um arquétipo ajustado (ex.: valores trocados em um arquivo de cenário).
+pub struct CustomAgentFactory {
+    archetype: AgentArchetype,
+}
+
+impl CustomAgentFactory {
+    pub fn new(archetype: AgentArchetype) -> Self {
+        Self { archetype }
+    }
+}
+
+impl AgentFactory for CustomAgentFactory {
+    fn archetype(&self) -> AgentArchetype {
+        self.archetype
+    }
+}
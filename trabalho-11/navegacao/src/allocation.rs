@@ -0,0 +1,483 @@
+use crate::agent_decorator::AgentComponent;
+use crate::command::{AssignRouteCommand, Command, CompositeCommand};
+use crate::energy::{self, Traveler, path_length};
+use crate::grid::Grid;
+use crate::{GridMode, calculate_waypoints, grid_to_screen_center, screen_to_grid};
+
+/// Como distribuir N agentes entre M alvos (um alvo por agente)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AllocationMethod {
+    /// Ótimo: menor soma de custos (algoritmo Húngaro, O(n³))
+    Hungarian,
+    /// Cada agente, na ordem de chegada, pega o alvo livre mais barato
+    Greedy,
+    /// Leilão de Bertsekas: agentes dão lances e podem tomar o alvo de quem chegou antes
+    Auction,
+}
+
+impl AllocationMethod {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "hungarian" | "hungaro" => Some(AllocationMethod::Hungarian),
+            "greedy" => Some(AllocationMethod::Greedy),
+            "auction" => Some(AllocationMethod::Auction),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AllocationMethod::Hungarian => "hungarian",
+            AllocationMethod::Greedy => "greedy",
+            AllocationMethod::Auction => "auction",
+        }
+    }
+
+    /// Próximo método (tecla H na janela)
+    pub fn next(&self) -> Self {
+        match self {
+            AllocationMethod::Hungarian => AllocationMethod::Greedy,
+            AllocationMethod::Greedy => AllocationMethod::Auction,
+            AllocationMethod::Auction => AllocationMethod::Hungarian,
+        }
+    }
+}
+
+/// De onde vem o custo de mandar um agente a um alvo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CostModel {
+    /// Comprimento (px) do caminho do planejador atual (A* pelo cache do `PathManager` ou navmesh); sem caminho = infinito
+    PathLength,
+    /// Distância em linha reta (px) entre os centros das células: barato para grupos grandes
+    Heuristic,
+}
+
+impl CostModel {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "path" | "path_length" => Some(CostModel::PathLength),
+            "heuristic" | "distance" => Some(CostModel::Heuristic),
+            _ => None,
+        }
+    }
+}
+
+/// Matriz `custos[agente][alvo]`; `radii` é o raio de cada agente (folga do caminho na malha de navegação)
+pub fn cost_matrix(
+    grid: &Grid,
+    starts: &[(usize, usize)],
+    radii: &[f32],
+    goals: &[(usize, usize)],
+    grid_mode: GridMode,
+    model: CostModel,
+) -> Vec<Vec<f32>> {
+    starts
+        .iter()
+        .zip(radii)
+        .map(|(&start, &radius)| {
+            goals
+                .iter()
+                .map(|&goal| match model {
+                    CostModel::Heuristic => {
+                        grid_to_screen_center(start, grid_mode).distance(grid_to_screen_center(goal, grid_mode))
+                    }
+                    CostModel::PathLength => match calculate_waypoints(grid, start, goal, grid_mode, radius) {
+                        Some(waypoints) => path_length(&waypoints),
+                        None => f32::INFINITY,
+                    },
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Alvo de cada agente (`None` quando sobram agentes ou nenhum alvo é alcançável)
+pub fn assign(costs: &[Vec<f32>], method: AllocationMethod) -> Vec<Option<usize>> {
+    let goals = costs.first().map_or(0, |row| row.len());
+    match method {
+        AllocationMethod::Hungarian => hungarian(costs),
+        AllocationMethod::Greedy => {
+            let mut greedy = GreedyAllocator::new(goals);
+            costs.iter().map(|row| greedy.arrive(row)).collect()
+        }
+        AllocationMethod::Auction => {
+            let mut auction = AuctionAllocator::new(goals, auction_epsilon(costs));
+            for row in costs {
+                auction.arrive(row.clone());
+            }
+            auction.assignment().to_vec()
+        }
+    }
+}
+
+/// Ação "mandar os agentes selecionados para estes alvos" da janela:
+/// custo pelo comprimento dos caminhos A*, atribuição pelo método escolhido e um
+/// `AssignRouteCommand` por agente, juntos em um comando (um undo desfaz tudo).
+/// O caminho de cada agente é planejado com a energia que ele tem agora.
+pub fn assign_agents_to_targets(
+    grid: &Grid,
+    agents: &[Box<dyn AgentComponent>],
+    selected: &[usize],
+    targets: &[(usize, usize)],
+    grid_mode: GridMode,
+    method: AllocationMethod,
+) -> Option<Box<dyn Command>> {
+    let chosen: Vec<&dyn AgentComponent> =
+        selected.iter().filter_map(|&id| agents.get(id)).map(|a| a.as_ref()).collect();
+    if chosen.is_empty() || targets.is_empty() {
+        return None;
+    }
+
+    let starts: Vec<(usize, usize)> = chosen
+        .iter()
+        .map(|a| {
+            let pos = a.get_pos();
+            screen_to_grid(pos.x, pos.y, grid_mode)
+        })
+        .collect();
+    let radii: Vec<f32> = chosen.iter().map(|a| a.get_physical_radius()).collect();
+    let costs = cost_matrix(grid, &starts, &radii, targets, grid_mode, CostModel::PathLength);
+    let assignment = assign(&costs, method);
+    println!(
+        "Alocação {}: {} de {} agentes com alvo, custo total {:.0} px",
+        method.name(),
+        assignment.iter().flatten().count(),
+        chosen.len(),
+        total_cost(&costs, &assignment)
+    );
+
+    let mut commands: Vec<Box<dyn Command>> = Vec::new();
+    for ((agent, &start), goal) in chosen.iter().zip(&starts).zip(assignment) {
+        let Some(goal) = goal else { continue };
+        let traveler = Traveler {
+            max_speed: agent.get_max_speed(),
+            radius: agent.get_physical_radius(),
+            energy: agent.get_fuel(),
+            capacity: agent.get_fuel_capacity(),
+        };
+        let route = energy::plan_route_with_energy(grid, start, targets[goal], grid_mode, traveler);
+        if let Some(route) = route {
+            commands.push(Box::new(AssignRouteCommand::new(agent.get_id(), route)));
+        }
+    }
+    (!commands.is_empty()).then(|| Box::new(CompositeCommand::new(commands)) as Box<dyn Command>)
+}
+
+/// Soma dos custos de uma atribuição (para comparar os métodos)
+pub fn total_cost(costs: &[Vec<f32>], assignment: &[Option<usize>]) -> f32 {
+    assignment
+        .iter()
+        .enumerate()
+        .filter_map(|(agent, goal)| goal.map(|g| costs[agent][g]))
+        .sum()
+}
+
+/// Custo que substitui o infinito nas contas: maior que qualquer atribuição só com custos finitos
+fn unreachable_cost(costs: &[Vec<f32>]) -> f64 {
+    let finite: f64 = costs.iter().flatten().filter(|c| c.is_finite()).map(|&c| c as f64).sum();
+    finite * 2.0 + 1.0
+}
+
+/// Algoritmo Húngaro com potenciais (versão O(n²·m) para n linhas ≤ m colunas).
+/// Com mais agentes que alvos, resolve a transposta: os agentes que sobram ficam sem alvo.
+pub fn hungarian(costs: &[Vec<f32>]) -> Vec<Option<usize>> {
+    let rows = costs.len();
+    let cols = costs.first().map_or(0, |row| row.len());
+    if rows == 0 || cols == 0 {
+        return vec![None; rows];
+    }
+
+    let big = unreachable_cost(costs);
+    let value = |agent: usize, goal: usize| {
+        let c = costs[agent][goal];
+        if c.is_finite() { c as f64 } else { big }
+    };
+
+    // Linhas do algoritmo = lado menor
+    let transposed = rows > cols;
+    let (n, m) = if transposed { (cols, rows) } else { (rows, cols) };
+    let cost = |i: usize, j: usize| if transposed { value(j, i) } else { value(i, j) };
+
+    // Índices 1-based; a coluna 0 é a coluna fictícia da construção
+    let mut u = vec![0.0f64; n + 1];
+    let mut v = vec![0.0f64; m + 1];
+    let mut row_of_col = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        row_of_col[0] = i;
+        let mut j0 = 0;
+        let mut min_v = vec![f64::INFINITY; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of_col[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let reduced = cost(i0 - 1, j - 1) - u[i0] - v[j];
+                if reduced < min_v[j] {
+                    min_v[j] = reduced;
+                    way[j] = j0;
+                }
+                if min_v[j] < delta {
+                    delta = min_v[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[row_of_col[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_v[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of_col[j0] == 0 {
+                break;
+            }
+        }
+        // Inverte o caminho aumentante
+        loop {
+            let j1 = way[j0];
+            row_of_col[j0] = row_of_col[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut assignment = vec![None; rows];
+    for (j, &i) in row_of_col.iter().enumerate().skip(1) {
+        if i == 0 {
+            continue;
+        }
+        let (agent, goal) = if transposed { (j - 1, i - 1) } else { (i - 1, j - 1) };
+        if costs[agent][goal].is_finite() {
+            assignment[agent] = Some(goal);
+        }
+    }
+    assignment
+}
+
+/// Alocação gulosa para agentes que chegam um de cada vez
+pub struct GreedyAllocator {
+    taken: Vec<bool>,
+}
+
+impl GreedyAllocator {
+    pub fn new(goals: usize) -> Self {
+        Self { taken: vec![false; goals] }
+    }
+
+    /// Reserva o alvo livre mais barato para o agente que chegou
+    pub fn arrive(&mut self, costs: &[f32]) -> Option<usize> {
+        let goal = (0..self.taken.len())
+            .filter(|&g| !self.taken[g] && costs[g].is_finite())
+            .min_by(|&a, &b| costs[a].total_cmp(&costs[b]))?;
+        self.taken[goal] = true;
+        Some(goal)
+    }
+}
+
+/// ε padrão do leilão: a soma final fica no máximo n·ε acima do ótimo
+pub fn auction_epsilon(costs: &[Vec<f32>]) -> f32 {
+    let max = costs.iter().flatten().filter(|c| c.is_finite()).fold(0.0f32, |a, &c| a.max(c));
+    (max / (100.0 * (costs.len() as f32 + 1.0))).max(1e-3)
+}
+
+/// Leilão de Bertsekas incremental. Cada alvo tem um preço; um agente sem alvo dá
+/// lance no alvo de maior valor (−custo − preço), subindo o preço pela diferença
+/// para o segundo melhor mais ε, e quem tinha o alvo volta para a fila.
+/// Agentes novos entram no leilão sem recomeçar do zero (os preços continuam).
+pub struct AuctionAllocator {
+    epsilon: f32,
+    /// Custo de ficar sem alvo, igual para todos: o dobro do maior custo finito já visto
+    opt_out_cost: f32,
+    prices: Vec<f32>,
+    owner: Vec<Option<usize>>,
+    costs: Vec<Vec<f32>>,
+    assignment: Vec<Option<usize>>,
+}
+
+impl AuctionAllocator {
+    pub fn new(goals: usize, epsilon: f32) -> Self {
+        Self {
+            epsilon,
+            opt_out_cost: 0.0,
+            prices: vec![0.0; goals],
+            owner: vec![None; goals],
+            costs: Vec::new(),
+            assignment: Vec::new(),
+        }
+    }
+
+    pub fn assignment(&self) -> &[Option<usize>] {
+        &self.assignment
+    }
+
+    /// Coloca um agente novo no leilão e devolve os agentes cujo alvo mudou
+    /// (o novo e os que perderam o alvo para ele, direta ou indiretamente).
+    pub fn arrive(&mut self, costs: Vec<f32>) -> Vec<(usize, Option<usize>)> {
+        let agent = self.costs.len();
+        let worst = costs.iter().filter(|c| c.is_finite()).fold(0.0f32, |a, &c| a.max(c));
+        self.opt_out_cost = self.opt_out_cost.max(2.0 * worst + self.epsilon);
+        self.costs.push(costs);
+        self.assignment.push(None);
+
+        let before = self.assignment.clone();
+        let mut queue = vec![agent];
+        while let Some(bidder) = queue.pop() {
+            if let Some(evicted) = self.bid(bidder) {
+                queue.push(evicted);
+            }
+        }
+
+        (0..self.assignment.len())
+            .filter(|&a| before.get(a).copied().flatten() != self.assignment[a] || a == agent)
+            .map(|a| (a, self.assignment[a]))
+            .collect()
+    }
+
+    /// Um lance; devolve quem perdeu o alvo. Quando todos os alvos ficam mais caros
+    /// que ficar sem alvo (mais agentes que alvos, ou nenhum alvo alcançável),
+    /// o agente desiste. Isso também limita os preços, então o leilão sempre termina.
+    fn bid(&mut self, bidder: usize) -> Option<usize> {
+        let row = &self.costs[bidder];
+        let opt_out = -self.opt_out_cost;
+
+        let mut best: Option<(usize, f32)> = None;
+        let mut second = opt_out;
+        for (goal, &cost) in row.iter().enumerate() {
+            if !cost.is_finite() {
+                continue;
+            }
+            let value = -cost - self.prices[goal];
+            match best {
+                Some((_, best_value)) if value <= best_value => second = second.max(value),
+                _ => {
+                    if let Some((_, best_value)) = best {
+                        second = second.max(best_value);
+                    }
+                    best = Some((goal, value));
+                }
+            }
+        }
+        let (goal, best_value) = best?;
+        if best_value < opt_out {
+            return None;
+        }
+
+        let increment = best_value - second + self.epsilon;
+        self.prices[goal] += increment;
+
+        let evicted = self.owner[goal].replace(bidder);
+        self.assignment[bidder] = Some(goal);
+        if let Some(previous) = evicted {
+            self.assignment[previous] = None;
+        }
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [[f32; 4]; 4] = [[9.0, 2.0, 7.0, 8.0], [6.0, 4.0, 3.0, 7.0], [5.0, 8.0, 1.0, 8.0], [7.0, 6.0, 9.0, 4.0]];
+    /// Menos agentes (linhas) que alvos (colunas)
+    const WIDE: [[f32; 5]; 3] = [[12.0, 3.5, 8.0, 4.0, 10.0], [2.0, 7.0, 6.5, 3.0, 9.0], [5.0, 4.0, 1.5, 11.0, 2.5]];
+
+    fn matrix<const N: usize>(rows: &[[f32; N]]) -> Vec<Vec<f32>> {
+        rows.iter().map(|row| row.to_vec()).collect()
+    }
+
+    fn transpose(costs: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        (0..costs[0].len()).map(|goal| costs.iter().map(|row| row[goal]).collect()).collect()
+    }
+
+    /// Menor custo total testando todas as atribuições (uma coluna diferente para cada linha;
+    /// matriz com linhas ≤ colunas)
+    fn brute_force(costs: &[Vec<f32>], row: usize, used: &mut [bool]) -> f32 {
+        if row == costs.len() {
+            return 0.0;
+        }
+        let mut best = f32::INFINITY;
+        for goal in 0..used.len() {
+            if used[goal] {
+                continue;
+            }
+            used[goal] = true;
+            best = best.min(costs[row][goal] + brute_force(costs, row + 1, used));
+            used[goal] = false;
+        }
+        best
+    }
+
+    fn optimal_cost(costs: &[Vec<f32>]) -> f32 {
+        let narrow = if costs.len() <= costs[0].len() { costs.to_vec() } else { transpose(costs) };
+        brute_force(&narrow, 0, &mut vec![false; narrow[0].len()])
+    }
+
+    /// Cada alvo com no máximo um agente e, com todos os custos finitos, min(agentes, alvos) atribuídos
+    fn assert_one_to_one(costs: &[Vec<f32>], assignment: &[Option<usize>]) {
+        assert_eq!(assignment.len(), costs.len());
+        let mut goals: Vec<usize> = assignment.iter().flatten().copied().collect();
+        assert!(goals.iter().all(|&goal| goal < costs[0].len()));
+        goals.sort_unstable();
+        goals.dedup();
+        assert_eq!(goals.len(), assignment.iter().flatten().count(), "alvo repetido em {:?}", assignment);
+        assert_eq!(goals.len(), costs.len().min(costs[0].len()), "faltou atribuir em {:?}", assignment);
+    }
+
+    fn assert_optimal(costs: &[Vec<f32>]) {
+        let assignment = hungarian(costs);
+        assert_one_to_one(costs, &assignment);
+        let best = optimal_cost(costs);
+        assert!((total_cost(costs, &assignment) - best).abs() < 1e-3, "{:?} custa mais que {}", assignment, best);
+    }
+
+    #[test]
+    fn hungarian_matches_brute_force_on_a_square_matrix() {
+        assert_optimal(&matrix(&SQUARE));
+        // Empates e um alvo que o guloso pegaria primeiro
+        assert_optimal(&[vec![1.0, 2.0, 3.0], vec![1.0, 9.0, 9.0], vec![2.0, 2.0, 9.0]]);
+    }
+
+    #[test]
+    fn hungarian_matches_brute_force_with_fewer_agents_than_goals() {
+        assert_optimal(&matrix(&WIDE));
+    }
+
+    #[test]
+    fn hungarian_matches_brute_force_with_more_agents_than_goals() {
+        // Resolve a transposta: os agentes que sobram ficam sem alvo
+        let tall = transpose(&matrix(&WIDE));
+        assert_optimal(&tall);
+        assert_eq!(hungarian(&tall).iter().filter(|goal| goal.is_none()).count(), 2);
+    }
+
+    #[test]
+    fn greedy_and_auction_assign_one_agent_per_goal() {
+        for costs in [matrix(&SQUARE), matrix(&WIDE), transpose(&matrix(&WIDE))] {
+            for method in [AllocationMethod::Greedy, AllocationMethod::Auction] {
+                assert_one_to_one(&costs, &assign(&costs, method));
+            }
+            // O leilão fica a no máximo n·ε do ótimo
+            let auction = total_cost(&costs, &assign(&costs, AllocationMethod::Auction));
+            let slack = costs.len() as f32 * auction_epsilon(&costs);
+            assert!(auction <= optimal_cost(&costs) + slack + 1e-3, "leilão custou {}", auction);
+        }
+    }
+
+    #[test]
+    fn greedy_takes_the_cheapest_free_goal_in_arrival_order() {
+        let costs = [vec![1.0, 2.0, 3.0], vec![1.0, 9.0, 9.0], vec![2.0, 2.0, 9.0]];
+        assert_eq!(assign(&costs, AllocationMethod::Greedy), vec![Some(0), Some(1), Some(2)]);
+    }
+}
@@ -1,13 +1,7 @@
-use crate::agent_decorator::{
-    AgentComponent, DirectionDeviateDecorator, SpeedBoostDecorator, VisualAlertDecorator,
-};
+use crate::agent_decorator::AgentComponent;
 use crate::agent_factory::AgentFactory;
 use crate::grid::Grid;
-use crate::grid_adapter::{
-    HexagonalAdapter, RectangularCardinalAdapter, RectangularDiagonalAdapter,
-};
-use crate::path_manager::PathManager;
-use crate::pathfinding_adapter::a_star_with_adapter;
+use crate::path_manager::PathKey;
 use crate::{CELL_SIZE, GridMode};
 use macroquad::prelude::*;
 use std::fs::OpenOptions;
@@ -98,6 +92,12 @@ impl BenchmarkManager {
             .open(filename)
             .expect("Falha ao abrir arquivo de benchmark");
 
+        // Arquivo novo começa pelo cabeçalho (o nav_plotter reconhece o formato por ele)
+        let is_new = file.metadata().map(|m| m.len() == 0).unwrap_or(false);
+        if is_new && let Err(e) = writeln!(file, "test, time, fps, agents") {
+            eprintln!("Erro ao escrever no CSV: {}", e);
+        }
+
         // Escreve os dados: Teste, Tempo, FPS, Agentes
         for record in &self.frame_data {
             if let Err(e) = writeln!(
@@ -126,15 +126,8 @@ pub fn spawn_opposing_rows(
     next_id: &mut usize,
 ) {
     let rows_count = 1; // 1 Fileira de cada lado
-    spawn_lanes(
-        grid,
-        agents,
-        factory,
-        grid_mode,
-        next_id,
-        rows_count,
-        "1_Row_Vs_1_Row",
-    );
+    let count = 2 * rows_count * grid.height;
+    spawn_opposing_lanes(grid, agents, factory, grid_mode, next_id, count);
 }
 
 /// Cenário 2: Duas fileiras inteiras de cada lado
@@ -146,64 +139,42 @@ pub fn spawn_double_opposing_rows(
     next_id: &mut usize,
 ) {
     let rows_count = 2; // 2 Fileiras de cada lado
-    spawn_lanes(
-        grid,
-        agents,
-        factory,
-        grid_mode,
-        next_id,
-        rows_count,
-        "2_Rows_Vs_2_Rows",
-    );
+    let count = 2 * rows_count * grid.height;
+    spawn_opposing_lanes(grid, agents, factory, grid_mode, next_id, count);
 }
 
-/// Lógica interna para criar fileiras opostas
-fn spawn_lanes(
+/// Fileiras opostas com um número arbitrário de agentes.
+/// Metade sai da esquerda e metade da direita; as colunas são preenchidas
+/// de cima para baixo e, quando uma coluna enche, passa para a próxima.
+pub fn spawn_opposing_lanes(
     grid: &Grid,
     agents: &mut Vec<Box<dyn AgentComponent>>,
     factory: &dyn AgentFactory,
     grid_mode: GridMode,
     next_id: &mut usize,
-    rows_width: usize,
-    _scenario_tag: &str,
+    count: usize,
 ) {
-    let path_manager = PathManager::instance();
-    let mut spawned = 0;
+    let mut queries = Vec::new();
 
-    for y in 0..grid.height {
-        // Esquerda -> Direita (Azuis/Normais)
-        for x_off in 0..rows_width {
-            let start = (x_off, y);
-            let end = (grid.width - 1 - x_off, y);
-            spawn_single_agent(
-                grid,
-                agents,
-                factory,
-                grid_mode,
-                next_id,
-                start,
-                end,
-                path_manager,
-            );
-            spawned += 1;
-        }
+    for i in 0..count {
+        let slot = i / 2;
+        let x_off = slot / grid.height;
+        let y = slot % grid.height;
 
-        for x_off in 0..rows_width {
-            let start = (grid.width - 1 - x_off, y);
-            let end = (x_off, y);
-            spawn_single_agent(
-                grid,
-                agents,
-                factory,
-                grid_mode,
-                next_id,
-                start,
-                end,
-                path_manager,
-            );
-            spawned += 1;
+        // As duas metades não podem se sobrepor
+        if 2 * x_off + 1 >= grid.width {
+            break;
         }
+
+        queries.push(if i % 2 == 0 {
+            // Esquerda -> Direita
+            ((x_off, y), (grid.width - 1 - x_off, y))
+        } else {
+            // Direita -> Esquerda
+            ((grid.width - 1 - x_off, y), (x_off, y))
+        });
     }
+    let spawned = spawn_batch(grid, agents, factory, grid_mode, next_id, &queries);
     println!("Spawned {} agents in lanes.", spawned);
 }
 
@@ -216,97 +187,65 @@ pub fn spawn_random_scenario(
     next_id: &mut usize,
     count: usize,
 ) {
-    let path_manager = PathManager::instance();
     let mut spawned = 0;
     let max_attempts = count * 10;
     let mut attempts = 0;
 
     while spawned < count && attempts < max_attempts {
-        attempts += 1;
-        if let (Some(start), Some(end)) =
-            (grid.get_random_empty_cell(), grid.get_random_empty_cell())
-        {
-            if start != end {
-                if spawn_single_agent(
-                    grid,
-                    agents,
-                    factory,
-                    grid_mode,
-                    next_id,
-                    start,
-                    end,
-                    path_manager,
-                ) {
-                    spawned += 1;
-                }
+        // Sorteia de uma vez os pares que faltam, na mesma ordem em que um por vez os sortearia;
+        // o destino sai da componente do início, então todo par tem caminho
+        let mut queries = Vec::new();
+        while spawned + queries.len() < count && attempts < max_attempts {
+            attempts += 1;
+            if let Some((start, end)) = random_connected_pair(grid, grid_mode)
+                && start != end
+            {
+                queries.push((start, end));
             }
         }
+        spawned += spawn_batch(grid, agents, factory, grid_mode, next_id, &queries);
     }
     println!("Spawned {} random agents.", spawned);
 }
 
-/// Helper para criar um único agente com a stack completa de Decorators
-fn spawn_single_agent(
+/// Sorteia uma célula livre e um destino na mesma componente conexa
+pub fn random_connected_pair(grid: &Grid, grid_mode: GridMode) -> Option<((usize, usize), (usize, usize))> {
+    let start = grid.get_random_empty_cell()?;
+    let component = grid.component_of(start, grid_mode)?;
+    Some((start, grid.random_empty_cell_in_component(component, grid_mode)?))
+}
+
+/// Helper para criar os agentes de vários pares (início, destino) com a stack completa de Decorators.
+/// Devolve quantos foram criados.
+fn spawn_batch(
     grid: &Grid,
     agents: &mut Vec<Box<dyn AgentComponent>>,
     factory: &dyn AgentFactory,
     grid_mode: GridMode,
     next_id: &mut usize,
-    start: (usize, usize),
-    end: (usize, usize),
-    path_manager: &PathManager,
-) -> bool {
-    // 1. Calcula Caminho
-    let path_opt = path_manager.get_or_calculate(start, end, || match grid_mode {
-        GridMode::Hexagonal => {
-            let adapter = HexagonalAdapter::new(grid, true);
-            a_star_with_adapter(&adapter, start, end)
-        }
-        GridMode::Diagonal => {
-            let adapter = RectangularDiagonalAdapter::new(grid);
-            a_star_with_adapter(&adapter, start, end)
-        }
-        GridMode::Cardinal => {
-            let adapter = RectangularCardinalAdapter::new(grid);
-            a_star_with_adapter(&adapter, start, end)
-        }
-    });
-
-    if let Some(grid_path) = path_opt {
-        // 2. Converte para Pixels
-        let pixel_path: Vec<Vec2> = grid_path
-            .into_iter()
-            .map(|p| get_screen_pos(p, grid_mode))
-            .collect();
-
-        let start_pos = get_screen_pos(start, grid_mode);
-        let speed = 150.0; // Velocidade base
-
-        // 3. Cria Agente Base
-        let base_agent = factory.create_agent(start_pos, pixel_path, speed, *next_id);
+    queries: &[PathKey],
+) -> usize {
+    // 1. Calcula os caminhos em paralelo (com paradas de recarga se o tanque não bastar)
+    let routes = crate::plan_agent_routes(grid, queries, grid_mode, factory);
 
-        // 4. Aplica Decorators (Mesma lógica do Main)
-        // D1: Desvio de Direção
-        let direction_agent = DirectionDeviateDecorator::new(Box::new(base_agent));
-        // D2: Velocidade Reativa
-        let speed_agent = SpeedBoostDecorator::new(Box::new(direction_agent), 2.0); // 2.0 base boost
-        // D3: Alerta Visual
-        let mut visual_agent = VisualAlertDecorator::new(Box::new(speed_agent));
-
-        // Observer
-        visual_agent.add_observer(Box::new(crate::observer::RespawnHandler));
-
-        agents.push(Box::new(visual_agent));
-        *next_id += 1;
-        return true;
+    // 2. Cria os agentes na ordem dos pares, com a mesma stack de Decorators da janela
+    //    (os ids não dependem de qual thread terminou primeiro)
+    let mut spawned = 0;
+    for (&(start, _), route) in queries.iter().zip(routes) {
+        if let Some(route) = route {
+            let start_pos = get_screen_pos(start, grid_mode);
+            agents.push(crate::create_agent_stack(factory, start_pos, route, *next_id));
+            *next_id += 1;
+            spawned += 1;
+        }
     }
-    false
+    spawned
 }
 
 // Helper local para evitar dependência circular complexa com main
 fn get_screen_pos(pos: (usize, usize), mode: GridMode) -> Vec2 {
     match mode {
-        GridMode::Hexagonal => crate::hexagonal_renderer::hex_grid_to_screen(pos),
+        GridMode::Hexagonal => crate::hex::HexLayout::current().cell_center(pos),
         _ => vec2(
             pos.0 as f32 * CELL_SIZE + CELL_SIZE / 2.0,
             pos.1 as f32 * CELL_SIZE + CELL_SIZE / 2.0,
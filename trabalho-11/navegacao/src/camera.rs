@@ -0,0 +1,171 @@
+use crate::hex::HexLayout;
+use crate::{CELL_SIZE, GridMode, screen_to_grid};
+use macroquad::prelude::*;
+use std::ops::Range;
+
+/// Limites do zoom (pixels de tela por pixel do mundo)
+const MIN_ZOOM: f32 = 0.02;
+const MAX_ZOOM: f32 = 8.0;
+/// Fator de zoom a cada passo da roda do mouse
+const ZOOM_STEP: f32 = 1.15;
+/// Abaixo deste tamanho de célula na tela (px) as linhas da grade não são desenhadas
+pub const MIN_OUTLINE_CELL: f32 = 4.0;
+
+/// Tamanho do mapa em pixels do mundo
+pub fn world_size(grid_mode: GridMode, width: usize, height: usize) -> Vec2 {
+    match grid_mode {
+        GridMode::Hexagonal => HexLayout::current().world_size(width, height),
+        _ => vec2(width as f32 * CELL_SIZE, height as f32 * CELL_SIZE),
+    }
+}
+
+/// Parte do mapa que aparece na janela neste quadro: os renderizadores só desenham o que está aqui
+pub struct Viewport {
+    /// Retângulo visível, em pixels do mundo
+    pub rect: Rect,
+    /// Colunas e linhas do grid que encostam no retângulo visível
+    pub cols: Range<usize>,
+    pub rows: Range<usize>,
+    /// Pixels de tela por pixel do mundo
+    pub zoom: f32,
+}
+
+impl Viewport {
+    /// Um círculo (agente, raio de detecção) aparece na janela?
+    pub fn contains_circle(&self, center: Vec2, radius: f32) -> bool {
+        center.x + radius >= self.rect.x
+            && center.x - radius <= self.rect.x + self.rect.w
+            && center.y + radius >= self.rect.y
+            && center.y - radius <= self.rect.y + self.rect.h
+    }
+
+    /// As células estão grandes o bastante na tela para valer desenhar o contorno de cada uma?
+    pub fn show_outlines(&self, cell_size: f32) -> bool {
+        cell_size * self.zoom >= MIN_OUTLINE_CELL
+    }
+}
+
+/// Câmera da janela (`Camera2D`): roda do mouse dá zoom em volta do cursor, o botão do meio arrasta
+/// e `V` volta a enquadrar o mapa inteiro. Enquanto o usuário não mexe, o enquadramento acompanha
+/// o tamanho da janela e do mapa.
+pub struct ViewCamera {
+    /// Ponto do mundo no centro da janela
+    target: Vec2,
+    /// Pixels de tela por pixel do mundo
+    zoom: f32,
+    /// Arrasto em andamento: mouse (tela) e centro da câmera no início do arrasto
+    drag: Option<(Vec2, Vec2)>,
+    /// O usuário já moveu a câmera (deixa de enquadrar sozinha)
+    manual: bool,
+}
+
+impl ViewCamera {
+    pub fn new() -> Self {
+        Self { target: Vec2::ZERO, zoom: 1.0, drag: None, manual: false }
+    }
+
+    /// Volta a enquadrar o mapa inteiro (ex.: ao carregar um cenário)
+    pub fn reset(&mut self) {
+        self.manual = false;
+        self.drag = None;
+    }
+
+    /// Trata o mouse e o teclado; `input` é falso enquanto outra janela (seletor de cenários) usa o teclado
+    pub fn update(&mut self, world: Vec2, input: bool) {
+        if input {
+            if is_key_pressed(KeyCode::V) {
+                self.reset();
+            }
+
+            let mouse = Vec2::from(mouse_position());
+            let wheel = mouse_wheel().1;
+            if wheel != 0.0 {
+                // O ponto do mundo sob o cursor continua sob o cursor
+                let anchor = self.screen_to_world(mouse);
+                self.zoom = (self.zoom * ZOOM_STEP.powf(wheel.signum())).clamp(MIN_ZOOM, MAX_ZOOM);
+                self.target = anchor - (mouse - screen_center()) / self.zoom;
+                self.manual = true;
+            }
+
+            if is_mouse_button_pressed(MouseButton::Middle) {
+                self.drag = Some((mouse, self.target));
+            }
+            if let Some((from, target)) = self.drag {
+                if is_mouse_button_down(MouseButton::Middle) {
+                    self.target = target - (mouse - from) / self.zoom;
+                    self.manual = true;
+                } else {
+                    self.drag = None;
+                }
+            }
+        }
+
+        if !self.manual {
+            self.fit(world);
+        }
+    }
+
+    /// Mapa inteiro na janela, preso ao canto superior esquerdo como sem câmera;
+    /// mapas pequenos ficam em escala 1:1
+    fn fit(&mut self, world: Vec2) {
+        let screen = vec2(screen_width(), screen_height());
+        self.zoom = (screen.x / world.x).min(screen.y / world.y).clamp(MIN_ZOOM, 1.0);
+        self.target = screen / (2.0 * self.zoom);
+    }
+
+    /// Câmera do macroquad para desenhar o mundo (o y cresce para baixo, como na tela)
+    pub fn camera(&self) -> Camera2D {
+        Camera2D {
+            target: self.target,
+            zoom: vec2(2.0 * self.zoom / screen_width(), -2.0 * self.zoom / screen_height()),
+            ..Default::default()
+        }
+    }
+
+    /// Posição da tela (ex.: o mouse) no mundo
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.camera().screen_to_world(point)
+    }
+
+    /// Célula sob o mouse, se ele estiver dentro do mapa
+    pub fn mouse_cell(&self, grid_mode: GridMode, width: usize, height: usize) -> Option<(usize, usize)> {
+        let world = self.screen_to_world(Vec2::from(mouse_position()));
+        if world.x < 0.0 || world.y < 0.0 {
+            return None;
+        }
+        let (x, y) = screen_to_grid(world.x, world.y, grid_mode);
+        (x < width && y < height).then_some((x, y))
+    }
+
+    /// O que aparece na janela neste quadro
+    pub fn viewport(&self, grid_mode: GridMode, width: usize, height: usize) -> Viewport {
+        let min = self.screen_to_world(Vec2::ZERO);
+        let max = self.screen_to_world(vec2(screen_width(), screen_height()));
+        let rect = Rect::new(min.x, min.y, max.x - min.x, max.y - min.y);
+
+        // Faixa de células que encostam no retângulo; no hexagonal, uma célula de folga
+        // por causa do deslocamento das linhas (ou colunas) ímpares e das pontas dos hexágonos
+        let (cell_w, cell_h, margin) = match grid_mode {
+            GridMode::Hexagonal => {
+                let layout = HexLayout::current();
+                (layout.column_spacing(), layout.row_spacing(), 1.0)
+            }
+            _ => (CELL_SIZE, CELL_SIZE, 0.0),
+        };
+        let range = |from: f32, to: f32, size: f32, count: usize| {
+            let start = (from / size - margin).floor().max(0.0) as usize;
+            let end = ((to / size + margin).ceil().max(0.0) as usize).min(count);
+            start.min(end)..end
+        };
+        Viewport {
+            cols: range(rect.x, rect.x + rect.w, cell_w, width),
+            rows: range(rect.y, rect.y + rect.h, cell_h, height),
+            rect,
+            zoom: self.zoom,
+        }
+    }
+}
+
+fn screen_center() -> Vec2 {
+    vec2(screen_width(), screen_height()) / 2.0
+}
@@ -0,0 +1,391 @@
+use crate::agent_decorator::AgentComponent;
+use crate::debug_overlay::DebugCapture;
+use crate::observer::AgentEvent;
+use crate::pheromone::{self, PheromoneManager};
+use crate::grid::Grid;
+use crate::obstacle::ObstacleMap;
+use crate::rvo::{self, NeighborGrid, RvoAgent, RvoManager, RvoStates};
+use crate::GridMode;
+use macroquad::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Sem comunicação: quanto tempo (s) à frente o agente testa cada velocidade
+const STATIC_VO_HORIZON: f32 = 1.0;
+/// Sem comunicação: espera aleatória quando nenhuma direção está livre
+const BACKOFF_MIN: f32 = 0.2;
+const BACKOFF_MAX: f32 = 0.8;
+
+/// Strategy: cada método de prevenção de colisão recebe a velocidade
+/// preferida de cada agente (a que segue o caminho A*) e devolve a velocidade
+/// que ele realmente vai usar neste passo. O resto da simulação (agentes,
+/// Decorators, Commands, sensores) é o mesmo para todos os métodos.
+pub trait CollisionAvoidanceStrategy {
+    /// Nome curto usado em arquivos, no HUD e na linha de comando
+    fn name(&self) -> &'static str;
+
+    /// Calcula a velocidade segura de cada agente (mesma ordem de `agents`)
+    fn compute_velocities(
+        &mut self,
+        agents: &[Box<dyn AgentComponent>],
+        preferred: &[Vec2],
+        grid: &Grid,
+        grid_mode: GridMode,
+        dt: f32,
+    ) -> Vec<Vec2>;
+
+    /// Descarta o estado interno (ex.: rastros) ao reiniciar a simulação
+    fn reset(&mut self) {}
+}
+
+/// Métodos disponíveis, usado para escolher a Strategy na linha de comando e no teclado
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum AvoidanceMode {
+    Rvo,       // Comunicação direta: velocidades trocadas entre vizinhos
+    Pheromone, // Comunicação indireta: rastro de feromônio no grid
+    None,      // Sem comunicação: só enxerga posições dentro do raio de detecção
+    Off,       // Sem prevenção: cada agente segue o próprio caminho A*
+}
+
+impl AvoidanceMode {
+    /// Converte o nome usado na linha de comando
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "rvo" | "direta" => Some(AvoidanceMode::Rvo),
+            "pheromone" | "indireta" => Some(AvoidanceMode::Pheromone),
+            "none" | "sem" => Some(AvoidanceMode::None),
+            "off" => Some(AvoidanceMode::Off),
+            _ => None,
+        }
+    }
+
+    /// Nome curto usado em arquivos e no HUD
+    pub fn name(&self) -> &'static str {
+        match self {
+            AvoidanceMode::Rvo => "rvo",
+            AvoidanceMode::Pheromone => "pheromone",
+            AvoidanceMode::None => "none",
+            AvoidanceMode::Off => "off",
+        }
+    }
+
+    /// Próximo método (tecla de troca na janela)
+    pub fn next(&self) -> Self {
+        match self {
+            AvoidanceMode::Rvo => AvoidanceMode::Pheromone,
+            AvoidanceMode::Pheromone => AvoidanceMode::None,
+            AvoidanceMode::None => AvoidanceMode::Off,
+            AvoidanceMode::Off => AvoidanceMode::Rvo,
+        }
+    }
+
+    /// Factory: cria a Strategy correspondente para um grid do tamanho dado
+    pub fn create_strategy(&self, width: usize, height: usize) -> Box<dyn CollisionAvoidanceStrategy> {
+        match self {
+            AvoidanceMode::Rvo => Box::new(RvoStrategy::new()),
+            AvoidanceMode::Pheromone => Box::new(PheromoneStrategy::new(width, height)),
+            AvoidanceMode::None => Box::new(NoCommunicationStrategy::new()),
+            AvoidanceMode::Off => Box::new(NoAvoidanceStrategy),
+        }
+    }
+}
+
+// --- STRATEGY 1: Comunicação Direta (RVO) ---
+/// Além dos vizinhos, o RVO trata o contorno dos obstáculos do grid como restrições estáticas.
+/// O contorno fica guardado e só é refeito quando o grid muda.
+pub struct RvoStrategy {
+    obstacles: Option<ObstacleMap>,
+}
+
+impl RvoStrategy {
+    pub fn new() -> Self {
+        Self { obstacles: None }
+    }
+
+    /// Velocidade segura de cada agente a partir dos estados anunciados (mesma ordem de `states`).
+    /// Os estados não mudam durante o cálculo, então cada agente é resolvido em paralelo no pool
+    /// atual do rayon; o resultado é o mesmo com qualquer número de threads.
+    pub fn velocities_for(&mut self, states: &RvoStates, grid: &Grid, grid_mode: GridMode, dt: f32) -> Vec<Vec2> {
+        if !self.obstacles.as_ref().is_some_and(|map| map.is_current(grid, grid_mode)) {
+            self.obstacles = Some(ObstacleMap::build(grid, grid_mode));
+        }
+        let obstacles = self.obstacles.as_ref().expect("contorno dos obstáculos");
+        let neighbor_grid = NeighborGrid::build(&states.positions);
+
+        // Com a camada de depuração ligada, as candidatas de cada agente ficam guardadas
+        let capture = DebugCapture::instance();
+        let captures = capture.captures_agents();
+        let (velocities, captured): (Vec<Vec2>, Vec<Vec<(Vec2, f32)>>) = (0..states.len())
+            .into_par_iter()
+            .map_init(Vec::new, |neighbors, i| {
+                // Nenhum candidato é mais rápido que a velocidade preferida, e o passo
+                // não chega a uma velocidade maior que a atual e a do candidato
+                let speed = states.pref_velocities[i].length().min(states.max_speeds[i]);
+                let edges = if speed > 0.0 {
+                    let reach_speed = speed.max(states.velocities[i].length());
+                    let horizon = RvoManager::obstacle_horizon(reach_speed, states.max_accelerations[i], dt);
+                    let range = states.radii[i] + reach_speed * rvo::OBSTACLE_LOOKAHEAD.max(horizon);
+                    obstacles.edges_near(states.positions[i], range)
+                } else {
+                    Vec::new()
+                };
+                neighbor_grid.neighbors(states, i, neighbors);
+                if captures {
+                    let scored = RvoManager::score_candidates(states, i, neighbors, &edges, dt);
+                    (RvoManager::best_velocity(&scored), scored)
+                } else {
+                    (RvoManager::choose_velocity(states, i, neighbors, &edges, dt), Vec::new())
+                }
+            })
+            .unzip();
+        if captures {
+            capture.record_candidates(captured);
+        }
+        velocities
+    }
+}
+
+impl CollisionAvoidanceStrategy for RvoStrategy {
+    fn name(&self) -> &'static str {
+        AvoidanceMode::Rvo.name()
+    }
+
+    fn compute_velocities(
+        &mut self,
+        agents: &[Box<dyn AgentComponent>],
+        preferred: &[Vec2],
+        grid: &Grid,
+        grid_mode: GridMode,
+        dt: f32,
+    ) -> Vec<Vec2> {
+        // Cada agente "anuncia" posição, velocidade e intenção aos vizinhos
+        let mut states = RvoStates::with_capacity(agents.len());
+        for (a, &pref_velocity) in agents.iter().zip(preferred) {
+            states.push(RvoAgent {
+                id: a.get_id(),
+                pos: a.get_pos(),
+                velocity: a.get_velocity(),
+                radius: a.get_physical_radius(),
+                max_speed: a.get_max_speed(),
+                max_acceleration: a.get_max_acceleration(),
+                pref_velocity,
+            });
+        }
+        self.velocities_for(&states, grid, grid_mode, dt)
+    }
+
+    fn reset(&mut self) {
+        self.obstacles = None;
+    }
+}
+
+// --- STRATEGY 2: Comunicação Indireta (Feromônios) ---
+pub struct PheromoneStrategy;
+
+impl PheromoneStrategy {
+    pub fn new(width: usize, height: usize) -> Self {
+        PheromoneManager::instance().init(width, height);
+        Self
+    }
+}
+
+impl CollisionAvoidanceStrategy for PheromoneStrategy {
+    fn name(&self) -> &'static str {
+        AvoidanceMode::Pheromone.name()
+    }
+
+    fn compute_velocities(
+        &mut self,
+        agents: &[Box<dyn AgentComponent>],
+        preferred: &[Vec2],
+        _grid: &Grid,
+        grid_mode: GridMode,
+        dt: f32,
+    ) -> Vec<Vec2> {
+        let pheromones = PheromoneManager::instance();
+
+        // 0. Decaimento dos Feromônios
+        pheromones.update(dt);
+
+        // 1. ESCRITA: cada agente marca as células que o corpo cobre
+        for agent in agents.iter().filter(|a| !a.is_finished()) {
+            pheromones.deposit(agent.get_pos(), agent.get_physical_radius(), grid_mode);
+        }
+
+        // 2. LEITURA: não entra em célula marcada por outro agente
+        agents
+            .iter()
+            .zip(preferred)
+            .map(|(agent, &pref)| {
+                if pref == Vec2::ZERO {
+                    return Vec2::ZERO;
+                }
+
+                // Olha para onde a borda do corpo vai chegar
+                let pos = agent.get_pos();
+                let radius = agent.get_physical_radius();
+                let ahead = pos + pref.normalize() * radius;
+                let own_cells = pheromone::footprint(pos, radius, grid_mode);
+                let ahead_cell = crate::screen_to_grid(ahead.x, ahead.y, grid_mode);
+
+                // Só checa bloqueio fora das células que o próprio corpo cobre, isso evita que o agente se bloqueie com seu próprio rastro
+                if !own_cells.contains(&ahead_cell) && pheromones.is_blocked(ahead_cell.0, ahead_cell.1) {
+                    // Célula ocupada: Aciona o ProximityAlert e não move
+                    agent.notify(AgentEvent::ProximityAlert(9999));
+                    return Vec2::ZERO;
+                }
+
+                pref
+            })
+            .collect()
+    }
+
+    fn reset(&mut self) {
+        PheromoneManager::instance().clear();
+    }
+}
+
+// --- STRATEGY 3: Sem Comunicação ---
+/// Nenhuma informação é trocada: cada agente só enxerga as posições dos agentes
+/// que estão dentro do seu raio de detecção (sem velocidades e sem grid compartilhado).
+/// Regra local: obstáculo de velocidade supondo que os outros estão parados;
+/// se nenhuma direção estiver livre, espera um tempo aleatório (back-off) e tenta de novo.
+pub struct NoCommunicationStrategy {
+    /// Tempo restante de espera de cada agente (por id)
+    waiting: HashMap<usize, f32>,
+}
+
+impl NoCommunicationStrategy {
+    pub fn new() -> Self {
+        Self {
+            waiting: HashMap::new(),
+        }
+    }
+
+    /// Verifica se a velocidade leva o agente a tocar algum dos corpos vistos,
+    /// tratando cada um como parado na posição atual
+    fn is_free(pos: Vec2, radius: f32, velocity: Vec2, seen: &[(usize, Vec2, f32)]) -> bool {
+        seen.iter().all(|&(_, other_pos, other_radius)| {
+            let combined_radius = radius + other_radius;
+            let rel_pos = other_pos - pos;
+
+            // Já sobrepostos: só aceita velocidades que afastam
+            if rel_pos.length() < combined_radius {
+                return rel_pos.dot(velocity) <= 0.0;
+            }
+
+            let speed_sq = velocity.length_squared();
+            if speed_sq < 0.0001 {
+                return true;
+            }
+
+            // Ponto mais próximo da trajetória dentro do horizonte
+            let t = (rel_pos.dot(velocity) / speed_sq).clamp(0.0, STATIC_VO_HORIZON);
+            (rel_pos - velocity * t).length() >= combined_radius
+        })
+    }
+}
+
+impl CollisionAvoidanceStrategy for NoCommunicationStrategy {
+    fn name(&self) -> &'static str {
+        AvoidanceMode::None.name()
+    }
+
+    fn compute_velocities(
+        &mut self,
+        agents: &[Box<dyn AgentComponent>],
+        preferred: &[Vec2],
+        _grid: &Grid,
+        _grid_mode: GridMode,
+        dt: f32,
+    ) -> Vec<Vec2> {
+        agents
+            .iter()
+            .zip(preferred)
+            .map(|(agent, &pref)| {
+                let id = agent.get_id();
+
+                // Back-off: continua esperando
+                if let Some(remaining) = self.waiting.get_mut(&id) {
+                    *remaining -= dt;
+                    if *remaining > 0.0 {
+                        return Vec2::ZERO;
+                    }
+                    self.waiting.remove(&id);
+                }
+
+                if pref == Vec2::ZERO {
+                    return Vec2::ZERO;
+                }
+
+                // Percepção: só posições dentro do raio de detecção
+                let pos = agent.get_pos();
+                let radius = agent.get_physical_radius();
+                let detection = agent.get_detection_radius();
+                let seen: Vec<(usize, Vec2, f32)> = agents
+                    .iter()
+                    .filter(|o| o.get_id() != id && !o.is_finished())
+                    .map(|o| (o.get_id(), o.get_pos(), o.get_physical_radius()))
+                    .filter(|&(_, other_pos, other_radius)| pos.distance(other_pos) < detection + other_radius)
+                    .collect();
+
+                if seen.is_empty() {
+                    return pref;
+                }
+
+                // Candidatos em ordem de desvio da intenção original
+                let speed = pref.length();
+                let base_angle = pref.y.atan2(pref.x);
+                let mut candidates = vec![pref];
+                for deg in [20.0f32, -20.0, 45.0, -45.0, 70.0, -70.0, 90.0, -90.0] {
+                    let angle = base_angle + deg.to_radians();
+                    let dir = vec2(angle.cos(), angle.sin());
+                    candidates.push(dir * speed);
+                    candidates.push(dir * (speed * 0.5));
+                }
+
+                if let Some(&velocity) = candidates
+                    .iter()
+                    .find(|&&v| Self::is_free(pos, radius, v, &seen))
+                {
+                    return velocity;
+                }
+
+                // Nenhuma direção livre: espera um tempo aleatório
+                self.waiting.insert(id, rand::gen_range(BACKOFF_MIN, BACKOFF_MAX));
+                let nearest = seen
+                    .iter()
+                    .min_by(|a, b| pos.distance(a.1).total_cmp(&pos.distance(b.1)))
+                    .map(|&(other_id, _, _)| other_id)
+                    .unwrap_or(id);
+                agent.notify(AgentEvent::ProximityAlert(nearest));
+                Vec2::ZERO
+            })
+            .collect()
+    }
+
+    fn reset(&mut self) {
+        self.waiting.clear();
+    }
+}
+
+// --- Sem prevenção (linha de base dos benchmarks) ---
+/// Cada agente segue a velocidade preferida e atravessa os outros.
+pub struct NoAvoidanceStrategy;
+
+impl CollisionAvoidanceStrategy for NoAvoidanceStrategy {
+    fn name(&self) -> &'static str {
+        AvoidanceMode::Off.name()
+    }
+
+    fn compute_velocities(
+        &mut self,
+        _agents: &[Box<dyn AgentComponent>],
+        preferred: &[Vec2],
+        _grid: &Grid,
+        _grid_mode: GridMode,
+        _dt: f32,
+    ) -> Vec<Vec2> {
+        preferred.to_vec()
+    }
+}
@@ -0,0 +1,763 @@
+use crate::agent_decorator::{AgentComponent, AgentSnapshot};
+use crate::energy::Route;
+use crate::grid::{CellType, Grid};
+use crate::path_manager::PathManager;
+use crate::replay::ReplayRecorder;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Quantos ticks ficam guardados para undo/redo (os mais antigos são descartados)
+const MAX_HISTORY_TICKS: usize = 600;
+/// A cada quantos ticks é guardado um checkpoint do estado dos agentes
+const CHECKPOINT_INTERVAL: u64 = 300;
+/// Quantos checkpoints ficam guardados (os mais antigos são descartados)
+const MAX_CHECKPOINTS: usize = 32;
+
+/// A interface Command
+pub trait Command {
+    /// Executa a ação (altera o estado do jogo)
+    fn execute(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid);
+    /// Desfaz a ação (restaura o estado anterior)
+    fn undo(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid);
+    /// Forma serializável do comando, gravada no log de replay
+    fn record(&self) -> CommandRecord;
+    /// Quando o comando foi criado (relógio do sistema, em segundos), se ele guarda isso
+    fn timestamp(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// Comandos como são gravados em disco
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandRecord {
+    Move {
+        agent_id: usize,
+        from: [f32; 2],
+        to: [f32; 2],
+        #[serde(default = "legacy_move_energy")]
+        energy: f32,
+    },
+    Composite {
+        commands: Vec<CommandRecord>,
+    },
+    PaintCells {
+        cells: Vec<(usize, usize)>,
+        #[serde(default = "default_paint_type")]
+        cell_type: CellType,
+    },
+    EraseCells {
+        cells: Vec<(usize, usize)>,
+    },
+    AssignRoute {
+        agent_id: usize,
+        waypoints: Vec<[f32; 2]>,
+        #[serde(default)]
+        charging_stops: Vec<usize>,
+    },
+    SetFinished {
+        agent_id: usize,
+        finished: bool,
+    },
+}
+
+// Logs antigos: cada passo gastava 1.0 e toda pincelada era de obstáculos
+fn legacy_move_energy() -> f32 {
+    1.0
+}
+fn default_paint_type() -> CellType {
+    CellType::Obstacle
+}
+
+impl CommandRecord {
+    /// Recria o comando a partir do registro
+    pub fn into_command(self) -> Box<dyn Command> {
+        match self {
+            CommandRecord::Move {
+                agent_id,
+                from,
+                to,
+                energy,
+            } => Box::new(MoveCommand::new(
+                agent_id,
+                vec2(from[0], from[1]),
+                vec2(to[0], to[1]),
+                energy,
+            )),
+            CommandRecord::Composite { commands } => Box::new(CompositeCommand::new(
+                commands
+                    .into_iter()
+                    .map(CommandRecord::into_command)
+                    .collect(),
+            )),
+            CommandRecord::PaintCells { cells, cell_type } => {
+                Box::new(PaintCellsCommand::new(cells, cell_type))
+            }
+            CommandRecord::EraseCells { cells } => Box::new(EraseCellsCommand::new(cells)),
+            CommandRecord::AssignRoute {
+                agent_id,
+                waypoints,
+                charging_stops,
+            } => {
+                let waypoints = waypoints.into_iter().map(|p| vec2(p[0], p[1])).collect();
+                Box::new(AssignRouteCommand::new(
+                    agent_id,
+                    Route {
+                        waypoints,
+                        charging_stops,
+                    },
+                ))
+            }
+            CommandRecord::SetFinished { agent_id, finished } => {
+                Box::new(SetFinishedCommand::new(agent_id, finished))
+            }
+        }
+    }
+}
+
+/// Comando Concreto: Mover Agente
+/// `energy` é o gasto do passo (negativo quando recarrega); o tanque fica entre
+/// zero e a capacidade, por isso o undo volta ao valor anterior guardado.
+pub struct MoveCommand {
+    agent_id: usize,
+    old_pos: Vec2,
+    new_pos: Vec2,
+    energy: f32,
+    previous_fuel: f32,
+    timestamp: f64,
+}
+
+impl MoveCommand {
+    pub fn new(agent_id: usize, old_pos: Vec2, new_pos: Vec2, energy: f32) -> Self {
+        Self {
+            agent_id,
+            old_pos,
+            new_pos,
+            energy,
+            previous_fuel: 0.0,
+            // Mesmo relógio do `get_time`, que não existe sem janela (modo headless)
+            timestamp: miniquad::date::now(),
+        }
+    }
+}
+
+impl Command for MoveCommand {
+    fn execute(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, _grid: &mut Grid) {
+        // Verifica se o agente ainda existe (proteção contra índices inválidos)
+        // e se o ID bate (caso a lista tenha mudado)
+        if let Some(agent) = agents.get_mut(self.agent_id)
+            && agent.get_id() == self.agent_id
+        {
+            agent.set_pos(self.new_pos);
+            self.previous_fuel = agent.get_fuel();
+            agent.consume_fuel(self.energy);
+        }
+    }
+
+    fn undo(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, _grid: &mut Grid) {
+        if let Some(agent) = agents.get_mut(self.agent_id)
+            && agent.get_id() == self.agent_id
+        {
+            agent.set_pos(self.old_pos);
+            let fuel = agent.get_fuel();
+            agent.restore_fuel(self.previous_fuel - fuel);
+        }
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Move {
+            agent_id: self.agent_id,
+            from: [self.old_pos.x, self.old_pos.y],
+            to: [self.new_pos.x, self.new_pos.y],
+            energy: self.energy,
+        }
+    }
+
+    fn timestamp(&self) -> Option<f64> {
+        Some(self.timestamp)
+    }
+}
+
+/// Troca o tipo de várias células e devolve o tipo anterior de cada uma
+fn set_cells(grid: &mut Grid, cells: &[(usize, usize)], cell_type: CellType) -> Vec<CellType> {
+    cells
+        .iter()
+        .map(|&(x, y)| {
+            let previous = grid.get_cell(x, y);
+            grid.set_cell(x, y, cell_type);
+            previous.unwrap_or(CellType::Empty)
+        })
+        .collect()
+}
+
+/// Volta cada célula para o tipo que tinha antes do comando
+fn restore_cells(grid: &mut Grid, cells: &[(usize, usize)], previous: &[CellType]) {
+    for (&(x, y), &cell_type) in cells.iter().zip(previous).rev() {
+        grid.set_cell(x, y, cell_type);
+    }
+}
+
+/// Comando Concreto: Pintar Obstáculos ou Estações de Recarga (uma pincelada inteira)
+pub struct PaintCellsCommand {
+    cells: Vec<(usize, usize)>,
+    cell_type: CellType,
+    previous: Vec<CellType>,
+}
+
+impl PaintCellsCommand {
+    pub fn new(cells: Vec<(usize, usize)>, cell_type: CellType) -> Self {
+        Self {
+            cells,
+            cell_type,
+            previous: Vec::new(),
+        }
+    }
+}
+
+impl Command for PaintCellsCommand {
+    fn execute(&mut self, _agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        self.previous = set_cells(grid, &self.cells, self.cell_type);
+        if self.cell_type == CellType::Obstacle {
+            // Só os caminhos que passam pelas células novas deixam de valer
+            PathManager::instance().invalidate_cells(&self.cells);
+        } else {
+            // Uma estação pintada sobre um obstáculo libera a célula
+            PathManager::instance().clear_cache();
+        }
+    }
+
+    fn undo(&mut self, _agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        restore_cells(grid, &self.cells, &self.previous);
+        // Células liberadas podem encurtar qualquer caminho
+        PathManager::instance().clear_cache();
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::PaintCells {
+            cells: self.cells.clone(),
+            cell_type: self.cell_type,
+        }
+    }
+}
+
+/// Comando Concreto: Apagar Obstáculos (uma pincelada inteira)
+pub struct EraseCellsCommand {
+    cells: Vec<(usize, usize)>,
+    previous: Vec<CellType>,
+}
+
+impl EraseCellsCommand {
+    pub fn new(cells: Vec<(usize, usize)>) -> Self {
+        Self {
+            cells,
+            previous: Vec::new(),
+        }
+    }
+}
+
+impl Command for EraseCellsCommand {
+    fn execute(&mut self, _agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        self.previous = set_cells(grid, &self.cells, CellType::Empty);
+        PathManager::instance().clear_cache();
+    }
+
+    fn undo(&mut self, _agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        restore_cells(grid, &self.cells, &self.previous);
+        PathManager::instance().invalidate_cells(&self.cells);
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::EraseCells {
+            cells: self.cells.clone(),
+        }
+    }
+}
+
+/// Comando Concreto: Mandar um agente para outro alvo (alocação de tarefas).
+/// Guarda o trecho que faltava do caminho antigo para o undo.
+pub struct AssignRouteCommand {
+    agent_id: usize,
+    route: Route,
+    previous: Option<Route>,
+}
+
+impl AssignRouteCommand {
+    pub fn new(agent_id: usize, route: Route) -> Self {
+        Self {
+            agent_id,
+            route,
+            previous: None,
+        }
+    }
+}
+
+impl Command for AssignRouteCommand {
+    fn execute(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, _grid: &mut Grid) {
+        if let Some(agent) = agents.get_mut(self.agent_id)
+            && agent.get_id() == self.agent_id
+        {
+            self.previous = Some(agent.replace_route(self.route.clone()));
+        }
+    }
+
+    fn undo(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, _grid: &mut Grid) {
+        if let Some(agent) = agents.get_mut(self.agent_id)
+            && agent.get_id() == self.agent_id
+            && let Some(previous) = self.previous.take()
+        {
+            agent.replace_route(previous);
+        }
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::AssignRoute {
+            agent_id: self.agent_id,
+            waypoints: self.route.waypoints.iter().map(|p| [p.x, p.y]).collect(),
+            charging_stops: self.route.charging_stops.clone(),
+        }
+    }
+}
+
+/// Comando Concreto: Marcar a chegada (ou a saída) de um agente.
+/// A chegada acontece no `update` do agente, fora da fila; o `CommandManager` cria este
+/// comando para que o tick em que só houve chegadas entre no histórico e no log de replay.
+pub struct SetFinishedCommand {
+    agent_id: usize,
+    finished: bool,
+}
+
+impl SetFinishedCommand {
+    pub fn new(agent_id: usize, finished: bool) -> Self {
+        Self { agent_id, finished }
+    }
+
+    fn apply(&self, agents: &mut [Box<dyn AgentComponent>], finished: bool) {
+        if let Some(agent) = agents.get_mut(self.agent_id)
+            && agent.get_id() == self.agent_id
+        {
+            let mut state = agent.snapshot(false);
+            state.agent.set_finished(finished);
+            agent.restore(state);
+        }
+    }
+}
+
+impl Command for SetFinishedCommand {
+    fn execute(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, _grid: &mut Grid) {
+        self.apply(agents, self.finished);
+    }
+
+    fn undo(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, _grid: &mut Grid) {
+        self.apply(agents, !self.finished);
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::SetFinished {
+            agent_id: self.agent_id,
+            finished: self.finished,
+        }
+    }
+}
+
+/// Comando Composto: tudo que foi executado em um tick da simulação.
+/// Desfazer um tick inteiro é o que faz sentido para o usuário;
+/// um `MoveCommand` sozinho é o passo de um agente em um frame.
+pub struct CompositeCommand {
+    commands: Vec<Box<dyn Command>>,
+}
+
+impl CompositeCommand {
+    pub fn new(commands: Vec<Box<dyn Command>>) -> Self {
+        Self { commands }
+    }
+}
+
+impl Command for CompositeCommand {
+    fn execute(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        for cmd in self.commands.iter_mut() {
+            cmd.execute(agents, grid);
+        }
+    }
+
+    fn undo(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        // Desfaz na ordem inversa
+        for cmd in self.commands.iter_mut().rev() {
+            cmd.undo(agents, grid);
+        }
+    }
+
+    fn record(&self) -> CommandRecord {
+        CommandRecord::Composite {
+            commands: self.commands.iter().map(|c| c.record()).collect(),
+        }
+    }
+
+    fn timestamp(&self) -> Option<f64> {
+        self.commands.iter().find_map(|c| c.timestamp())
+    }
+}
+
+/// Um tick do histórico e o estado dos agentes do outro lado dele:
+/// antes do tick no histórico de undo, depois dele na pilha de redo.
+/// Os comandos só voltam posição e combustível; o resto (waypoint, chegada, velocidade,
+/// recarga, decorators) volta pelo estado salvo. Fica vazio nas ações fora do tick.
+struct TickEntry {
+    commands: CompositeCommand,
+    agents: Vec<AgentSnapshot>,
+}
+
+/// Estado de cada agente (por id), sem o caminho
+fn capture_agents(agents: &[Box<dyn AgentComponent>], count: usize) -> Vec<AgentSnapshot> {
+    agents
+        .iter()
+        .take(count)
+        .map(|a| a.snapshot(false))
+        .collect()
+}
+
+fn restore_agents(agents: &mut [Box<dyn AgentComponent>], states: Vec<AgentSnapshot>) {
+    for (agent, state) in agents.iter_mut().zip(states) {
+        agent.restore(state);
+    }
+}
+
+/// Estado salvo dos agentes e do grid, para voltar além do histórico guardado
+struct Checkpoint {
+    position: u64,              // Quantos ticks tinham sido aplicados
+    agents: Vec<AgentSnapshot>, // Estado de cada agente (por id), com o caminho
+    revision: u64,              // Revisão do grid quando as células foram copiadas
+    cells: Rc<Vec<CellType>>, // Células do grid, linha por linha (pinceladas também entram no histórico)
+}
+
+impl Checkpoint {
+    /// Se o grid não mudou desde o checkpoint anterior, as células são compartilhadas com ele
+    fn capture(
+        position: u64,
+        agents: &[Box<dyn AgentComponent>],
+        grid: &Grid,
+        previous: Option<&Checkpoint>,
+    ) -> Self {
+        let cells = match previous {
+            Some(previous) if previous.revision == grid.revision() => Rc::clone(&previous.cells),
+            _ => Rc::new(grid.cells().to_vec()),
+        };
+        Self {
+            position,
+            agents: agents.iter().map(|a| a.snapshot(true)).collect(),
+            revision: grid.revision(),
+            cells,
+        }
+    }
+
+    fn restore(&self, agents: &mut [Box<dyn AgentComponent>], grid: &mut Grid) {
+        for (agent, state) in agents.iter_mut().zip(&self.agents) {
+            agent.restore(state.clone());
+        }
+        if grid.cells() != self.cells.as_slice() {
+            grid.replace_cells(self.cells.as_ref().clone());
+            PathManager::instance().clear_cache();
+        }
+    }
+}
+
+/// Gerenciador de Comandos (Invoker)
+pub struct CommandManager {
+    history: VecDeque<TickEntry>, // Buffer circular de undo (um item por tick)
+    redo: Vec<TickEntry>,         // Pilha de redo
+    checkpoints: VecDeque<Checkpoint>, // Checkpoints periódicos
+    position: u64,                // Ticks aplicados (desfazer diminui)
+    queue: VecDeque<Box<dyn Command>>, // Fila de execução
+    tick_start: Vec<AgentSnapshot>, // Estado dos agentes no começo do tick atual
+    tick: u64,                    // Quantas vezes a fila foi processada
+    recorder: Option<ReplayRecorder>, // Log de replay (quando gravando)
+}
+
+impl CommandManager {
+    pub fn new() -> Self {
+        Self {
+            history: VecDeque::new(),
+            redo: Vec::new(),
+            checkpoints: VecDeque::new(),
+            position: 0,
+            queue: VecDeque::new(),
+            tick_start: Vec::new(),
+            tick: 0,
+            recorder: None,
+        }
+    }
+
+    /// Passa a gravar tudo que for executado em um log de replay
+    pub fn start_recording(&mut self, recorder: ReplayRecorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Encerra a gravação (se houver) e fecha o arquivo
+    pub fn stop_recording(&mut self) -> std::io::Result<()> {
+        match self.recorder.take() {
+            Some(recorder) => recorder.finish(self.tick),
+            None => Ok(()),
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Adiciona um comando à fila para ser executado
+    pub fn add_command(&mut self, cmd: Box<dyn Command>) {
+        self.queue.push_back(cmd);
+    }
+
+    /// Guarda o estado dos agentes antes de o passo mexer neles, para o undo do tick
+    pub fn begin_tick(&mut self, agents: &[Box<dyn AgentComponent>]) {
+        self.tick_start = capture_agents(agents, agents.len());
+    }
+
+    /// Processa a fila de comandos (Executa tudo que está pendente)
+    /// Cada chamada corresponde a um tick da simulação e vira um `CompositeCommand`.
+    pub fn process_commands(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_spawns(self.tick, agents);
+        }
+
+        // Quem chegou (ou voltou a andar) durante o passo vira um comando do tick, na frente da fila:
+        // assim o tick em que só houve chegadas entra no histórico e o replay recebe a mesma marca
+        let states = std::mem::take(&mut self.tick_start);
+        for (state, agent) in states.iter().zip(agents.iter()).rev() {
+            if state.agent.is_finished() != agent.is_finished() {
+                let command = SetFinishedCommand::new(agent.get_id(), agent.is_finished());
+                self.queue.push_front(Box::new(command));
+            }
+        }
+
+        let mut executed = Vec::with_capacity(self.queue.len());
+        while let Some(mut cmd) = self.queue.pop_front() {
+            cmd.execute(agents, grid);
+            if let Some(recorder) = &mut self.recorder {
+                recorder.record_command(self.tick, cmd.as_ref());
+            }
+            executed.push(cmd);
+        }
+        self.tick += 1;
+
+        // Ticks sem nenhum comando não entram no histórico
+        if executed.is_empty() {
+            return;
+        }
+
+        self.push_history(
+            TickEntry {
+                commands: CompositeCommand::new(executed),
+                agents: states,
+            },
+            agents,
+            grid,
+        );
+    }
+
+    /// Executa um comando na hora, fora da fila do tick (ex.: uma pincelada).
+    /// Ele vira uma entrada própria do histórico, desfeita com um único undo.
+    pub fn execute_now(
+        &mut self,
+        mut cmd: Box<dyn Command>,
+        agents: &mut Vec<Box<dyn AgentComponent>>,
+        grid: &mut Grid,
+    ) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_spawns(self.tick, agents);
+        }
+        cmd.execute(agents, grid);
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_execute(self.tick, cmd.as_ref());
+        }
+        self.push_history(
+            TickEntry {
+                commands: CompositeCommand::new(vec![cmd]),
+                agents: Vec::new(),
+            },
+            agents,
+            grid,
+        );
+    }
+
+    fn push_history(&mut self, entry: TickEntry, agents: &[Box<dyn AgentComponent>], grid: &Grid) {
+        // Uma ação nova invalida o futuro que tinha sido desfeito
+        self.redo.clear();
+        self.checkpoints.retain(|c| c.position <= self.position);
+
+        if self.history.len() == MAX_HISTORY_TICKS {
+            self.history.pop_front();
+        }
+        self.history.push_back(entry);
+        self.position += 1;
+
+        if self.position.is_multiple_of(CHECKPOINT_INTERVAL) {
+            if self.checkpoints.len() == MAX_CHECKPOINTS {
+                self.checkpoints.pop_front();
+            }
+            let checkpoint =
+                Checkpoint::capture(self.position, agents, grid, self.checkpoints.back());
+            self.checkpoints.push_back(checkpoint);
+        }
+    }
+
+    /// Desfaz o último tick executado. Quando o histórico acaba,
+    /// volta para o checkpoint anterior mais próximo.
+    pub fn undo_last(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        let mut age = None;
+        if let Some(mut entry) = self.history.pop_back() {
+            age = entry
+                .commands
+                .timestamp()
+                .map(|t| miniquad::date::now() - t);
+            let after = capture_agents(agents, entry.agents.len());
+            entry.commands.undo(agents, grid);
+            restore_agents(agents, std::mem::replace(&mut entry.agents, after));
+            self.redo.push(entry);
+            self.position -= 1;
+        } else if let Some(checkpoint) = self
+            .checkpoints
+            .iter()
+            .rev()
+            .find(|c| c.position < self.position)
+        {
+            // O que foi descartado do histórico não pode ser refeito
+            checkpoint.restore(agents, grid);
+            self.position = checkpoint.position;
+            self.redo.clear();
+            self.checkpoints.retain(|c| c.position <= self.position);
+            println!("Voltou para o checkpoint do tick {}", self.position);
+        } else {
+            return;
+        }
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_undo(self.tick);
+        }
+        match age {
+            Some(age) => println!(
+                "Ação desfeita! (tick {}, de {:.1}s atrás)",
+                self.position, age
+            ),
+            None => println!("Ação desfeita! (tick {})", self.position),
+        }
+    }
+
+    /// Refaz o último tick desfeito
+    pub fn redo_last(&mut self, agents: &mut Vec<Box<dyn AgentComponent>>, grid: &mut Grid) {
+        let Some(mut entry) = self.redo.pop() else {
+            return;
+        };
+        let before = capture_agents(agents, entry.agents.len());
+        entry.commands.execute(agents, grid);
+        restore_agents(agents, std::mem::replace(&mut entry.agents, before));
+        if self.history.len() == MAX_HISTORY_TICKS {
+            self.history.pop_front();
+        }
+        self.history.push_back(entry);
+        self.position += 1;
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_redo(self.tick);
+        }
+        println!("Ação refeita! (tick {})", self.position);
+    }
+
+    /// Limpa todo o histórico e fila
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.tick_start.clear();
+        self.history.clear();
+        self.redo.clear();
+        self.checkpoints.clear();
+        self.position = 0;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_clear(self.tick);
+        }
+        println!("CommandManager limpo.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridMode;
+    use crate::agent::{Agent, AgentArchetype};
+    use crate::collision_avoidance::NoAvoidanceStrategy;
+    use crate::simulation;
+
+    /// Carregador (aceleração finita) andando até o fim de um caminho de três waypoints
+    fn run_to_arrival(
+        agents: &mut Vec<Box<dyn AgentComponent>>,
+        grid: &mut Grid,
+        manager: &mut CommandManager,
+    ) -> usize {
+        let mut ticks = 0;
+        while !agents[0].is_finished() {
+            simulation::step(
+                agents,
+                grid,
+                manager,
+                &mut NoAvoidanceStrategy,
+                GridMode::Cardinal,
+                1.0 / 60.0,
+            );
+            ticks += 1;
+            assert!(ticks < 2000, "o agente não chegou");
+        }
+        ticks
+    }
+
+    #[test]
+    fn undo_past_arrival_restores_the_whole_agent() {
+        let mut grid = Grid::new(12, 4);
+        let start = vec2(30.0, 30.0);
+        let path = vec![vec2(110.0, 30.0), vec2(110.0, 70.0), vec2(190.0, 70.0)];
+        let agent = Agent::from_archetype(0, start, path, &AgentArchetype::carrier());
+        let mut agents: Vec<Box<dyn AgentComponent>> = vec![Box::new(agent)];
+        let mut manager = CommandManager::new();
+
+        let ticks = run_to_arrival(&mut agents, &mut grid, &mut manager);
+        let arrived_at = agents[0].get_pos();
+        let fuel = agents[0].get_fuel();
+
+        for _ in 0..ticks {
+            manager.undo_last(&mut agents, &mut grid);
+        }
+        assert!(!agents[0].is_finished());
+        assert_eq!(agents[0].get_pos(), start);
+        assert_eq!(agents[0].get_velocity(), Vec2::ZERO);
+        assert_eq!(agents[0].get_planned_path().1, 0);
+
+        for _ in 0..ticks {
+            manager.redo_last(&mut agents, &mut grid);
+        }
+        assert!(agents[0].is_finished());
+        assert_eq!(agents[0].get_pos(), arrived_at);
+        assert_eq!(agents[0].get_fuel(), fuel);
+
+        // Desfeito pela metade, o agente volta a andar e chega de novo
+        for _ in 0..ticks / 2 {
+            manager.undo_last(&mut agents, &mut grid);
+        }
+        assert!(!agents[0].is_finished());
+        run_to_arrival(&mut agents, &mut grid, &mut manager);
+        assert!(agents[0].get_pos().distance(arrived_at) < 10.0);
+    }
+
+    #[test]
+    fn checkpoints_share_cells_while_the_grid_is_unchanged() {
+        let mut grid = Grid::new(8, 8);
+        let agents: Vec<Box<dyn AgentComponent>> = Vec::new();
+        let first = Checkpoint::capture(1, &agents, &grid, None);
+        let second = Checkpoint::capture(2, &agents, &grid, Some(&first));
+        assert!(Rc::ptr_eq(&first.cells, &second.cells));
+
+        grid.set_cell(3, 3, CellType::Obstacle);
+        let third = Checkpoint::capture(3, &agents, &grid, Some(&second));
+        assert!(!Rc::ptr_eq(&second.cells, &third.cells));
+        assert_eq!(third.cells[3 * 8 + 3], CellType::Obstacle);
+    }
+}
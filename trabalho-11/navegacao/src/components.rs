@@ -0,0 +1,364 @@
+use crate::GridMode;
+use crate::grid::{CellIndex, Grid};
+use crate::grid_adapter::{GridAdapter, HexagonalAdapter, RectangularCardinalAdapter, RectangularDiagonalAdapter};
+use crate::hex::{HexLayout, HexOrientation};
+use macroquad::prelude::*;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Rótulo das células fora de qualquer componente (obstáculos)
+const NO_COMPONENT: u32 = u32::MAX;
+/// Com mais de 1/REBUILD_FRACTION das células editadas desde a última consulta, rotula tudo de novo
+const REBUILD_FRACTION: usize = 8;
+/// Sorteios de célula do grid inteiro antes de sortear direto na lista da componente
+const SAMPLE_ATTEMPTS: usize = 64;
+
+/// Vizinhança usada para ligar as células: a mesma do Adapter de cada modo de grid
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Neighborhood {
+    Cardinal,
+    Diagonal,
+    Hexagonal(HexOrientation),
+}
+
+impl Neighborhood {
+    /// Vizinhança do modo de grid (no hexagonal, com a orientação atual do layout)
+    pub fn of(grid_mode: GridMode) -> Self {
+        match grid_mode {
+            GridMode::Cardinal => Neighborhood::Cardinal,
+            GridMode::Diagonal => Neighborhood::Diagonal,
+            GridMode::Hexagonal => Neighborhood::Hexagonal(HexLayout::current().orientation),
+        }
+    }
+
+    fn neighbors(self, grid: &Grid, cell: (usize, usize)) -> Vec<(usize, usize)> {
+        match self {
+            Neighborhood::Cardinal => RectangularCardinalAdapter::new(grid).get_neighbors(cell),
+            Neighborhood::Diagonal => RectangularDiagonalAdapter::new(grid).get_neighbors(cell),
+            Neighborhood::Hexagonal(orientation) => HexagonalAdapter::new(grid, orientation).get_neighbors(cell),
+        }
+    }
+}
+
+/// Componentes conexas das células livres do grid em uma vizinhança.
+/// Cada célula livre tem o rótulo da sua componente, e cada componente a lista das suas células:
+/// saber se há caminho entre duas células é comparar rótulos.
+/// As edições do grid ficam anotadas e são aplicadas na próxima consulta, mexendo só nas
+/// componentes tocadas: liberar uma célula junta as componentes vizinhas (a menor entra na maior),
+/// bloquear uma célula procura em paralelo a partir dos vizinhos e só percorre os pedaços que se soltaram.
+pub struct ComponentMap {
+    neighborhood: Neighborhood,
+    width: usize,
+    /// Rótulo de cada célula, pelo `CellIndex` do grid
+    labels: Vec<u32>,
+    members: Vec<Vec<usize>>,
+    /// Posição de cada célula na lista da sua componente (para tirá-la em O(1))
+    slots: Vec<usize>,
+    free_labels: Vec<u32>,
+    edited: Vec<usize>,
+}
+
+impl ComponentMap {
+    /// Rotula todas as células livres do grid, linha por linha
+    pub fn build(grid: &Grid, neighborhood: Neighborhood) -> Self {
+        let cells = grid.width * grid.height;
+        let mut map = Self {
+            neighborhood,
+            width: grid.width,
+            labels: vec![NO_COMPONENT; cells],
+            members: Vec::new(),
+            slots: vec![0; cells],
+            free_labels: Vec::new(),
+            edited: Vec::new(),
+        };
+        for index in 0..cells {
+            if map.labels[index] != NO_COMPONENT || grid.is_blocked(CellIndex(index)) {
+                continue;
+            }
+            let label = map.new_label();
+            map.attach(index, label);
+            let mut queue = VecDeque::from([index]);
+            while let Some(current) = queue.pop_front() {
+                for next in map.neighbor_indices(grid, current) {
+                    if map.labels[next] == NO_COMPONENT {
+                        map.attach(next, label);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        map
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    /// Anota uma célula editada no grid; a atualização fica para a próxima consulta
+    pub fn mark_edited(&mut self, x: usize, y: usize) {
+        self.edited.push(y * self.width + x);
+    }
+
+    /// Aplica as edições anotadas desde a última consulta
+    pub fn refresh(&mut self, grid: &Grid) {
+        if self.edited.is_empty() {
+            return;
+        }
+        if self.edited.len() * REBUILD_FRACTION > self.labels.len() {
+            *self = Self::build(grid, self.neighborhood);
+            return;
+        }
+        let mut edited = std::mem::take(&mut self.edited);
+        edited.sort_unstable();
+        edited.dedup();
+
+        // Primeiro as células bloqueadas: os vizinhos livres de cada componente tocada são as sementes
+        // da procura que separa os pedaços. As sementes de todas as células bloqueadas vão juntas,
+        // porque uma componente pode ter se partido só depois de várias delas.
+        let blocked: Vec<usize> =
+            edited.iter().copied().filter(|&index| self.labels[index] != NO_COMPONENT && grid.is_blocked(CellIndex(index))).collect();
+        for &index in &blocked {
+            self.detach(index);
+        }
+        let mut seeds: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        for &index in &blocked {
+            for next in self.neighbor_indices(grid, index) {
+                if self.labels[next] != NO_COMPONENT {
+                    seeds.entry(self.labels[next]).or_default().push(next);
+                }
+            }
+        }
+        for (_, mut group) in seeds {
+            group.sort_unstable();
+            group.dedup();
+            if group.len() > 1 {
+                self.split(grid, group);
+            }
+        }
+
+        // Depois as liberadas, na ordem das células, juntando as componentes vizinhas
+        for index in edited {
+            if self.labels[index] == NO_COMPONENT && !grid.is_blocked(CellIndex(index)) {
+                self.insert(grid, index);
+            }
+        }
+    }
+
+    /// Componente da célula (`None` em obstáculo ou fora do grid)
+    pub fn component_of(&self, cell: (usize, usize)) -> Option<u32> {
+        if cell.0 >= self.width {
+            return None;
+        }
+        let label = *self.labels.get(cell.1 * self.width + cell.0)?;
+        (label != NO_COMPONENT).then_some(label)
+    }
+
+    /// Se existe caminho entre as duas células (ambas livres e na mesma componente)
+    pub fn connected(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        self.component_of(a).is_some_and(|label| self.component_of(b) == Some(label))
+    }
+
+    /// Número de células livres da componente
+    pub fn size(&self, component: u32) -> usize {
+        self.members.get(component as usize).map_or(0, Vec::len)
+    }
+
+    /// Número de componentes
+    pub fn count(&self) -> usize {
+        self.members.len() - self.free_labels.len()
+    }
+
+    /// Célula livre aleatória da componente.
+    /// Sorteia no grid inteiro como `Grid::get_random_empty_cell` (mesmos sorteios quando o grid
+    /// é uma componente só); se a componente for pequena demais para aparecer, sorteia na lista dela.
+    pub fn random_cell(&self, component: u32) -> Option<(usize, usize)> {
+        let members = self.members.get(component as usize).filter(|members| !members.is_empty())?;
+        let height = self.labels.len() / self.width;
+        for _ in 0..SAMPLE_ATTEMPTS {
+            let cell = (rand::gen_range(0, self.width), rand::gen_range(0, height));
+            if self.component_of(cell) == Some(component) {
+                return Some(cell);
+            }
+        }
+        Some(self.cell(members[rand::gen_range(0, members.len())]))
+    }
+
+    fn cell(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    /// Vizinhos livres pelo Adapter (inclui células ainda sem rótulo)
+    fn neighbor_indices(&self, grid: &Grid, index: usize) -> Vec<usize> {
+        self.neighborhood.neighbors(grid, self.cell(index)).into_iter().map(|(x, y)| y * self.width + x).collect()
+    }
+
+    fn new_label(&mut self) -> u32 {
+        self.free_labels.pop().unwrap_or_else(|| {
+            self.members.push(Vec::new());
+            (self.members.len() - 1) as u32
+        })
+    }
+
+    fn attach(&mut self, index: usize, label: u32) {
+        let members = &mut self.members[label as usize];
+        self.labels[index] = label;
+        self.slots[index] = members.len();
+        members.push(index);
+    }
+
+    fn detach(&mut self, index: usize) {
+        let label = self.labels[index];
+        let members = &mut self.members[label as usize];
+        members.swap_remove(self.slots[index]);
+        if let Some(&moved) = members.get(self.slots[index]) {
+            self.slots[moved] = self.slots[index];
+        }
+        if members.is_empty() {
+            self.free_labels.push(label);
+        }
+        self.labels[index] = NO_COMPONENT;
+    }
+
+    /// Célula liberada: junta as componentes vizinhas na maior delas (ou abre uma nova)
+    fn insert(&mut self, grid: &Grid, index: usize) {
+        let mut neighbors: Vec<u32> = self
+            .neighbor_indices(grid, index)
+            .into_iter()
+            .map(|next| self.labels[next])
+            .filter(|&label| label != NO_COMPONENT)
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        let Some(&target) = neighbors.iter().max_by_key(|&&label| self.size(label)) else {
+            let label = self.new_label();
+            self.attach(index, label);
+            return;
+        };
+        for label in neighbors.into_iter().filter(|&label| label != target) {
+            for cell in std::mem::take(&mut self.members[label as usize]) {
+                self.attach(cell, target);
+            }
+            self.free_labels.push(label);
+        }
+        self.attach(index, target);
+    }
+
+    /// Procura em largura a partir de cada semente (todas da mesma componente), um passo de cada
+    /// por vez; procuras que se encontram viram uma só. Quando sobra no máximo uma procura com
+    /// fronteira, cada procura esgotada é um pedaço inteiro que se soltou e ganha rótulo novo;
+    /// o resto fica com o rótulo antigo. Só os pedaços soltos são percorridos por inteiro.
+    fn split(&mut self, grid: &Grid, seeds: Vec<usize>) {
+        let mut owner: HashMap<usize, usize> = seeds.iter().enumerate().map(|(search, &seed)| (seed, search)).collect();
+        let mut parent: Vec<usize> = (0..seeds.len()).collect();
+        let mut queues: Vec<VecDeque<usize>> = seeds.iter().map(|&seed| VecDeque::from([seed])).collect();
+        let mut visited: Vec<Vec<usize>> = seeds.iter().map(|&seed| vec![seed]).collect();
+
+        loop {
+            let active: Vec<usize> = (0..seeds.len()).filter(|&s| parent[s] == s && !queues[s].is_empty()).collect();
+            if active.len() <= 1 {
+                break;
+            }
+            for search in active {
+                let mut search = find(&mut parent, search);
+                let Some(current) = queues[search].pop_front() else {
+                    continue;
+                };
+                for next in self.neighbor_indices(grid, current) {
+                    if self.labels[next] == NO_COMPONENT {
+                        continue;
+                    }
+                    match owner.get(&next) {
+                        None => {
+                            owner.insert(next, search);
+                            queues[search].push_back(next);
+                            visited[search].push(next);
+                        }
+                        Some(&other) => {
+                            let other = find(&mut parent, other);
+                            if other != search {
+                                // A maior absorve a menor
+                                let (keep, gone) =
+                                    if visited[search].len() >= visited[other].len() { (search, other) } else { (other, search) };
+                                parent[gone] = keep;
+                                let queue = std::mem::take(&mut queues[gone]);
+                                queues[keep].extend(queue);
+                                let cells = std::mem::take(&mut visited[gone]);
+                                visited[keep].extend(cells);
+                                search = keep;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let roots: Vec<usize> = (0..seeds.len()).filter(|&s| parent[s] == s).collect();
+        // Fica com o rótulo antigo a procura que não terminou ou, se todas terminaram, a maior
+        let keep = roots
+            .iter()
+            .copied()
+            .find(|&s| !queues[s].is_empty())
+            .or_else(|| roots.iter().copied().max_by_key(|&s| visited[s].len()));
+        for root in roots.into_iter().filter(|&s| Some(s) != keep) {
+            let label = self.new_label();
+            for &cell in &visited[root] {
+                self.detach(cell);
+                self.attach(cell, label);
+            }
+        }
+    }
+}
+
+fn find(parent: &mut [usize], mut search: usize) -> usize {
+    while parent[search] != search {
+        parent[search] = parent[parent[search]];
+        search = parent[search];
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::CellType;
+
+    /// As duas divisões das células livres são iguais (os rótulos podem ser outros)
+    fn assert_same_partition(map: &ComponentMap, rebuilt: &ComponentMap, grid: &Grid) {
+        let cells: Vec<(usize, usize)> = (0..grid.height).flat_map(|y| (0..grid.width).map(move |x| (x, y))).collect();
+        assert_eq!(map.count(), rebuilt.count());
+        for &a in &cells {
+            assert_eq!(map.component_of(a).is_some(), rebuilt.component_of(a).is_some());
+            for &b in &cells {
+                assert_eq!(map.connected(a, b), rebuilt.connected(a, b), "{:?} e {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn blocking_and_freeing_a_bridge_matches_a_full_build() {
+        let neighborhoods =
+            [Neighborhood::Cardinal, Neighborhood::Diagonal, Neighborhood::Hexagonal(HexOrientation::PointyTop)];
+        for neighborhood in neighborhoods {
+            // Parede na coluna 4 com uma passagem na linha 2
+            let mut grid = Grid::new(9, 5);
+            for y in [0, 1, 3, 4] {
+                grid.set_cell(4, y, CellType::Obstacle);
+            }
+            let mut map = ComponentMap::build(&grid, neighborhood);
+            assert_eq!(map.count(), 1);
+
+            grid.set_cell(4, 2, CellType::Obstacle);
+            map.mark_edited(4, 2);
+            map.refresh(&grid);
+            assert_eq!(map.count(), 2);
+            assert!(!map.connected((0, 2), (8, 2)));
+            assert_same_partition(&map, &ComponentMap::build(&grid, neighborhood), &grid);
+
+            grid.set_cell(4, 2, CellType::Empty);
+            map.mark_edited(4, 2);
+            map.refresh(&grid);
+            assert_eq!(map.count(), 1);
+            assert!(map.connected((0, 2), (8, 2)));
+            assert_same_partition(&map, &ComponentMap::build(&grid, neighborhood), &grid);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use macroquad::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Camadas de depuração da janela (F1 a F6), desenhadas pelos dois renderizadores
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DebugOverlays {
+    /// Abertos, fechados e ordem de expansão da última busca A*
+    pub search: bool,
+    /// Caminho planejado inteiro de cada agente e o waypoint atual
+    pub paths: bool,
+    /// Setas da velocidade atual e da preferida
+    pub velocities: bool,
+    /// Velocidades candidatas do RVO, coloridas pela penalidade
+    pub rvo_candidates: bool,
+    /// Células que o feromônio bloqueia
+    pub blocked_cells: bool,
+    /// Componentes conexas das células livres, uma cor por componente
+    pub components: bool,
+}
+
+impl DebugOverlays {
+    /// Liga/desliga as camadas pelas teclas F1 a F6 e avisa o `DebugCapture` do que precisa guardar
+    pub fn update(&mut self) {
+        let toggles = [
+            (KeyCode::F1, &mut self.search),
+            (KeyCode::F2, &mut self.paths),
+            (KeyCode::F3, &mut self.velocities),
+            (KeyCode::F4, &mut self.rvo_candidates),
+            (KeyCode::F5, &mut self.blocked_cells),
+            (KeyCode::F6, &mut self.components),
+        ];
+        for (key, layer) in toggles {
+            if is_key_pressed(key) {
+                *layer = !*layer;
+            }
+        }
+        DebugCapture::instance().configure(self);
+    }
+
+    /// Camadas ligadas, para o HUD
+    pub fn summary(&self) -> String {
+        let names = [
+            (self.search, "A*"),
+            (self.paths, "caminhos"),
+            (self.velocities, "velocidades"),
+            (self.rvo_candidates, "candidatos RVO"),
+            (self.blocked_cells, "bloqueios"),
+            (self.components, "componentes"),
+        ];
+        let active: Vec<&str> = names.iter().filter(|(on, _)| *on).map(|&(_, name)| name).collect();
+        if active.is_empty() { "nenhuma".to_string() } else { active.join(", ") }
+    }
+}
+
+/// Um nó da busca: célula do grid (A* nas células) ou polígono da malha de navegação
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchNode {
+    Cell((usize, usize)),
+    Polygon(Vec<Vec2>),
+}
+
+/// O que o A* fez na última busca calculada (buscas respondidas pelo cache não contam)
+#[derive(Debug, Clone, Default)]
+pub struct SearchTrace {
+    /// Nós fechados, na ordem em que foram expandidos
+    pub expanded: Vec<SearchNode>,
+    /// Nós que ainda estavam na fronteira quando a busca terminou
+    pub open: Vec<SearchNode>,
+    /// Caminho encontrado, em pixels (vazio se não achou)
+    pub path: Vec<Vec2>,
+}
+
+/// Singleton com os dados internos que as camadas mostram. O A*, o passo da simulação e o RVO
+/// só guardam alguma coisa enquanto a camada correspondente está ligada (o headless nunca liga).
+pub struct DebugCapture {
+    search: AtomicBool,
+    agents: AtomicBool,
+    last_search: Mutex<Option<SearchTrace>>,
+    /// Velocidade preferida de cada agente no último passo (índice = id)
+    preferred: Mutex<Vec<Vec2>>,
+    /// Velocidades candidatas do RVO e a penalidade de cada uma, por agente
+    candidates: Mutex<Vec<Vec<(Vec2, f32)>>>,
+}
+
+impl DebugCapture {
+    pub fn instance() -> &'static DebugCapture {
+        static INSTANCE: OnceLock<DebugCapture> = OnceLock::new();
+        INSTANCE.get_or_init(|| DebugCapture {
+            search: AtomicBool::new(false),
+            agents: AtomicBool::new(false),
+            last_search: Mutex::new(None),
+            preferred: Mutex::new(Vec::new()),
+            candidates: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn configure(&self, overlays: &DebugOverlays) {
+        self.search.store(overlays.search, Ordering::Relaxed);
+        let agents = overlays.velocities || overlays.rvo_candidates;
+        if !agents {
+            self.preferred.lock().unwrap().clear();
+            self.candidates.lock().unwrap().clear();
+        }
+        self.agents.store(agents, Ordering::Relaxed);
+    }
+
+    /// A próxima busca deve guardar o rastro?
+    pub fn captures_search(&self) -> bool {
+        self.search.load(Ordering::Relaxed)
+    }
+
+    /// O passo da simulação deve guardar velocidades preferidas e candidatas?
+    pub fn captures_agents(&self) -> bool {
+        self.agents.load(Ordering::Relaxed)
+    }
+
+    pub fn record_search(&self, trace: SearchTrace) {
+        *self.last_search.lock().unwrap() = Some(trace);
+    }
+
+    pub fn last_search(&self) -> Option<SearchTrace> {
+        self.last_search.lock().unwrap().clone()
+    }
+
+    pub fn record_preferred(&self, preferred: &[Vec2]) {
+        if self.captures_agents() {
+            *self.preferred.lock().unwrap() = preferred.to_vec();
+        }
+    }
+
+    pub fn preferred(&self) -> Vec<Vec2> {
+        self.preferred.lock().unwrap().clone()
+    }
+
+    pub fn record_candidates(&self, candidates: Vec<Vec<(Vec2, f32)>>) {
+        *self.candidates.lock().unwrap() = candidates;
+    }
+
+    /// As outras estratégias não têm candidatas: o passo começa sem nenhuma
+    pub fn clear_candidates(&self) {
+        self.candidates.lock().unwrap().clear();
+    }
+
+    pub fn candidates(&self) -> Vec<Vec<(Vec2, f32)>> {
+        self.candidates.lock().unwrap().clone()
+    }
+}
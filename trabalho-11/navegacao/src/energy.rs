@@ -0,0 +1,238 @@
+use crate::agent::AgentArchetype;
+use crate::grid::Grid;
+use crate::path_manager::PathKey;
+use crate::{GridMode, calculate_waypoints, grid_to_screen_center};
+use macroquad::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashSet;
+
+/// Energia gasta por pixel percorrido, independente da velocidade
+pub const ENERGY_PER_PIXEL: f32 = 0.3;
+/// Parcela que cresce com a velocidade (arrasto): custo por pixel a cada px/s
+pub const ENERGY_DRAG: f32 = 0.0005;
+/// Consumo parado (sensores, eletrônica), por segundo
+pub const IDLE_DRAIN: f32 = 4.0;
+/// Energia recebida por segundo sobre uma estação de recarga
+pub const RECHARGE_RATE: f32 = 400.0;
+/// Abaixo desta fração do tanque o agente avisa `LowEnergy`
+pub const LOW_ENERGY_FRACTION: f32 = 0.2;
+/// Fração do tanque que o planejamento pode usar em um trecho.
+/// O resto cobre desvios da prevenção de colisão e os decorators de velocidade.
+const PLANNING_RESERVE: f32 = 0.75;
+
+/// Energia de um passo: distância percorrida em `dt` mais o consumo parado
+pub fn movement_cost(distance: f32, dt: f32) -> f32 {
+    let speed = if dt > 0.0 { distance / dt } else { 0.0 };
+    distance * (ENERGY_PER_PIXEL + ENERGY_DRAG * speed) + IDLE_DRAIN * dt
+}
+
+/// Energia estimada para percorrer `length` pixels na velocidade máxima
+pub fn estimate_cost(length: f32, max_speed: f32) -> f32 {
+    if max_speed <= 0.0 {
+        return f32::INFINITY;
+    }
+    length * (ENERGY_PER_PIXEL + ENERGY_DRAG * max_speed) + IDLE_DRAIN * length / max_speed
+}
+
+pub fn path_length(waypoints: &[Vec2]) -> f32 {
+    waypoints.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+/// Caminho em pixels e os waypoints onde o agente para até encher o tanque
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Route {
+    pub waypoints: Vec<Vec2>,
+    pub charging_stops: Vec<usize>,
+}
+
+impl Route {
+    /// Caminho sem paradas
+    pub fn direct(waypoints: Vec<Vec2>) -> Self {
+        Self { waypoints, charging_stops: Vec::new() }
+    }
+}
+
+/// O que o planejamento sabe do agente: velocidade máxima, raio e a energia que ele tem
+#[derive(Clone, Copy, Debug)]
+pub struct Traveler {
+    pub max_speed: f32,
+    pub radius: f32,
+    pub energy: f32,
+    pub capacity: f32,
+}
+
+impl Traveler {
+    /// Agente novo do arquétipo, com o tanque cheio
+    pub fn from_archetype(archetype: &AgentArchetype) -> Self {
+        Self {
+            max_speed: archetype.max_speed,
+            radius: archetype.radius,
+            energy: archetype.fuel_capacity,
+            capacity: archetype.fuel_capacity,
+        }
+    }
+}
+
+/// Um trecho do caminho, já em pixels (A* nas células ou na malha de navegação)
+fn leg(grid: &Grid, from: (usize, usize), to: (usize, usize), grid_mode: GridMode, radius: f32) -> Option<Vec<Vec2>> {
+    calculate_waypoints(grid, from, to, grid_mode, radius)
+}
+
+/// Planeja os caminhos de vários agentes novos do mesmo arquétipo ao mesmo tempo, no pool de
+/// threads do rayon. O grid só é lido durante o lote; as rotas saem na ordem dos pares (início, destino).
+pub fn plan_routes(grid: &Grid, queries: &[PathKey], grid_mode: GridMode, archetype: &AgentArchetype) -> Vec<Option<Route>> {
+    queries.par_iter().map(|&(start, goal)| plan_route(grid, start, goal, grid_mode, archetype)).collect()
+}
+
+/// Planeja o caminho de um agente novo, com o tanque cheio do arquétipo.
+pub fn plan_route(
+    grid: &Grid,
+    start: (usize, usize),
+    goal: (usize, usize),
+    grid_mode: GridMode,
+    archetype: &AgentArchetype,
+) -> Option<Route> {
+    plan_route_with_energy(grid, start, goal, grid_mode, Traveler::from_archetype(archetype))
+}
+
+/// Planeja o caminho com a energia que o agente tem agora.
+/// Se ela não cobre o caminho direto, procura a sequência de estações de
+/// recarga mais curta em que cada trecho cabe no tanque (Dijkstra sobre início,
+/// estações e destino; o A* de cada trecho vem do cache do `PathManager`).
+/// O primeiro trecho usa a energia atual; os outros, o tanque cheio. Na malha de navegação os
+/// trechos passam longe dos cantos conforme o raio do agente.
+/// Sem sequência possível, devolve o caminho direto: o agente vai até onde der.
+pub fn plan_route_with_energy(
+    grid: &Grid,
+    start: (usize, usize),
+    goal: (usize, usize),
+    grid_mode: GridMode,
+    traveler: Traveler,
+) -> Option<Route> {
+    let radius = traveler.radius;
+    let direct = leg(grid, start, goal, grid_mode, radius)?;
+    let first_budget = traveler.energy * PLANNING_RESERVE;
+    let budget = traveler.capacity * PLANNING_RESERVE;
+    let cost = |length: f32| estimate_cost(length, traveler.max_speed);
+    if cost(path_length(&direct)) <= first_budget {
+        return Some(Route::direct(direct));
+    }
+
+    let stations = grid.charging_stations();
+    if stations.is_empty() {
+        return Some(Route::direct(direct));
+    }
+
+    // Nós: 0 = início, 1..=n = estações, n + 1 = destino
+    let mut nodes = vec![start];
+    nodes.extend(stations);
+    nodes.push(goal);
+    let goal_node = nodes.len() - 1;
+
+    let mut dist = vec![f32::INFINITY; nodes.len()];
+    let mut prev: Vec<Option<(usize, Vec<Vec2>)>> = vec![None; nodes.len()];
+    let mut done = HashSet::new();
+    dist[0] = 0.0;
+
+    while let Some(current) = (0..nodes.len())
+        .filter(|i| !done.contains(i) && dist[*i].is_finite())
+        .min_by(|a, b| dist[*a].total_cmp(&dist[*b]))
+    {
+        if current == goal_node {
+            break;
+        }
+        done.insert(current);
+
+        let leg_budget = if current == 0 { first_budget } else { budget };
+        let from = grid_to_screen_center(nodes[current], grid_mode);
+        for next in 1..nodes.len() {
+            if done.contains(&next) {
+                continue;
+            }
+            // A linha reta é o menor caminho possível: descarta trechos sem chance antes do A*
+            let to = grid_to_screen_center(nodes[next], grid_mode);
+            if cost(from.distance(to)) > leg_budget {
+                continue;
+            }
+            let Some(waypoints) = leg(grid, nodes[current], nodes[next], grid_mode, radius) else {
+                continue;
+            };
+            let length = path_length(&waypoints);
+            if cost(length) <= leg_budget && dist[current] + length < dist[next] {
+                dist[next] = dist[current] + length;
+                prev[next] = Some((current, waypoints));
+            }
+        }
+    }
+
+    if !dist[goal_node].is_finite() {
+        return Some(Route::direct(direct));
+    }
+
+    // Remonta os trechos do destino para o início
+    let mut legs = Vec::new();
+    let mut node = goal_node;
+    while let Some((from, waypoints)) = prev[node].take() {
+        legs.push(waypoints);
+        node = from;
+    }
+    legs.reverse();
+
+    let mut route = Route::direct(Vec::new());
+    for (i, waypoints) in legs.into_iter().enumerate() {
+        if i > 0 {
+            // O trecho anterior termina em uma estação: o agente recarrega ali
+            route.charging_stops.push(route.waypoints.len() - 1);
+            route.waypoints.extend(waypoints.into_iter().skip(1));
+        } else {
+            route.waypoints.extend(waypoints);
+        }
+    }
+    Some(route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Agent;
+    use crate::agent_decorator::AgentComponent;
+    use crate::collision_avoidance::NoAvoidanceStrategy;
+    use crate::command::CommandManager;
+    use crate::grid::CellType;
+    use crate::simulation;
+
+    #[test]
+    fn route_through_a_station_stops_there_until_full() {
+        // Corredor de 580 px com uma estação no meio: o tanque só cobre metade dele
+        let mut grid = Grid::new(30, 3);
+        grid.set_cell(15, 1, CellType::ChargingStation);
+        let archetype = AgentArchetype { fuel_capacity: 200.0, ..AgentArchetype::carrier() };
+        let (start, goal) = ((0, 1), (29, 1));
+        let route = plan_route(&grid, start, goal, GridMode::Cardinal, &archetype).unwrap();
+        let station = grid_to_screen_center((15, 1), GridMode::Cardinal);
+        assert_eq!(route.charging_stops.len(), 1);
+        assert_eq!(route.waypoints[route.charging_stops[0]], station);
+
+        let agent = Agent::from_archetype(0, grid_to_screen_center(start, GridMode::Cardinal), route.waypoints, &archetype)
+            .with_charging_stops(route.charging_stops);
+        let mut agents: Vec<Box<dyn AgentComponent>> = vec![Box::new(agent)];
+        let mut manager = CommandManager::new();
+
+        let (mut reached_station, mut charged) = (false, false);
+        for _ in 0..3000 {
+            simulation::step(&mut agents, &mut grid, &mut manager, &mut NoAvoidanceStrategy, GridMode::Cardinal, 1.0 / 60.0);
+            let near = agents[0].get_pos().distance(station) < 10.0;
+            reached_station |= near;
+            if reached_station && !charged {
+                charged = agents[0].get_fuel() >= archetype.fuel_capacity;
+                assert!(charged || near, "saiu da estação com {:.1} de energia", agents[0].get_fuel());
+            }
+            if agents[0].is_finished() {
+                break;
+            }
+        }
+        assert!(charged);
+        assert!(agents[0].is_finished());
+        assert!(agents[0].get_pos().distance(grid_to_screen_center(goal, GridMode::Cardinal)) < 10.0);
+    }
+}
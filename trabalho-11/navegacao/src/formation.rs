@@ -0,0 +1,317 @@
+use crate::GridMode;
+use crate::grid::Grid;
+use macroquad::prelude::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Fração da velocidade máxima em que o líder anda: a folga deixa os seguidores alcançarem as vagas
+pub const LEADER_CRUISE: f32 = 0.8;
+/// Quanto o seguidor acelera por pixel de distância até a vaga (1/s)
+pub const FOLLOWER_GAIN: f32 = 3.0;
+/// Passo (px) das amostras na medição do corredor e na linha de visão
+const SAMPLE_STEP: f32 = 4.0;
+/// Distância (px) entre as marcas do rastro do líder
+const TRAIL_STEP: f32 = 4.0;
+/// Quanto à frente (px, ao longo do rastro) o seguidor mira quando persegue o rastro
+const LOOKAHEAD: f32 = 24.0;
+/// Folga (px) entre agentes vizinhos quando a formação encolhe
+const MIN_GAP: f32 = 4.0;
+/// Velocidade (px/s) com que o espaçamento volta a crescer depois de um corredor
+const REGROW_RATE: f32 = 30.0;
+
+/// Desenho da formação em volta do líder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormationShape {
+    /// Lado a lado com o líder, alternando direita e esquerda
+    Line,
+    /// "V" aberto para trás, com o líder na ponta
+    Wedge,
+    /// Fila indiana sobre o rastro do líder
+    Column,
+}
+
+impl FormationShape {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "line" => Some(FormationShape::Line),
+            "wedge" => Some(FormationShape::Wedge),
+            "column" => Some(FormationShape::Column),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FormationShape::Line => "line",
+            FormationShape::Wedge => "wedge",
+            FormationShape::Column => "column",
+        }
+    }
+
+    /// Posição da vaga `slot` (1 = primeiro seguidor) em unidades de espaçamento:
+    /// `x` para a frente do líder, `y` para a direita
+    fn offset(&self, slot: usize) -> Vec2 {
+        let rank = slot.div_ceil(2) as f32;
+        let side = if slot % 2 == 1 { 1.0 } else { -1.0 };
+        match self {
+            FormationShape::Line => vec2(0.0, side * rank),
+            FormationShape::Wedge => vec2(-rank, side * rank),
+            FormationShape::Column => vec2(-(slot as f32), 0.0),
+        }
+    }
+}
+
+/// Estado compartilhado por todos os membros de uma formação.
+/// O líder escreve posição, direção, rastro e o espaçamento que cabe no corredor;
+/// os seguidores leem para achar a própria vaga e devolvem o quanto estão atrasados.
+#[derive(Clone, Debug)]
+pub struct Formation {
+    pub shape: FormationShape,
+    /// Espaçamento pedido (px entre vagas vizinhas)
+    pub spacing: f32,
+    /// Espaçamento em uso: encolhe em corredores estreitos
+    pub current_spacing: f32,
+    /// Sem espaço nem para o espaçamento mínimo: todos seguem o rastro em fila
+    pub single_file: bool,
+    /// Número de membros, com o líder
+    pub size: usize,
+    /// Maior raio entre os membros (espaçamento mínimo e folga nas paredes)
+    pub member_radius: f32,
+    pub leader_pos: Vec2,
+    pub leader_heading: Vec2,
+    pub leader_speed: f32,
+    pub leader_finished: bool,
+    /// Posições recentes do líder, da mais nova para a mais antiga
+    trail: VecDeque<Vec2>,
+    /// Maior distância de um seguidor até a vaga no último passo (o líder espera se for grande)
+    lag: f32,
+    lag_this_step: f32,
+}
+
+pub type FormationHandle = Rc<RefCell<Formation>>;
+
+impl Formation {
+    pub fn new(shape: FormationShape, spacing: f32, size: usize, member_radius: f32) -> FormationHandle {
+        Rc::new(RefCell::new(Self {
+            shape,
+            spacing,
+            current_spacing: spacing,
+            single_file: shape == FormationShape::Column,
+            size,
+            member_radius,
+            leader_pos: Vec2::ZERO,
+            leader_heading: vec2(1.0, 0.0),
+            leader_speed: 0.0,
+            leader_finished: false,
+            trail: VecDeque::new(),
+            lag: 0.0,
+            lag_this_step: 0.0,
+        }))
+    }
+
+    fn min_spacing(&self) -> f32 {
+        2.0 * self.member_radius + MIN_GAP
+    }
+
+    /// Chamado pelo líder antes de cada passo
+    pub fn update_leader(&mut self, grid: &Grid, grid_mode: GridMode, pos: Vec2, heading: Vec2, speed: f32, dt: f32) {
+        self.leader_pos = pos;
+        if heading.length_squared() > 0.0 {
+            self.leader_heading = heading.normalize();
+        }
+        self.leader_speed = speed;
+        self.lag = self.lag_this_step;
+        self.lag_this_step = 0.0;
+
+        if self.trail.front().is_none_or(|last| last.distance(pos) >= TRAIL_STEP) {
+            self.trail.push_front(pos);
+            let max_len = (self.size as f32 * self.spacing * 1.5 / TRAIL_STEP) as usize + 2;
+            self.trail.truncate(max_len);
+        }
+
+        self.fit_to_corridor(grid, grid_mode, dt);
+    }
+
+    /// Encolhe o espaçamento até as vagas laterais caberem no corredor em volta do líder;
+    /// se nem o mínimo cabe, passa para fila indiana. Encolher é imediato, voltar a crescer é gradual.
+    fn fit_to_corridor(&mut self, grid: &Grid, grid_mode: GridMode, dt: f32) {
+        if self.shape == FormationShape::Column {
+            return;
+        }
+        let lateral_units = (1..self.size).map(|slot| self.shape.offset(slot).y.abs()).fold(0.0, f32::max);
+        if lateral_units == 0.0 {
+            return;
+        }
+        let right = vec2(-self.leader_heading.y, self.leader_heading.x);
+        let reach = lateral_units * self.spacing + self.member_radius;
+        let free = free_distance(grid, grid_mode, self.leader_pos, right, reach)
+            .min(free_distance(grid, grid_mode, self.leader_pos, -right, reach));
+        let fit = (free - self.member_radius) / lateral_units;
+
+        // Histerese: só sai da fila com folga, para não ficar alternando na boca do corredor
+        let min_spacing = self.min_spacing();
+        let exit_spacing = if self.single_file { min_spacing * 1.25 } else { min_spacing };
+        if fit < exit_spacing {
+            self.single_file = true;
+            return;
+        }
+        self.single_file = false;
+        let target = fit.min(self.spacing);
+        self.current_spacing = if target < self.current_spacing {
+            target
+        } else {
+            (self.current_spacing + REGROW_RATE * dt).min(target)
+        };
+    }
+
+    /// Vaga do seguidor `slot` em volta do líder
+    pub fn slot_position(&self, slot: usize) -> Vec2 {
+        let offset = self.shape.offset(slot) * self.current_spacing;
+        let right = vec2(-self.leader_heading.y, self.leader_heading.x);
+        self.leader_pos + self.leader_heading * offset.x + right * offset.y
+    }
+
+    /// Alvo do seguidor `slot` que está em `pos` e o atraso dele (distância até a vaga).
+    /// A vaga na formação vale se estiver livre e visível do líder e do seguidor; senão (ou em
+    /// fila indiana) o seguidor persegue o rastro do líder a partir do ponto mais próximo dele,
+    /// sem passar da própria posição na fila, em vez de cortar caminho pelas quinas.
+    pub fn follower_target(&self, grid: &Grid, grid_mode: GridMode, slot: usize, pos: Vec2) -> (Option<Vec2>, f32) {
+        if let Some(vaga) = self.usable_slot(grid, grid_mode, slot, pos) {
+            return (Some(vaga), pos.distance(vaga));
+        }
+
+        let clearance = self.member_radius * 0.5;
+        let slot_arc = slot as f32 * self.spacing.max(self.min_spacing());
+        let own_arc = self.closest_trail_arc(pos);
+        let lag = pos.distance(self.trail_point(slot_arc));
+        let mut arc = slot_arc.max(own_arc - LOOKAHEAD);
+        while arc <= own_arc.max(slot_arc) {
+            let point = self.trail_point(arc);
+            if clear_path(grid, grid_mode, pos, point, clearance) {
+                return (Some(point), lag);
+            }
+            arc += TRAIL_STEP;
+        }
+        let closest = self.trail_point(own_arc);
+        (line_of_sight(grid, grid_mode, pos, closest).then_some(closest), lag)
+    }
+
+    /// Caminho do seguidor quando o líder chega: até a vaga, ou pelo rastro até a posição na fila
+    pub fn final_route(&self, grid: &Grid, grid_mode: GridMode, slot: usize, pos: Vec2) -> Vec<Vec2> {
+        if let Some(vaga) = self.usable_slot(grid, grid_mode, slot, pos) {
+            return vec![vaga];
+        }
+        let slot_arc = slot as f32 * self.spacing.max(self.min_spacing());
+        let mut arc = self.closest_trail_arc(pos) - LOOKAHEAD;
+        let mut waypoints = Vec::new();
+        while arc > slot_arc {
+            waypoints.push(self.trail_point(arc));
+            arc -= LOOKAHEAD;
+        }
+        waypoints.push(self.trail_point(slot_arc));
+        waypoints
+    }
+
+    /// Vaga na formação, se ela está livre e visível do líder e do seguidor
+    fn usable_slot(&self, grid: &Grid, grid_mode: GridMode, slot: usize, pos: Vec2) -> Option<Vec2> {
+        if self.single_file {
+            return None;
+        }
+        let clearance = self.member_radius * 0.5;
+        let vaga = self.slot_position(slot);
+        (!is_blocked(grid, grid_mode, vaga)
+            && clear_path(grid, grid_mode, self.leader_pos, vaga, clearance)
+            && clear_path(grid, grid_mode, pos, vaga, clearance))
+        .then_some(vaga)
+    }
+
+    /// Ponto do rastro a `arc` pixels do líder, medidos ao longo do rastro.
+    /// Além do fim do rastro, continua em linha reta atrás da marca mais antiga.
+    fn trail_point(&self, arc: f32) -> Vec2 {
+        let mut remaining = arc;
+        let mut previous = self.leader_pos;
+        for &mark in &self.trail {
+            let length = previous.distance(mark);
+            if length >= remaining && length > 0.0 {
+                return previous + (mark - previous) * (remaining / length);
+            }
+            remaining -= length;
+            previous = mark;
+        }
+        previous - self.leader_heading * remaining
+    }
+
+    /// Distância ao longo do rastro até o ponto do rastro mais próximo de `pos`
+    fn closest_trail_arc(&self, pos: Vec2) -> f32 {
+        let mut best = (pos.distance_squared(self.leader_pos), 0.0);
+        let mut arc = 0.0;
+        let mut previous = self.leader_pos;
+        for &mark in &self.trail {
+            let segment = mark - previous;
+            let length = segment.length();
+            if length > 0.0 {
+                let t = ((pos - previous).dot(segment) / (length * length)).clamp(0.0, 1.0);
+                let distance = pos.distance_squared(previous + segment * t);
+                if distance < best.0 {
+                    best = (distance, arc + length * t);
+                }
+            }
+            arc += length;
+            previous = mark;
+        }
+        best.1
+    }
+
+    /// Seguidores informam a distância até a vaga
+    pub fn report_lag(&mut self, distance: f32) {
+        self.lag_this_step = self.lag_this_step.max(distance);
+    }
+
+    /// Multiplicador da velocidade do líder: anda em `LEADER_CRUISE` e desacelera
+    /// enquanto algum seguidor estiver longe da vaga (a formação se refaz depois de desvios)
+    pub fn leader_speed_factor(&self) -> f32 {
+        let tolerance = 2.0 * self.current_spacing.max(self.min_spacing());
+        if self.lag <= tolerance {
+            LEADER_CRUISE
+        } else {
+            LEADER_CRUISE * (tolerance / self.lag).max(0.25)
+        }
+    }
+}
+
+/// Ponto em obstáculo ou fora do grid
+pub fn is_blocked(grid: &Grid, grid_mode: GridMode, point: Vec2) -> bool {
+    if point.x < 0.0 || point.y < 0.0 {
+        return true;
+    }
+    let (x, y) = crate::screen_to_grid(point.x, point.y, grid_mode);
+    grid.is_obstacle(x, y)
+}
+
+/// O segmento `from`-`to` não passa por nenhuma célula bloqueada (amostrado a cada `SAMPLE_STEP`)
+pub fn line_of_sight(grid: &Grid, grid_mode: GridMode, from: Vec2, to: Vec2) -> bool {
+    let samples = (from.distance(to) / SAMPLE_STEP).ceil().max(1.0) as usize;
+    (0..=samples).all(|i| !is_blocked(grid, grid_mode, from.lerp(to, i as f32 / samples as f32)))
+}
+
+/// Linha de visão com folga: o centro e as duas laterais a `clearance` pixels do segmento
+fn clear_path(grid: &Grid, grid_mode: GridMode, from: Vec2, to: Vec2, clearance: f32) -> bool {
+    let dir = (to - from).normalize_or_zero();
+    let side = vec2(-dir.y, dir.x) * clearance;
+    line_of_sight(grid, grid_mode, from, to)
+        && line_of_sight(grid, grid_mode, from + side, to + side)
+        && line_of_sight(grid, grid_mode, from - side, to - side)
+}
+
+/// Distância livre a partir de `from` na direção `dir`, até no máximo `max`
+fn free_distance(grid: &Grid, grid_mode: GridMode, from: Vec2, dir: Vec2, max: f32) -> f32 {
+    let mut distance = SAMPLE_STEP;
+    while distance <= max {
+        if is_blocked(grid, grid_mode, from + dir * distance) {
+            return distance - SAMPLE_STEP;
+        }
+        distance += SAMPLE_STEP;
+    }
+    max
+}
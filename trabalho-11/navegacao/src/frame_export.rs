@@ -0,0 +1,266 @@
+use crate::agent_decorator::AgentComponent;
+use crate::camera::{self, MIN_OUTLINE_CELL};
+use crate::grid::{CellType, Grid};
+use crate::hex::HexLayout;
+use crate::pheromone::PheromoneManager;
+use crate::renderer::{STATION_COLOR, terrain_color};
+use crate::{CELL_SIZE, GridMode};
+use macroquad::prelude::{BLACK, Color, GRAY, Vec2, WHITE, vec2};
+use plotters::coord::Shift;
+use plotters::prelude::{
+    BitMapBackend, Circle, DrawingArea, DrawingAreaErrorKind, DrawingBackend, FontStyle, IntoDrawingArea, IntoFont,
+    PathElement, Polygon, RGBAColor, Rectangle, SVGBackend, ShapeStyle, Text,
+};
+use std::path::Path;
+use std::sync::Once;
+
+/// Fonte da legenda: a mesma fonte padrão que o macroquad usa no HUD da janela
+const CAPTION_FONT: &[u8] = include_bytes!("../assets/ProggyClean.ttf");
+const CAPTION_FONT_NAME: &str = "proggy";
+const CAPTION_SIZE: f64 = 16.0;
+
+/// Formato das imagens exportadas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    Png,
+    Svg,
+}
+
+impl FrameFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "png" => Some(FrameFormat::Png),
+            "svg" => Some(FrameFormat::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FrameFormat::Png => "png",
+            FrameFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Camadas que podem ser escondidas; obstáculos, estações e agentes sempre aparecem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlays {
+    pub grid: bool,
+    pub pheromones: bool,
+    pub paths: bool,
+    pub detection: bool,
+}
+
+impl Overlays {
+    pub const ALL: Overlays = Overlays { grid: true, pheromones: true, paths: true, detection: true };
+    pub const NONE: Overlays = Overlays { grid: false, pheromones: false, paths: false, detection: false };
+
+    /// Lista separada por vírgulas (`grid,paths`), `all` ou `none`
+    pub fn parse(list: &str) -> Result<Self, String> {
+        match list.to_lowercase().as_str() {
+            "all" => return Ok(Overlays::ALL),
+            "none" => return Ok(Overlays::NONE),
+            _ => {}
+        }
+        let mut overlays = Overlays::NONE;
+        for name in list.split(',').map(|name| name.trim().to_lowercase()) {
+            match name.as_str() {
+                "grid" => overlays.grid = true,
+                "pheromones" | "pheromone" => overlays.pheromones = true,
+                "paths" | "path" => overlays.paths = true,
+                "detection" => overlays.detection = true,
+                _ => return Err(format!("Camada desconhecida: '{}' (grid, pheromones, paths, detection)", name)),
+            }
+        }
+        Ok(overlays)
+    }
+}
+
+/// Como as imagens (e os quadros dos vídeos) são desenhadas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameStyle {
+    /// Pixels da imagem por pixel do mundo
+    pub scale: f32,
+    pub overlays: Overlays,
+    /// Legenda com cenário, método e tempo simulado no canto superior esquerdo
+    pub caption: bool,
+}
+
+impl Default for FrameStyle {
+    fn default() -> Self {
+        Self { scale: 1.0, overlays: Overlays::ALL, caption: true }
+    }
+}
+
+/// Texto da legenda; a semente só aparece quando é conhecida (na janela, só com cenário carregado)
+pub fn caption(scenario: &str, avoidance: &str, seed: Option<u64>, time: f32) -> String {
+    let seed = seed.map(|seed| format!(" | seed {}", seed)).unwrap_or_default();
+    format!("{} | {}{} | t = {:.2}s", scenario, avoidance, seed, time)
+}
+
+/// Estado da simulação a desenhar sem precisar de janela (o macroquad só desenha com uma aberta).
+/// Desenha o mesmo que a janela, nas mesmas cores: grade, obstáculos e estações, campo de
+/// feromônios, caminhos planejados, raios de detecção e agentes, em qualquer backend do plotters.
+pub struct Frame<'a> {
+    pub grid: &'a Grid,
+    pub grid_mode: GridMode,
+    pub agents: &'a [Box<dyn AgentComponent>],
+    pub style: FrameStyle,
+    pub caption: String,
+}
+
+pub type DrawResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+
+impl Frame<'_> {
+    /// Tamanho da imagem (px)
+    pub fn size(&self) -> (u32, u32) {
+        let world = camera::world_size(self.grid_mode, self.grid.width, self.grid.height) * self.style.scale;
+        (world.x.ceil().max(1.0) as u32, world.y.ceil().max(1.0) as u32)
+    }
+
+    /// Grava a imagem em PNG ou SVG
+    pub fn save(&self, path: &Path, format: FrameFormat) -> Result<(), String> {
+        match format {
+            FrameFormat::Png => {
+                let root = BitMapBackend::new(path, self.size()).into_drawing_area();
+                self.draw(&root).map_err(|e| e.to_string())?;
+                root.present().map_err(|e| e.to_string())
+            }
+            FrameFormat::Svg => {
+                let root = SVGBackend::new(path, self.size()).into_drawing_area();
+                self.draw(&root).map_err(|e| e.to_string())?;
+                root.present().map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    pub fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> DrawResult<DB> {
+        root.fill(&rgba(Color::from_hex(0x111111)))?;
+        let layout = HexLayout::current();
+        let hexagonal = self.grid_mode == GridMode::Hexagonal;
+        let cell_size = if hexagonal { layout.size } else { CELL_SIZE };
+        let overlays = self.style.overlays;
+
+        // Grade (só se as células ficarem grandes o bastante na imagem, como na janela)
+        if overlays.grid && cell_size * self.style.scale >= MIN_OUTLINE_CELL {
+            for y in 0..self.grid.height {
+                for x in 0..self.grid.width {
+                    let mut outline = self.cell_shape(&layout, (x, y));
+                    outline.push(outline[0]);
+                    root.draw(&PathElement::new(outline, style(GRAY, false, 1)))?;
+                }
+            }
+        }
+
+        // Obstáculos, estações de recarga e terreno
+        for y in 0..self.grid.height {
+            for (x, (&cell, &cost)) in self.grid.row(y).iter().zip(self.grid.terrain_row(y)).enumerate() {
+                let color = match cell {
+                    CellType::Obstacle => BLACK,
+                    CellType::ChargingStation => STATION_COLOR,
+                    CellType::Empty => match terrain_color(cost as usize) {
+                        Some(color) => color,
+                        None => continue,
+                    },
+                };
+                self.fill_cell(root, &layout, (x, y), color)?;
+            }
+        }
+
+        // Campo de feromônios (vazio quando a estratégia não é a de feromônios)
+        let pheromones = if overlays.pheromones { PheromoneManager::instance().get_grid_snapshot() } else { Vec::new() };
+        for (y, row) in pheromones.iter().enumerate().take(self.grid.height) {
+            for (x, &intensity) in row.iter().enumerate().take(self.grid.width) {
+                if intensity > 0.1 {
+                    let color = Color::new(1.0, 0.0, 1.0, (intensity / 5.0).min(0.6));
+                    self.fill_cell(root, &layout, (x, y), color)?;
+                }
+            }
+        }
+
+        // Caminhos planejados: da posição atual pelos waypoints que faltam
+        for agent in self.agents.iter().filter(|a| overlays.paths && !a.is_finished()) {
+            let (path, current) = agent.get_planned_path();
+            let remaining = &path[current..];
+            if remaining.is_empty() {
+                continue;
+            }
+            let color = agent.get_color();
+            let points: Vec<_> = std::iter::once(agent.get_pos()).chain(remaining.iter().copied()).map(|p| self.pixel(p)).collect();
+            root.draw(&PathElement::new(points, style(Color::new(color.r, color.g, color.b, 0.5), false, 1)))?;
+        }
+
+        // Agentes: raio de detecção e corpo
+        for agent in self.agents {
+            let center = self.pixel(agent.get_pos());
+            if overlays.detection {
+                let detection = agent.get_detection_radius() * self.style.scale;
+                root.draw(&Circle::new(center, detection, style(agent.get_detection_color(), false, 2)))?;
+            }
+            let radius = (agent.get_physical_radius() * self.style.scale).max(1.0);
+            root.draw(&Circle::new(center, radius, style(agent.get_color(), true, 1)))?;
+        }
+
+        if self.style.caption && !self.caption.is_empty() {
+            self.draw_caption(root)?;
+        }
+        Ok(())
+    }
+
+    /// Legenda sobre uma faixa escura, para continuar legível em cima dos agentes
+    fn draw_caption<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> DrawResult<DB> {
+        static REGISTER: Once = Once::new();
+        REGISTER.call_once(|| {
+            plotters::style::register_font(CAPTION_FONT_NAME, FontStyle::Normal, CAPTION_FONT)
+                .unwrap_or_else(|_| panic!("Fonte da legenda inválida"));
+        });
+
+        let font = (CAPTION_FONT_NAME, CAPTION_SIZE).into_font().color(&rgba(WHITE));
+        let (w, h) = root.estimate_text_size(&self.caption, &font)?;
+        let background = style(Color::new(0.0, 0.0, 0.0, 0.6), true, 1);
+        root.draw(&Rectangle::new([(0, 0), (w as i32 + 12, h as i32 + 10)], background))?;
+        root.draw(&Text::new(self.caption.as_str(), (6, 5), font))
+    }
+
+    /// Contorno da célula em pixels da imagem (quadrado ou hexágono)
+    fn cell_shape(&self, layout: &HexLayout, (x, y): (usize, usize)) -> Vec<(i32, i32)> {
+        let corners: Vec<Vec2> = match self.grid_mode {
+            GridMode::Hexagonal => layout.corners(layout.cell_center((x, y))).to_vec(),
+            _ => {
+                let min = vec2(x as f32, y as f32) * CELL_SIZE;
+                vec![min, min + vec2(CELL_SIZE, 0.0), min + vec2(CELL_SIZE, CELL_SIZE), min + vec2(0.0, CELL_SIZE)]
+            }
+        };
+        corners.into_iter().map(|p| self.pixel(p)).collect()
+    }
+
+    fn fill_cell<DB: DrawingBackend>(
+        &self,
+        root: &DrawingArea<DB, Shift>,
+        layout: &HexLayout,
+        cell: (usize, usize),
+        color: Color,
+    ) -> DrawResult<DB> {
+        let shape = self.cell_shape(layout, cell);
+        match self.grid_mode {
+            GridMode::Hexagonal => root.draw(&Polygon::new(shape, style(color, true, 1))),
+            _ => root.draw(&Rectangle::new([shape[0], shape[2]], style(color, true, 1))),
+        }
+    }
+
+    /// Pixel da imagem correspondente a um ponto do mundo
+    fn pixel(&self, point: Vec2) -> (i32, i32) {
+        let p = point * self.style.scale;
+        (p.x.round() as i32, p.y.round() as i32)
+    }
+}
+
+fn rgba(color: Color) -> RGBAColor {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    RGBAColor(channel(color.r), channel(color.g), channel(color.b), color.a as f64)
+}
+
+fn style(color: Color, filled: bool, stroke_width: u32) -> ShapeStyle {
+    ShapeStyle { color: rgba(color), filled, stroke_width }
+}
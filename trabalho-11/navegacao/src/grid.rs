@@ -0,0 +1,361 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::ops::Range;
+use std::sync::Mutex;
+
+use crate::GridMode;
+use crate::components::{ComponentMap, Neighborhood};
+
+/// Fonte das revisões: cada mudança em qualquer grid recebe um número novo,
+/// então dois grids diferentes nunca têm a mesma revisão
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Tipos de célula que podem existir no grid.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CellType {
+    Empty,           // Célula vazia, livre para movimento
+    Obstacle,        // Célula de obstáculo, bloqueada
+    ChargingStation, // Estação de recarga, livre para movimento
+}
+
+/// Posição de uma célula no armazenamento do grid, linha por linha (`y * largura + x`).
+/// Vizinhos no grid retangular ficam a ±1 (mesma linha) e ±largura (linhas vizinhas).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct CellIndex(pub usize);
+
+impl CellIndex {
+    /// Célula `dx` colunas e `dy` linhas adiante em um grid de largura `width`.
+    /// Não confere os limites: quem chama sabe, pela coordenada, se o vizinho existe.
+    pub fn offset(self, dx: isize, dy: isize, width: usize) -> CellIndex {
+        CellIndex(self.0.wrapping_add_signed(dy * width as isize + dx))
+    }
+}
+
+/// Bits por palavra do conjunto de obstáculos
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Estrutura principal que representa o ambiente de simulação.
+/// As células ficam em um buffer só, linha por linha, e os obstáculos também em um conjunto de bits
+/// (uma palavra de 64 bits cobre 64 células da linha): `is_obstacle` é uma comparação de limites e um bit.
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<CellType>,
+    /// Bit `i` ligado: a célula de índice `i` é obstáculo
+    obstacles: Vec<u64>,
+    /// Custo de atravessar cada célula (1 = normal), multiplicado no custo de movimento do A*
+    terrain: Vec<u8>,
+    /// Muda a cada mudança nas células (quem guarda dados derivados do grid sabe quando refazer)
+    revision: u64,
+    /// Componentes conexas de cada vizinhança já consultada; as células editadas ficam anotadas nelas
+    components: Mutex<Vec<ComponentMap>>,
+}
+
+impl Grid {
+    /// Cria um novo grid preenchido com células vazias.
+    pub fn new(width: usize, height: usize) -> Self {
+        let cells = width * height;
+        Self {
+            width,
+            height,
+            cells: vec![CellType::Empty; cells],
+            obstacles: vec![0; cells.div_ceil(WORD_BITS)],
+            terrain: vec![1; cells],
+            revision: next_revision(),
+            components: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Índice da célula (x, y), se estiver dentro do grid
+    pub fn index(&self, x: usize, y: usize) -> Option<CellIndex> {
+        (x < self.width && y < self.height).then_some(CellIndex(y * self.width + x))
+    }
+
+    /// Coordenada (x, y) de um índice
+    pub fn position(&self, index: CellIndex) -> (usize, usize) {
+        (index.0 % self.width, index.0 / self.width)
+    }
+
+    /// Número de células (tamanho das tabelas indexadas por `CellIndex`)
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Tipo da célula (x, y), `None` fora do grid
+    pub fn get_cell(&self, x: usize, y: usize) -> Option<CellType> {
+        self.index(x, y).map(|index| self.cells[index.0])
+    }
+
+    /// Todas as células, linha por linha (ex.: para guardar um checkpoint)
+    pub fn cells(&self) -> &[CellType] {
+        &self.cells
+    }
+
+    /// Células da linha `y`
+    pub fn row(&self, y: usize) -> &[CellType] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Custo do terreno das células da linha `y`
+    pub fn terrain_row(&self, y: usize) -> &[u8] {
+        &self.terrain[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Define o tipo de uma célula específica nas coordenadas (x, y).
+    pub fn set_cell(&mut self, x: usize, y: usize, cell_type: CellType) {
+        let Some(index) = self.index(x, y) else {
+            return;
+        };
+        if self.cells[index.0] != cell_type {
+            let was_obstacle = self.cells[index.0] == CellType::Obstacle;
+            self.cells[index.0] = cell_type;
+            self.revision = next_revision();
+            if was_obstacle != (cell_type == CellType::Obstacle) {
+                self.obstacles[index.0 / WORD_BITS] ^= 1 << (index.0 % WORD_BITS);
+                for map in self.components.get_mut().unwrap().iter_mut() {
+                    map.mark_edited(x, y);
+                }
+            }
+        }
+    }
+
+    /// Verifica se uma célula é um obstáculo.
+    /// Retorna `true` se estiver fora dos limites ou for um obstáculo.
+    pub fn is_obstacle(&self, x: usize, y: usize) -> bool {
+        if x < self.width && y < self.height {
+            self.is_blocked(CellIndex(y * self.width + x))
+        } else {
+            true // Considera fora dos limites como obstáculo para a busca de caminho
+        }
+    }
+
+    /// Obstáculo pelo índice (que precisa estar dentro do grid)
+    pub fn is_blocked(&self, index: CellIndex) -> bool {
+        self.obstacles[index.0 / WORD_BITS] >> (index.0 % WORD_BITS) & 1 == 1
+    }
+
+    /// Trechos seguidos de obstáculos da linha `y` dentro das colunas `cols`, lidos do conjunto de bits
+    /// (palavras sem obstáculo são puladas de 64 em 64)
+    pub fn obstacle_runs(&self, y: usize, cols: Range<usize>) -> Vec<Range<usize>> {
+        let row = y * self.width;
+        let end = row + cols.end.min(self.width);
+        let mut runs = Vec::new();
+        let mut from = row + cols.start;
+        while from < end {
+            let start = self.next_bit(from, end, true);
+            if start == end {
+                break;
+            }
+            from = self.next_bit(start, end, false);
+            runs.push(start - row..from - row);
+        }
+        runs
+    }
+
+    /// Primeiro índice em `from..end` com o bit de obstáculo igual a `value` (ou `end`)
+    fn next_bit(&self, from: usize, end: usize, value: bool) -> usize {
+        let mut index = from;
+        while index < end {
+            let word = self.obstacles[index / WORD_BITS];
+            let word = if value { word } else { !word } >> (index % WORD_BITS);
+            if word != 0 {
+                return (index + word.trailing_zeros() as usize).min(end);
+            }
+            index = (index / WORD_BITS + 1) * WORD_BITS;
+        }
+        end
+    }
+
+    /// Custo do terreno da célula (1 fora dos limites)
+    pub fn terrain_cost(&self, x: usize, y: usize) -> usize {
+        self.index(x, y).map_or(1, |index| self.terrain[index.0] as usize)
+    }
+
+    /// Define o custo do terreno de uma célula (pelo menos 1, para a heurística do A* continuar válida)
+    pub fn set_terrain_cost(&mut self, x: usize, y: usize, cost: u8) {
+        let cost = cost.max(1);
+        if let Some(index) = self.index(x, y)
+            && self.terrain[index.0] != cost
+        {
+            self.terrain[index.0] = cost;
+            self.revision = next_revision();
+        }
+    }
+
+    /// Verifica se uma célula é uma estação de recarga.
+    pub fn is_charging_station(&self, x: usize, y: usize) -> bool {
+        self.get_cell(x, y) == Some(CellType::ChargingStation)
+    }
+
+    /// Coordenadas de todas as estações de recarga, linha por linha.
+    pub fn charging_stations(&self) -> Vec<(usize, usize)> {
+        (0..self.cells.len())
+            .filter(|&index| self.cells[index] == CellType::ChargingStation)
+            .map(|index| self.position(CellIndex(index)))
+            .collect()
+    }
+
+    /// Limpa todos os obstáculos do grid, redefinindo todas as células para Empty e o terreno para normal
+    /// (sobrescreve os buffers, sem alocar de novo).
+    pub fn clear(&mut self) {
+        self.cells.fill(CellType::Empty);
+        self.obstacles.fill(0);
+        self.terrain.fill(1);
+        self.revision = next_revision();
+        self.components.get_mut().unwrap().clear();
+    }
+
+    /// Substitui todas as células, linha por linha (ex.: ao restaurar um checkpoint)
+    pub fn replace_cells(&mut self, cells: Vec<CellType>) {
+        self.cells = cells;
+        self.obstacles.fill(0);
+        for index in (0..self.cells.len()).filter(|&index| self.cells[index] == CellType::Obstacle) {
+            self.obstacles[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+        }
+        self.revision = next_revision();
+        self.components.get_mut().unwrap().clear();
+    }
+
+    /// Número da versão atual das células
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Encontra e retorna as coordenadas (x, y) de uma célula vazia aleatória.
+    /// Retorna `None` se não conseguir encontrar após muitas tentativas.
+    pub fn get_random_empty_cell(&self) -> Option<(usize, usize)> {
+        let max_attempts = self.width * self.height;
+        let mut attempts = 0;
+
+        while attempts < max_attempts {
+            let x = rand::gen_range(0, self.width);
+            let y = rand::gen_range(0, self.height);
+
+            if !self.is_obstacle(x, y) {
+                return Some((x, y));
+            }
+            attempts += 1;
+        }
+        None // Não encontrou célula vazia
+    }
+
+    /// Consulta as componentes conexas na vizinhança do modo de grid.
+    /// Rotula o grid na primeira consulta de cada vizinhança; depois só aplica as células editadas.
+    pub fn with_components<R>(&self, grid_mode: GridMode, f: impl FnOnce(&ComponentMap) -> R) -> R {
+        let neighborhood = Neighborhood::of(grid_mode);
+        let mut maps = self.components.lock().unwrap();
+        let index = match maps.iter().position(|map| map.neighborhood() == neighborhood) {
+            Some(index) => index,
+            None => {
+                maps.push(ComponentMap::build(self, neighborhood));
+                maps.len() - 1
+            }
+        };
+        maps[index].refresh(self);
+        f(&maps[index])
+    }
+
+    /// Componente conexa da célula (`None` em obstáculo)
+    pub fn component_of(&self, cell: (usize, usize), grid_mode: GridMode) -> Option<u32> {
+        self.with_components(grid_mode, |components| components.component_of(cell))
+    }
+
+    /// Se há caminho entre as células, comparando as componentes (O(1) com as componentes em dia)
+    pub fn is_reachable(&self, from: (usize, usize), to: (usize, usize), grid_mode: GridMode) -> bool {
+        self.with_components(grid_mode, |components| components.connected(from, to))
+    }
+
+    /// Célula livre aleatória da mesma componente (ex.: destino alcançável a partir de um início)
+    pub fn random_empty_cell_in_component(&self, component: u32, grid_mode: GridMode) -> Option<(usize, usize)> {
+        self.with_components(grid_mode, |components| components.random_cell(component))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// O conjunto de bits diz o mesmo que as células, em todo o grid
+    fn assert_bits_match_cells(grid: &Grid) {
+        for index in 0..grid.cell_count() {
+            let expected = grid.cells()[index] == CellType::Obstacle;
+            assert_eq!(grid.is_blocked(CellIndex(index)), expected, "célula {}", index);
+        }
+    }
+
+    /// Trechos de obstáculos lidos célula por célula
+    fn naive_runs(grid: &Grid, y: usize, cols: Range<usize>) -> Vec<Range<usize>> {
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        for x in cols.start..cols.end.min(grid.width) {
+            if grid.get_cell(x, y) != Some(CellType::Obstacle) {
+                continue;
+            }
+            match runs.last_mut() {
+                Some(run) if run.end == x => run.end = x + 1,
+                _ => runs.push(x..x + 1),
+            }
+        }
+        runs
+    }
+
+    #[test]
+    fn bits_follow_cells_across_word_boundaries() {
+        // 70 colunas: as linhas não começam no início de uma palavra e a última palavra fica pela metade
+        let mut grid = Grid::new(70, 3);
+        let last = grid.cell_count() - 1;
+        for index in [63, 64, 127, 128, last] {
+            let (x, y) = grid.position(CellIndex(index));
+            grid.set_cell(x, y, CellType::Obstacle);
+            assert!(grid.is_obstacle(x, y));
+            assert_bits_match_cells(&grid);
+
+            // Trocar entre tipos livres não mexe no bit
+            grid.set_cell(x, y, CellType::ChargingStation);
+            grid.set_cell(x, y, CellType::Empty);
+            assert!(!grid.is_obstacle(x, y));
+            assert_bits_match_cells(&grid);
+        }
+
+        for index in [63, 64, last] {
+            let (x, y) = grid.position(CellIndex(index));
+            grid.set_cell(x, y, CellType::Obstacle);
+        }
+        let cells = grid.cells().to_vec();
+        grid.clear();
+        assert_bits_match_cells(&grid);
+        grid.replace_cells(cells);
+        assert!(grid.is_obstacle(69, 2));
+        assert_bits_match_cells(&grid);
+        // Fora do grid continua bloqueado, mesmo com bits sobrando na última palavra
+        assert!(grid.is_obstacle(70, 2));
+        assert!(grid.is_obstacle(0, 3));
+    }
+
+    #[test]
+    fn obstacle_runs_match_a_naive_scan() {
+        let mut grid = Grid::new(150, 6);
+        let mut state = 12345u32;
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                // Linhas com densidades diferentes (vazia, esparsa, cheia de trechos longos)
+                let density = [0, 10, 30, 50, 80, 100][y];
+                if (state >> 16) % 100 < density {
+                    grid.set_cell(x, y, CellType::Obstacle);
+                }
+            }
+        }
+        for y in 0..grid.height {
+            for cols in [0..150, 0..64, 63..65, 60..130, 64..128, 100..200, 7..7] {
+                let expected = naive_runs(&grid, y, cols.clone());
+                assert_eq!(grid.obstacle_runs(y, cols.clone()), expected, "linha {} {:?}", y, cols);
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::grid::{CellIndex, Grid};
+use crate::hex::{Hex, HexOrientation};
+
+/// Interface unificada (Target) para trabalhar com diferentes tipos de grid.
+/// O Adapter Pattern permite que grids com diferentes sistemas de vizinhança
+/// sejam usados através da mesma interface.
+pub trait GridAdapter {
+    /// Grid adaptado (as buscas usam o tamanho e os índices dele para as tabelas de custo)
+    fn grid(&self) -> &Grid;
+
+    /// Retorna os vizinhos de uma célula, independente do tipo de grid
+    fn get_neighbors(&self, pos: (usize, usize)) -> Vec<(usize, usize)>;
+
+    /// Verifica se uma posição é válida e não é obstáculo
+    fn is_valid_position(&self, pos: (usize, usize)) -> bool;
+
+    /// Calcula o custo de movimento entre duas células adjacentes (já com o custo do terreno do destino)
+    fn movement_cost(&self, from: (usize, usize), to: (usize, usize)) -> usize;
+
+    /// Estimativa do custo até o destino usada pelo A* (padrão: distância de Manhattan)
+    fn heuristic(&self, from: (usize, usize), to: (usize, usize)) -> usize {
+        from.0.abs_diff(to.0) + from.1.abs_diff(to.1)
+    }
+}
+
+/// Adapter Concreto: Grid Retangular com 4 direções (Cardinal)
+pub struct RectangularCardinalAdapter<'a> {
+    grid: &'a Grid,
+}
+
+impl<'a> RectangularCardinalAdapter<'a> {
+    pub fn new(grid: &'a Grid) -> Self {
+        Self { grid }
+    }
+}
+
+impl<'a> GridAdapter for RectangularCardinalAdapter<'a> {
+    fn grid(&self) -> &Grid {
+        self.grid
+    }
+
+    fn get_neighbors(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        let Some(index) = self.grid.index(pos.0, pos.1) else {
+            return Vec::new();
+        };
+        let (x, y) = pos;
+
+        // Norte, sul, oeste e leste: o índice anda ±largura ou ±1 e o obstáculo é um bit
+        let directions = [
+            (y > 0, 0, -1),                   // Norte (cima)
+            (y + 1 < self.grid.height, 0, 1), // Sul (baixo)
+            (x > 0, -1, 0),                   // Oeste (esquerda)
+            (x + 1 < self.grid.width, 1, 0),  // Leste (direita)
+        ];
+        directions
+            .into_iter()
+            .filter(|&(inside, dx, dy)| inside && !self.grid.is_blocked(index.offset(dx, dy, self.grid.width)))
+            .map(|(_, dx, dy)| (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)))
+            .collect()
+    }
+
+    fn is_valid_position(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.grid.width && pos.1 < self.grid.height && !self.grid.is_obstacle(pos.0, pos.1)
+    }
+
+    fn movement_cost(&self, _from: (usize, usize), to: (usize, usize)) -> usize {
+        // Custo uniforme para movimento cardinal, vezes o custo do terreno
+        self.grid.terrain_cost(to.0, to.1)
+    }
+}
+
+/// Adapter Concreto: Grid Retangular com 8 direções (Cardinal + Diagonal)
+pub struct RectangularDiagonalAdapter<'a> {
+    grid: &'a Grid,
+}
+
+impl<'a> RectangularDiagonalAdapter<'a> {
+    pub fn new(grid: &'a Grid) -> Self {
+        Self { grid }
+    }
+}
+
+impl<'a> GridAdapter for RectangularDiagonalAdapter<'a> {
+    fn grid(&self) -> &Grid {
+        self.grid
+    }
+
+    fn get_neighbors(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut neighbors = Vec::with_capacity(8);
+        let Some(index) = self.grid.index(pos.0, pos.1) else {
+            return neighbors;
+        };
+        let x = pos.0 as isize;
+        let y = pos.1 as isize;
+
+        // 8 direções: cardinais + diagonais
+        let directions = [
+            (0, -1),  // Norte
+            (0, 1),   // Sul
+            (-1, 0),  // Oeste
+            (1, 0),   // Leste
+            (-1, -1), // Noroeste
+            (1, -1),  // Nordeste
+            (-1, 1),  // Sudoeste
+            (1, 1),   // Sudeste
+        ];
+
+        for (dx, dy) in directions {
+            let nx = x + dx;
+            let ny = y + dy;
+
+            if nx >= 0
+                && ny >= 0
+                && (nx as usize) < self.grid.width
+                && (ny as usize) < self.grid.height
+                && !self.grid.is_blocked(index.offset(dx, dy, self.grid.width))
+            {
+                neighbors.push((nx as usize, ny as usize));
+            }
+        }
+
+        neighbors
+    }
+
+    fn is_valid_position(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.grid.width && pos.1 < self.grid.height && !self.grid.is_obstacle(pos.0, pos.1)
+    }
+
+    fn movement_cost(&self, from: (usize, usize), to: (usize, usize)) -> usize {
+        // Movimento diagonal custa mais (aproximadamente √2 ≈ 1.414)
+        // Usamos 14 para movimento diagonal e 10 para cardinal
+        let dx = from.0.abs_diff(to.0);
+        let dy = from.1.abs_diff(to.1);
+
+        let step = if dx > 0 && dy > 0 {
+            14 // Diagonal
+        } else {
+            10 // Cardinal
+        };
+        step * self.grid.terrain_cost(to.0, to.1)
+    }
+}
+
+/// Adapter Concreto: Grid Hexagonal
+/// Em grids hexagonais, cada célula tem 6 vizinhos. As células do `Grid` são (coluna, linha)
+/// deslocadas conforme a orientação; os vizinhos e a distância saem das coordenadas axiais.
+pub struct HexagonalAdapter<'a> {
+    grid: &'a Grid,
+    orientation: HexOrientation,
+}
+
+impl<'a> HexagonalAdapter<'a> {
+    pub fn new(grid: &'a Grid, orientation: HexOrientation) -> Self {
+        Self { grid, orientation }
+    }
+
+    fn axial(&self, pos: (usize, usize)) -> Hex {
+        Hex::from_offset(pos.0 as i32, pos.1 as i32, self.orientation)
+    }
+}
+
+impl<'a> GridAdapter for HexagonalAdapter<'a> {
+    fn grid(&self) -> &Grid {
+        self.grid
+    }
+
+    fn get_neighbors(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        // Vizinhos pelas coordenadas axiais (o deslocamento das linhas ímpares muda o índice); o obstáculo é um bit
+        self.axial(pos)
+            .neighbors()
+            .into_iter()
+            .filter_map(|hex| hex.to_cell(self.orientation, self.grid.width, self.grid.height))
+            .filter(|&(x, y)| !self.grid.is_blocked(CellIndex(y * self.grid.width + x)))
+            .collect()
+    }
+
+    fn is_valid_position(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.grid.width && pos.1 < self.grid.height && !self.grid.is_obstacle(pos.0, pos.1)
+    }
+
+    fn movement_cost(&self, _from: (usize, usize), to: (usize, usize)) -> usize {
+        // Custo uniforme para todos os 6 vizinhos hexagonais, vezes o custo do terreno
+        self.grid.terrain_cost(to.0, to.1)
+    }
+
+    fn heuristic(&self, from: (usize, usize), to: (usize, usize)) -> usize {
+        self.axial(from).distance(self.axial(to)) as usize
+    }
+}
@@ -0,0 +1,502 @@
+use crate::grid::{CellType, Grid};
+
+/// Contrato (Trait) para qualquer fábrica responsável por criar estruturas de Grid.
+/// Isso permite a criação de diferentes tipos de grid sem alterar o código que os utiliza.
+pub trait GridFactory {
+    /// Cria e retorna uma nova instância de Grid.
+    fn create(&self, width: usize, height: usize) -> Grid;
+}
+
+/// Implementação Concreta: Fábrica para o Grid Retangular Padrão.
+pub struct RectangularGridFactory;
+
+impl GridFactory for RectangularGridFactory {
+    fn create(&self, width: usize, height: usize) -> Grid {
+        // Simplesmente chama o construtor do Grid existente.
+        Grid::new(width, height)
+    }
+}
+
+/// Mapas gerados que a corrente de inicialização, os cenários em arquivo e os benchmarks podem pedir
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum MapGenerator {
+    #[default]
+    Empty,      // Grid vazio (o padrão de sempre)
+    Maze,       // Labirinto (backtracking recursivo)
+    Caves,      // Cavernas (autômato celular)
+    Rooms,      // Salas ligadas por corredores
+    Rectangles, // Retângulos aleatórios
+    Terrain,    // Terreno com custos (ruído de Perlin)
+}
+
+impl MapGenerator {
+    /// Converte o nome usado na linha de comando e nos arquivos de cenário
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "empty" | "vazio" => Some(MapGenerator::Empty),
+            "maze" | "labirinto" => Some(MapGenerator::Maze),
+            "caves" | "cavernas" => Some(MapGenerator::Caves),
+            "rooms" | "salas" => Some(MapGenerator::Rooms),
+            "rectangles" | "retangulos" => Some(MapGenerator::Rectangles),
+            "terrain" | "terreno" => Some(MapGenerator::Terrain),
+            _ => None,
+        }
+    }
+
+    /// Nome curto usado em arquivos e no HUD
+    pub fn name(&self) -> &'static str {
+        match self {
+            MapGenerator::Empty => "empty",
+            MapGenerator::Maze => "maze",
+            MapGenerator::Caves => "caves",
+            MapGenerator::Rooms => "rooms",
+            MapGenerator::Rectangles => "rectangles",
+            MapGenerator::Terrain => "terrain",
+        }
+    }
+
+    /// Factory: a fábrica de grid do gerador, com a semente dada
+    pub fn create_factory(&self, seed: u64) -> Box<dyn GridFactory> {
+        match self {
+            MapGenerator::Empty => Box::new(RectangularGridFactory),
+            MapGenerator::Maze => Box::new(MazeGridFactory::new(seed)),
+            MapGenerator::Caves => Box::new(CaveGridFactory::new(seed)),
+            MapGenerator::Rooms => Box::new(RoomsGridFactory::new(seed)),
+            MapGenerator::Rectangles => Box::new(RectanglesGridFactory::new(seed)),
+            MapGenerator::Terrain => Box::new(TerrainGridFactory::new(seed)),
+        }
+    }
+}
+
+/// Gerador pseudoaleatório dos mapas (SplitMix64). É separado do `macroquad::rand` para que
+/// gerar um mapa não mude os sorteios seguintes da simulação e a mesma semente dê sempre o mesmo mapa.
+struct MapRng(u64);
+
+impl MapRng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Inteiro em `low..high` (`low` se o intervalo for vazio)
+    fn range(&mut self, low: usize, high: usize) -> usize {
+        if high <= low { low } else { low + (self.next_u64() % (high - low) as u64) as usize }
+    }
+
+    /// Número em [0, 1)
+    fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Preenche a região inteira com obstáculos
+fn fill_obstacles(grid: &mut Grid) {
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            grid.set_cell(x, y, CellType::Obstacle);
+        }
+    }
+}
+
+/// Abre (ou fecha) o retângulo `x0..x1` x `y0..y1`, cortado nos limites do grid
+fn set_rect(grid: &mut Grid, (x0, y0): (usize, usize), (x1, y1): (usize, usize), cell_type: CellType) {
+    for y in y0..y1.min(grid.height) {
+        for x in x0..x1.min(grid.width) {
+            grid.set_cell(x, y, cell_type);
+        }
+    }
+}
+
+/// Garante a conectividade: só a maior região livre (vizinhança de 4, a primeira em ordem de linha
+/// em caso de empate) continua livre; as outras viram obstáculo. Duas células vizinhas em 4 direções
+/// também são vizinhas nos grids diagonal e hexagonal, então o mapa fica conexo nos três.
+pub fn keep_largest_region(grid: &mut Grid) {
+    let (width, height) = (grid.width, grid.height);
+    let mut region = vec![usize::MAX; width * height];
+    let mut sizes = Vec::new();
+    for start in 0..width * height {
+        if region[start] != usize::MAX || grid.is_obstacle(start % width, start / width) {
+            continue;
+        }
+        let id = sizes.len();
+        let mut size = 0;
+        let mut stack = vec![start];
+        region[start] = id;
+        while let Some(i) = stack.pop() {
+            size += 1;
+            let (x, y) = (i % width, i / width);
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < width).then_some(i + 1),
+                (y > 0).then(|| i - width),
+                (y + 1 < height).then_some(i + width),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                if region[n] == usize::MAX && !grid.is_obstacle(n % width, n / width) {
+                    region[n] = id;
+                    stack.push(n);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+
+    let Some(largest) = (0..sizes.len()).max_by_key(|&id| (sizes[id], std::cmp::Reverse(id))) else {
+        return;
+    };
+    for (i, &id) in region.iter().enumerate() {
+        if id != usize::MAX && id != largest {
+            grid.set_cell(i % width, i / width, CellType::Obstacle);
+        }
+    }
+}
+
+/// Labirinto perfeito pelo backtracking recursivo (com pilha explícita): corredores de `corridor`
+/// células separados por paredes de uma célula. Um labirinto perfeito já é conexo.
+pub struct MazeGridFactory {
+    pub seed: u64,
+    pub corridor: usize,
+}
+
+impl MazeGridFactory {
+    pub fn new(seed: u64) -> Self {
+        // Corredores de 2 células: um agente padrão não passa folgado por uma só
+        Self { seed, corridor: 2 }
+    }
+}
+
+impl GridFactory for MazeGridFactory {
+    fn create(&self, width: usize, height: usize) -> Grid {
+        let mut grid = Grid::new(width, height);
+        let unit = self.corridor.max(1) + 1;
+        let (cols, rows) = (width.saturating_sub(1) / unit, height.saturating_sub(1) / unit);
+        if cols == 0 || rows == 0 {
+            return grid;
+        }
+        fill_obstacles(&mut grid);
+
+        let mut rng = MapRng::new(self.seed);
+        let origin = |(cx, cy): (usize, usize)| (1 + cx * unit, 1 + cy * unit);
+        let mut visited = vec![false; cols * rows];
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+        set_rect(&mut grid, (1, 1), (unit, unit), CellType::Empty);
+        while let Some(&(cx, cy)) = stack.last() {
+            let mut options = Vec::with_capacity(4);
+            if cx > 0 && !visited[cy * cols + cx - 1] {
+                options.push((cx - 1, cy));
+            }
+            if cx + 1 < cols && !visited[cy * cols + cx + 1] {
+                options.push((cx + 1, cy));
+            }
+            if cy > 0 && !visited[(cy - 1) * cols + cx] {
+                options.push((cx, cy - 1));
+            }
+            if cy + 1 < rows && !visited[(cy + 1) * cols + cx] {
+                options.push((cx, cy + 1));
+            }
+            if options.is_empty() {
+                stack.pop();
+                continue;
+            }
+
+            // Abre a célula sorteada e a parede entre ela e a atual
+            let next = options[rng.range(0, options.len())];
+            visited[next.1 * cols + next.0] = true;
+            let (a, b) = (origin((cx, cy)), origin(next));
+            let (x0, y0) = (a.0.min(b.0), a.1.min(b.1));
+            let (x1, y1) = (a.0.max(b.0) + unit - 1, a.1.max(b.1) + unit - 1);
+            set_rect(&mut grid, (x0, y0), (x1, y1), CellType::Empty);
+            stack.push(next);
+        }
+        grid
+    }
+}
+
+/// Cavernas por autômato celular: ruído inicial com `fill` de paredes e `iterations` rodadas
+/// da regra 4-5 (vira parede com 5 ou mais paredes no 3x3, contando a borda como parede)
+pub struct CaveGridFactory {
+    pub seed: u64,
+    pub fill: f32,
+    pub iterations: usize,
+}
+
+impl CaveGridFactory {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, fill: 0.45, iterations: 5 }
+    }
+}
+
+impl GridFactory for CaveGridFactory {
+    fn create(&self, width: usize, height: usize) -> Grid {
+        let mut rng = MapRng::new(self.seed);
+        let mut walls: Vec<bool> = (0..width * height).map(|_| rng.unit() < self.fill).collect();
+        for _ in 0..self.iterations {
+            walls = (0..width * height)
+                .map(|i| {
+                    let (x, y) = ((i % width) as isize, (i / width) as isize);
+                    let mut count = 0;
+                    for dy in -1..=1 {
+                        for dx in -1..=1 {
+                            let (nx, ny) = (x + dx, y + dy);
+                            let outside = nx < 0 || ny < 0 || nx >= width as isize || ny >= height as isize;
+                            if outside || walls[ny as usize * width + nx as usize] {
+                                count += 1;
+                            }
+                        }
+                    }
+                    count >= 5
+                })
+                .collect();
+        }
+
+        let mut grid = Grid::new(width, height);
+        for (i, _) in walls.iter().enumerate().filter(|(_, wall)| **wall) {
+            grid.set_cell(i % width, i / width, CellType::Obstacle);
+        }
+        keep_largest_region(&mut grid);
+        grid
+    }
+}
+
+/// Salas retangulares sem sobreposição, cada uma ligada à anterior por um corredor em L
+/// de `corridor` células de largura (então todas ficam ligadas)
+pub struct RoomsGridFactory {
+    pub seed: u64,
+    /// Tentativas de colocar uma sala (as que encostam em outra são descartadas)
+    pub attempts: usize,
+    pub min_size: usize,
+    pub max_size: usize,
+    pub corridor: usize,
+}
+
+impl RoomsGridFactory {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, attempts: 60, min_size: 4, max_size: 12, corridor: 2 }
+    }
+}
+
+impl GridFactory for RoomsGridFactory {
+    fn create(&self, width: usize, height: usize) -> Grid {
+        let mut grid = Grid::new(width, height);
+        if width < self.min_size + 2 || height < self.min_size + 2 {
+            return grid;
+        }
+        fill_obstacles(&mut grid);
+
+        let mut rng = MapRng::new(self.seed);
+        // Salas como (x, y, largura, altura), com uma célula de parede em volta
+        let mut rooms: Vec<(usize, usize, usize, usize)> = Vec::new();
+        for _ in 0..self.attempts {
+            let w = rng.range(self.min_size, self.max_size.min(width - 2) + 1);
+            let h = rng.range(self.min_size, self.max_size.min(height - 2) + 1);
+            let x = rng.range(1, width - w);
+            let y = rng.range(1, height - h);
+            let touches = rooms
+                .iter()
+                .any(|&(rx, ry, rw, rh)| x <= rx + rw && rx <= x + w && y <= ry + rh && ry <= y + h);
+            if touches {
+                continue;
+            }
+            set_rect(&mut grid, (x, y), (x + w, y + h), CellType::Empty);
+            if let Some(&(px, py, pw, ph)) = rooms.last() {
+                let (ax, ay) = (px + pw / 2, py + ph / 2);
+                let (bx, by) = (x + w / 2, y + h / 2);
+                let c = self.corridor.max(1);
+                // Corredor em L: horizontal e depois vertical, ou o contrário
+                let (corner_x, corner_y) = if rng.unit() < 0.5 { (bx, ay) } else { (ax, by) };
+                set_rect(&mut grid, (ax.min(corner_x), ay.min(corner_y)), (ax.max(corner_x) + c, ay.max(corner_y) + c), CellType::Empty);
+                set_rect(&mut grid, (bx.min(corner_x), by.min(corner_y)), (bx.max(corner_x) + c, by.max(corner_y) + c), CellType::Empty);
+            }
+            rooms.push((x, y, w, h));
+        }
+        keep_largest_region(&mut grid);
+        grid
+    }
+}
+
+/// Retângulos de obstáculo espalhados; bolsões fechados por eles viram obstáculo
+pub struct RectanglesGridFactory {
+    pub seed: u64,
+    /// Fração aproximada do grid coberta pelos retângulos (com sobreposição)
+    pub coverage: f32,
+    pub max_size: usize,
+}
+
+impl RectanglesGridFactory {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, coverage: 0.25, max_size: 8 }
+    }
+}
+
+impl GridFactory for RectanglesGridFactory {
+    fn create(&self, width: usize, height: usize) -> Grid {
+        let mut grid = Grid::new(width, height);
+        if width == 0 || height == 0 {
+            return grid;
+        }
+        let mut rng = MapRng::new(self.seed);
+        let mut covered = 0;
+        let target = (width * height) as f32 * self.coverage;
+        while (covered as f32) < target {
+            let w = rng.range(1, self.max_size.min(width) + 1);
+            let h = rng.range(1, self.max_size.min(height) + 1);
+            let x = rng.range(0, width - w + 1);
+            let y = rng.range(0, height - h + 1);
+            set_rect(&mut grid, (x, y), (x + w, y + h), CellType::Obstacle);
+            covered += w * h;
+        }
+        keep_largest_region(&mut grid);
+        grid
+    }
+}
+
+/// Terreno por ruído de Perlin em oitavas: as partes mais baixas são água (obstáculo)
+/// e o resto recebe custos crescentes com a altura (campo, mata, morro, montanha)
+pub struct TerrainGridFactory {
+    pub seed: u64,
+    /// Tamanho (em células) da onda da primeira oitava
+    pub scale: f32,
+    pub octaves: usize,
+    /// Altura (0 a 1) abaixo da qual a célula é água
+    pub water_level: f32,
+}
+
+impl TerrainGridFactory {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, scale: 16.0, octaves: 4, water_level: 0.3 }
+    }
+}
+
+/// Alturas acima das quais o terreno custa 2, 3 e 5
+const TERRAIN_LEVELS: [(f32, u8); 3] = [(0.75, 5), (0.62, 3), (0.5, 2)];
+
+impl GridFactory for TerrainGridFactory {
+    fn create(&self, width: usize, height: usize) -> Grid {
+        let noise = PerlinNoise::new(self.seed);
+        let heights: Vec<f32> = (0..width * height)
+            .map(|i| {
+                let (x, y) = ((i % width) as f32, (i / width) as f32);
+                let (mut value, mut amplitude, mut frequency) = (0.0, 1.0, 1.0 / self.scale.max(1.0));
+                for _ in 0..self.octaves.max(1) {
+                    value += noise.sample(x * frequency, y * frequency) * amplitude;
+                    amplitude *= 0.5;
+                    frequency *= 2.0;
+                }
+                value
+            })
+            .collect();
+        // A soma das oitavas fica concentrada perto do meio: as alturas são esticadas para [0, 1]
+        let (low, high) = heights.iter().fold((f32::MAX, f32::MIN), |(low, high), &h| (low.min(h), high.max(h)));
+        let span = (high - low).max(f32::EPSILON);
+
+        let mut grid = Grid::new(width, height);
+        for (i, &h) in heights.iter().enumerate() {
+            let (x, y) = (i % width, i / width);
+            let elevation = (h - low) / span;
+            if elevation < self.water_level {
+                grid.set_cell(x, y, CellType::Obstacle);
+            } else if let Some(&(_, cost)) = TERRAIN_LEVELS.iter().find(|&&(level, _)| elevation >= level) {
+                grid.set_terrain_cost(x, y, cost);
+            }
+        }
+        keep_largest_region(&mut grid);
+        grid
+    }
+}
+
+/// Ruído de Perlin 2D clássico: gradientes nos cantos de cada célula, interpolados pela curva 6t⁵-15t⁴+10t³
+struct PerlinNoise {
+    permutation: [u8; 512],
+}
+
+impl PerlinNoise {
+    fn new(seed: u64) -> Self {
+        let mut rng = MapRng::new(seed);
+        let mut table: Vec<u8> = (0..=255).collect();
+        for i in (1..table.len()).rev() {
+            table.swap(i, rng.range(0, i + 1));
+        }
+        let mut permutation = [0; 512];
+        for (i, p) in permutation.iter_mut().enumerate() {
+            *p = table[i % 256];
+        }
+        Self { permutation }
+    }
+
+    /// Valor aproximadamente em [-1, 1]
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (xf, yf) = (x.floor(), y.floor());
+        let (xi, yi) = ((xf as i64 & 255) as usize, (yf as i64 & 255) as usize);
+        let (dx, dy) = (x - xf, y - yf);
+        let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+        let (u, v) = (fade(dx), fade(dy));
+
+        let p = &self.permutation;
+        let corner = |cx: usize, cy: usize, ox: f32, oy: f32| {
+            // Oito direções de gradiente
+            let (gx, gy) = match p[p[xi + cx] as usize + yi + cy] & 7 {
+                0 => (1.0, 1.0),
+                1 => (-1.0, 1.0),
+                2 => (1.0, -1.0),
+                3 => (-1.0, -1.0),
+                4 => (1.0, 0.0),
+                5 => (-1.0, 0.0),
+                6 => (0.0, 1.0),
+                _ => (0.0, -1.0),
+            };
+            gx * ox + gy * oy
+        };
+        let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+        let top = lerp(corner(0, 0, dx, dy), corner(1, 0, dx - 1.0, dy), u);
+        let bottom = lerp(corner(0, 1, dx, dy - 1.0), corner(1, 1, dx - 1.0, dy - 1.0), u);
+        lerp(top, bottom, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridMode;
+    use crate::benchmark::random_connected_pair;
+    use crate::components::{ComponentMap, Neighborhood};
+
+    const GENERATORS: [MapGenerator; 5] =
+        [MapGenerator::Maze, MapGenerator::Caves, MapGenerator::Rooms, MapGenerator::Rectangles, MapGenerator::Terrain];
+
+    #[test]
+    fn same_seed_same_grid() {
+        for generator in GENERATORS {
+            for seed in [1, 7, 42] {
+                let first = generator.create_factory(seed).create(60, 36);
+                let second = generator.create_factory(seed).create(60, 36);
+                assert_eq!(first.cells(), second.cells(), "{} seed {}", generator.name(), seed);
+            }
+            let (a, b) = (generator.create_factory(1).create(60, 36), generator.create_factory(2).create(60, 36));
+            assert_ne!(a.cells(), b.cells(), "{}: sementes diferentes deram o mesmo mapa", generator.name());
+        }
+    }
+
+    #[test]
+    fn generated_pairs_are_connected() {
+        for generator in GENERATORS {
+            let grid = generator.create_factory(7).create(60, 36);
+            for grid_mode in [GridMode::Cardinal, GridMode::Diagonal, GridMode::Hexagonal] {
+                let components = ComponentMap::build(&grid, Neighborhood::of(grid_mode));
+                assert_eq!(components.count(), 1, "{} em {:?}", generator.name(), grid_mode);
+                for _ in 0..50 {
+                    let (start, goal) = random_connected_pair(&grid, grid_mode).expect("mapa sem células livres");
+                    let label = format!("{} em {:?}: {:?} -> {:?}", generator.name(), grid_mode, start, goal);
+                    assert!(components.connected(start, goal), "{}", label);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1031 @@
+use crate::agent_decorator::AgentComponent;
+use crate::agent_factory::BlueAgentFactory;
+use crate::benchmark;
+use crate::camera::Viewport;
+use crate::command::CommandManager;
+use crate::frame_export::{self, Frame, FrameFormat, FrameStyle};
+use crate::grid::{CellType, Grid};
+use crate::hex::{HexLayout, HexOrientation};
+use crate::grid_factory::MapGenerator;
+use crate::initialization::{init_system, init_system_with_map};
+use crate::navmesh::PathPlanner;
+use crate::energy::{self, Route};
+use crate::path_manager::{PathKey, PathManager};
+use crate::pathfinding_adapter::a_star_with_adapter;
+use crate::{hexagonal_renderer, renderer};
+use crate::observer;
+use crate::replay::{ReplayHeader, ReplayLog, ReplayRecorder};
+use crate::rvo::RvoStates;
+use crate::scenario::{self, DecoratorSpec, ScenarioFile};
+use crate::collision_avoidance::{AvoidanceMode, RvoStrategy};
+use crate::simulation::{self, CollisionMonitor};
+use crate::video::{self, VideoFormat, VideoOptions, VideoRecorder};
+use crate::{CELL_SIZE, GRID_HEIGHT, GRID_WIDTH, GridMode};
+use macroquad::prelude::{Rect, Vec2, vec2};
+use macroquad::rand;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Cenários disponíveis no modo headless
+#[derive(Debug, Clone)]
+pub enum Scenario {
+    Opposing, // Fileiras opostas (teclas 1 e 2 na janela)
+    Random,   // Inícios e destinos aleatórios (tecla 3 na janela)
+    /// Cenário descrito em arquivo TOML/JSON (o caminho vai para o log de replay)
+    File(PathBuf, Box<ScenarioFile>),
+}
+
+impl Scenario {
+    /// Nome de um cenário fixo ou caminho de um arquivo `.toml`/`.json`
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "opposing" | "rows" => Ok(Scenario::Opposing),
+            "random" => Ok(Scenario::Random),
+            lower if lower.ends_with(".toml") || lower.ends_with(".json") => {
+                let path = PathBuf::from(name);
+                let file = ScenarioFile::load(&path)?;
+                Ok(Scenario::File(path, Box::new(file)))
+            }
+            _ => Err(format!("Cenário desconhecido: '{}'", name)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Scenario::Opposing => "opposing",
+            Scenario::Random => "random",
+            Scenario::File(_, file) => file.name(),
+        }
+    }
+
+    /// Como o cenário é gravado no cabeçalho do replay (`parse` o lê de volta)
+    fn replay_name(&self) -> String {
+        match self {
+            Scenario::File(path, _) => path.display().to_string(),
+            _ => self.name().to_string(),
+        }
+    }
+}
+
+/// Parâmetros de uma execução do benchmark sem janela
+pub struct HeadlessConfig {
+    pub scenario: Scenario,
+    pub agents: usize,
+    pub seed: u64,
+    pub avoidance: AvoidanceMode,
+    pub grid_mode: GridMode,
+    pub planner: PathPlanner,
+    /// Orientação e tamanho dos hexágonos (só importa no grid hexagonal)
+    pub hex_layout: HexLayout,
+    pub steps: usize,
+    pub dt: f32,
+    pub out_dir: PathBuf,
+    /// Log de replay (JSON Lines) com todos os comandos executados
+    pub record: Option<PathBuf>,
+    /// Ticks em que o estado é gravado como imagem (0 = antes do primeiro passo)
+    pub frames: Vec<usize>,
+    pub frame_format: FrameFormat,
+    /// Escala, camadas e legenda das imagens e do vídeo
+    pub frame_style: FrameStyle,
+    /// GIF ou sequência de PNGs da execução
+    pub video: Option<VideoOptions>,
+    /// Gerador do mapa (com arquivo de cenário, troca o `generator` dele)
+    pub map: MapGenerator,
+    /// Threads medidas no `--plan-benchmark` e no `--rvo-benchmark` (vazio: 1, 2, 4... até os núcleos da máquina)
+    pub threads: Vec<usize>,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            scenario: Scenario::Random,
+            agents: 100,
+            seed: 42,
+            avoidance: AvoidanceMode::Rvo,
+            grid_mode: GridMode::Cardinal,
+            planner: PathPlanner::Grid,
+            hex_layout: HexLayout::default(),
+            steps: 1800,
+            dt: 1.0 / 60.0,
+            out_dir: PathBuf::from("benchmarks"),
+            record: None,
+            frames: Vec::new(),
+            frame_format: FrameFormat::Png,
+            frame_style: FrameStyle::default(),
+            video: None,
+            map: MapGenerator::Empty,
+            threads: Vec::new(),
+        }
+    }
+}
+
+pub const USAGE: &str = "Uso: navegacao --benchmark [opções]
+  --scenario <opposing|random|arquivo.toml|arquivo.json>  cenário (padrão: random)
+                                 o arquivo define agentes, semente, método, grid e duração;
+                                 as outras opções valem por cima dele
+  --agents <N>                   número de agentes (padrão: 100; ignorado com arquivo)
+  --seed <N>                     semente do gerador aleatório (padrão: 42)
+  --avoidance <rvo|pheromone|none|off>  prevenção de colisão (padrão: rvo)
+  --grid <cardinal|diagonal|hexagonal>  vizinhança do A* (padrão: cardinal)
+  --planner <grid|navmesh>       planejador das rotas (padrão: grid)
+  --map <empty|maze|caves|rooms|rectangles|terrain>  mapa gerado com a semente (padrão: empty)
+  --hex <pointy|flat>            orientação dos hexágonos (padrão: pointy)
+  --hex-size <px>                raio dos hexágonos (padrão: 15)
+  --steps <N>                    passos simulados (padrão: 1800)
+  --dt <segundos>                passo fixo da simulação (padrão: 1/60)
+  --out <pasta>                  pasta dos CSVs (padrão: benchmarks)
+  --record <arquivo>             grava o log de replay (JSON Lines)
+  --frames <t1,t2,...>           ticks gravados como imagem em <out>/<nome>_frames/
+  --frame-format <png|svg>       formato das imagens (padrão: png)
+
+Uso: navegacao --plan-benchmark [--grid ...] [--planner ...] [--map ...] [--seed N] [--out pasta] [--threads 1,2,4]
+  cronometra o planejamento em lote das rotas de 100 e 500 agentes aleatórios em grids com obstáculos
+  (sorteados ou, com --map, gerados), com cada número de threads (padrão: 1, 2, 4... até os núcleos
+  da máquina), e grava planning_*.csv
+
+Uso: navegacao --rvo-benchmark [--seed N] [--dt segundos] [--out pasta] [--threads 1,2,4]
+  cronometra o cálculo de velocidades do RVO com 1000 a 10000 agentes em um grid quadrado com
+  obstáculos, com cada número de threads, confere se todas dão o mesmo resultado e grava rvo_scaling_*.csv
+
+Uso: navegacao --grid-benchmark [--grid ...] [--map ...] [--seed N] [--out pasta]
+  cronometra a geração de um mapa de 2000x2000 (padrão: caves), is_obstacle em todas as células, clear,
+  20 buscas A* e a passada das células dos renderizadores, e grava grid_*.csv
+
+Uso: navegacao --verify <arquivo>
+  refaz a execução descrita no log e compara os comandos gerados";
+
+impl HeadlessConfig {
+    /// Lê os argumentos da linha de comando (sem o nome do programa)
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut config = HeadlessConfig::default();
+
+        // O arquivo de cenário vira o padrão; as opções explícitas são aplicadas depois
+        if let Some(i) = args.iter().position(|a| a == "--scenario")
+            && let Some(value) = args.get(i + 1)
+        {
+            config.scenario = Scenario::parse(value)?;
+            if let Scenario::File(_, file) = &config.scenario {
+                config.seed = file.seed;
+                config.avoidance = file.avoidance();
+                config.grid_mode = file.grid_mode();
+                config.planner = file.planner();
+                config.hex_layout = file.hex_layout();
+                config.map = file.map_generator();
+            }
+        }
+
+        let mut steps = None;
+        let mut video = VideoOptions::default();
+        let mut iter = args.iter();
+
+        while let Some(flag) = iter.next() {
+            if flag == "--benchmark" || flag == "--plan-benchmark" || flag == "--rvo-benchmark" || flag == "--grid-benchmark" {
+                continue;
+            }
+
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("Faltou o valor de '{}'", flag))?;
+
+            if video::parse_option(flag, value, &mut video, &mut config.frame_style)? {
+                if flag == "--video" {
+                    config.video = Some(video);
+                }
+                continue;
+            }
+
+            match flag.as_str() {
+                "--scenario" => {}
+                "--agents" => config.agents = parse_number(flag, value)?,
+                "--seed" => config.seed = parse_number(flag, value)?,
+                "--avoidance" => {
+                    config.avoidance = AvoidanceMode::parse(value)
+                        .ok_or_else(|| format!("Modo de prevenção desconhecido: '{}'", value))?;
+                }
+                "--grid" => {
+                    config.grid_mode = GridMode::parse(value)
+                        .ok_or_else(|| format!("Grid desconhecido: '{}'", value))?;
+                }
+                "--planner" => {
+                    config.planner = PathPlanner::parse(value)
+                        .ok_or_else(|| format!("Planejador desconhecido: '{}'", value))?;
+                }
+                "--map" => {
+                    config.map = MapGenerator::parse(value)
+                        .ok_or_else(|| format!("Gerador de mapa desconhecido: '{}'", value))?;
+                }
+                "--hex" => {
+                    config.hex_layout.orientation = HexOrientation::parse(value)
+                        .ok_or_else(|| format!("Orientação hexagonal desconhecida: '{}'", value))?;
+                }
+                "--hex-size" => config.hex_layout.size = parse_number(flag, value)?,
+                "--steps" => steps = Some(parse_number(flag, value)?),
+                "--dt" => config.dt = parse_number(flag, value)?,
+                "--out" => config.out_dir = PathBuf::from(value),
+                "--record" => config.record = Some(PathBuf::from(value)),
+                "--threads" => {
+                    config.threads = value
+                        .split(',')
+                        .map(|n| parse_number(flag, n.trim()))
+                        .collect::<Result<_, _>>()?;
+                    if config.threads.contains(&0) {
+                        return Err("--threads precisa de números positivos".to_string());
+                    }
+                }
+                "--frames" => {
+                    config.frames = value
+                        .split(',')
+                        .map(|tick| parse_number(flag, tick.trim()))
+                        .collect::<Result<_, _>>()?;
+                    config.frames.sort_unstable();
+                    config.frames.dedup();
+                }
+                "--frame-format" => {
+                    config.frame_format = FrameFormat::parse(value)
+                        .ok_or_else(|| format!("Formato de imagem desconhecido: '{}'", value))?;
+                }
+                _ => return Err(format!("Opção desconhecida: '{}'", flag)),
+            }
+        }
+
+        if config.dt <= 0.0 {
+            return Err("--dt precisa ser positivo".to_string());
+        }
+        if config.hex_layout.size <= 0.0 {
+            return Err("--hex-size precisa ser positivo".to_string());
+        }
+        // As opções de vídeo valem mesmo se vierem antes do --video
+        if config.video.is_some() {
+            config.video = Some(video);
+        }
+
+        // Com arquivo, o número de agentes e a duração vêm dele
+        if let Scenario::File(_, file) = &config.scenario {
+            config.agents = file.agent_count();
+            config.steps = (file.duration / config.dt).round() as usize;
+        }
+        if let Some(steps) = steps {
+            config.steps = steps;
+        }
+        if let Some(&tick) = config.frames.iter().find(|&&tick| tick > config.steps) {
+            return Err(format!("--frames: o tick {} passa do fim da execução ({} passos)", tick, config.steps));
+        }
+        config.apply_map();
+
+        Ok(config)
+    }
+
+    /// Refaz a configuração de uma execução headless a partir do log de replay
+    fn from_replay(log: &ReplayLog) -> Result<Self, String> {
+        let header = &log.header;
+        let missing = || "O log não veio de uma execução headless".to_string();
+
+        let scenario = header.scenario.as_deref().ok_or_else(missing)?;
+        let mut config = Self {
+            scenario: Scenario::parse(scenario)?,
+            agents: header.requested_agents.ok_or_else(missing)?,
+            seed: header.seed.ok_or_else(missing)?,
+            avoidance: AvoidanceMode::parse(&header.avoidance)
+                .ok_or_else(|| format!("Modo de prevenção desconhecido: '{}'", header.avoidance))?,
+            grid_mode: GridMode::parse(&header.grid_mode)
+                .ok_or_else(|| format!("Grid desconhecido: '{}'", header.grid_mode))?,
+            planner: match &header.planner {
+                Some(name) => PathPlanner::parse(name).ok_or_else(|| format!("Planejador desconhecido: '{}'", name))?,
+                None => PathPlanner::Grid,
+            },
+            hex_layout: header.hex_layout()?,
+            steps: log.total_ticks() as usize,
+            dt: header.dt.ok_or_else(missing)?,
+            map: match &header.map {
+                Some(name) => MapGenerator::parse(name).ok_or_else(|| format!("Gerador de mapa desconhecido: '{}'", name))?,
+                None => MapGenerator::Empty,
+            },
+            ..Self::default()
+        };
+        config.apply_map();
+        Ok(config)
+    }
+
+    /// O arquivo de cenário passa a gerar o mapa pedido (o `--map` vale por cima do `generator` dele)
+    fn apply_map(&mut self) {
+        if let Scenario::File(_, file) = &mut self.scenario {
+            file.grid.generator = self.map.name().to_string();
+        }
+    }
+
+    /// Nome base dos arquivos de saída: identifica a execução sem precisar abri-los
+    /// (o planejador e o layout hexagonal só aparecem quando não são o padrão)
+    pub fn file_stem(&self) -> String {
+        let planner = match self.planner {
+            PathPlanner::Grid => String::new(),
+            other => format!("_{}", other.name()),
+        };
+        let hex = if self.grid_mode == GridMode::Hexagonal && self.hex_layout != HexLayout::default() {
+            format!("_{}{}", self.hex_layout.orientation.name(), self.hex_layout.size)
+        } else {
+            String::new()
+        };
+        let map = match self.map {
+            MapGenerator::Empty => String::new(),
+            other => format!("_{}", other.name()),
+        };
+        format!(
+            "{}_{}_{:?}{}{}{}_{}a_seed{}",
+            self.scenario.name(),
+            self.avoidance.name(),
+            self.grid_mode,
+            hex,
+            planner,
+            map,
+            self.agents,
+            self.seed
+        )
+        .to_lowercase()
+    }
+
+    /// Colunas que identificam a execução, repetidas em todas as linhas
+    /// (o layout hexagonal fica vazio nos grids retangulares).
+    /// `agents` é o número pedido e `spawned` o de agentes criados (quem não tem caminho fica de fora)
+    fn run_columns(&self, spawned: usize) -> String {
+        let hex = match self.grid_mode {
+            GridMode::Hexagonal => format!("{}{}", self.hex_layout.orientation.name(), self.hex_layout.size),
+            _ => String::new(),
+        };
+        format!(
+            "{},{},{:?},{},{},{},{},{},{},{:.6}",
+            self.scenario.name(),
+            self.avoidance.name(),
+            self.grid_mode,
+            self.map.name(),
+            self.planner.name(),
+            hex,
+            self.seed,
+            self.agents,
+            spawned,
+            self.dt
+        )
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Valor inválido para '{}': '{}'", flag, value))
+}
+
+/// Uma linha do CSV: o estado da simulação depois de um passo
+struct StepRecord {
+    step: usize,
+    sim_time: f32,
+    step_ms: f64,
+    active: usize,
+    finished: usize,
+    new_collisions: usize,
+    contacts: usize,
+}
+
+/// Métricas de qualidade de um agente ao longo da execução
+#[derive(Default)]
+struct AgentMetrics {
+    time_to_goal: Option<f32>,
+    distance: f32,
+    collisions: usize,
+}
+
+/// Grids, densidades de obstáculo e números de agentes do `--plan-benchmark`
+/// (os do benchmark de A* do trabalho 5, com um grid maior)
+const PLANNING_GRIDS: [(usize, usize); 3] = [(60, 36), (120, 72), (240, 144)];
+const PLANNING_DENSITIES: [f32; 2] = [0.1, 0.3];
+const PLANNING_AGENTS: [usize; 2] = [100, 500];
+/// Execuções de cada combinação (o CSV guarda a média)
+const PLANNING_REPETITIONS: u32 = 3;
+
+/// Cronometra o planejamento em lote das rotas de agentes aleatórios (o que a criação de agentes
+/// espera) em pools do rayon com cada número de threads. Cada repetição começa com o cache vazio
+/// e os mesmos pares no mesmo grid.
+pub fn run_planning(config: &HeadlessConfig) -> Result<PathBuf, String> {
+    PathManager::instance().set_planner(config.planner);
+    HexLayout::set_current(config.hex_layout);
+    let threads = thread_counts(config);
+
+    fs::create_dir_all(&config.out_dir).map_err(|e| e.to_string())?;
+    let planner = match config.planner {
+        PathPlanner::Grid => String::new(),
+        other => format!("_{}", other.name()),
+    };
+    let map = match config.map {
+        MapGenerator::Empty => String::new(),
+        other => format!("_{}", other.name()),
+    };
+    let stem = format!("planning_{:?}{}{}_seed{}", config.grid_mode, planner, map, config.seed).to_lowercase();
+    let csv_path = config.out_dir.join(format!("{}.csv", stem));
+    let mut file = BufWriter::new(File::create(&csv_path).map_err(|e| e.to_string())?);
+    writeln!(file, "grid_width,grid_height,map,obstacle_density,grid,planner,agents,threads,spawned,time_ms,speedup")
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        ">>> BENCHMARK DE PLANEJAMENTO: {:?} | caminhos {} | mapa {} | threads {:?}",
+        config.grid_mode,
+        config.planner.name(),
+        config.map.name(),
+        threads
+    );
+    // Com um gerador, o mapa dele substitui os obstáculos sorteados com cada densidade
+    let densities: Vec<Option<f32>> = match config.map {
+        MapGenerator::Empty => PLANNING_DENSITIES.iter().copied().map(Some).collect(),
+        _ => vec![None],
+    };
+    for (width, height) in PLANNING_GRIDS {
+        for &sprinkled in &densities {
+            rand::srand(config.seed);
+            let init_ctx = init_system_with_map(width, height, config.map, config.seed);
+            let mut grid = init_ctx.grid.expect("Grid error");
+            let archetype = init_ctx.factory.expect("Factory error").create_blue_agent_factory().archetype();
+            if let Some(density) = sprinkled {
+                for y in 0..height {
+                    for x in 0..width {
+                        if rand::gen_range(0.0, 1.0) < density {
+                            grid.set_cell(x, y, CellType::Obstacle);
+                        }
+                    }
+                }
+            }
+            let blocked = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).filter(|&(x, y)| grid.is_obstacle(x, y)).count();
+            let density = sprinkled.unwrap_or(blocked as f32 / (width * height) as f32);
+
+            for agents_count in PLANNING_AGENTS {
+                let queries: Vec<PathKey> = std::iter::repeat_with(|| grid.get_random_empty_cell().zip(grid.get_random_empty_cell()))
+                    .take(agents_count * 10)
+                    .flatten()
+                    .filter(|(start, end)| start != end)
+                    .take(agents_count)
+                    .collect();
+                let mut sequential_ms = None;
+                for &n in &threads {
+                    let pool = rayon::ThreadPoolBuilder::new().num_threads(n).build().map_err(|e| e.to_string())?;
+                    let mut total_ms = 0.0;
+                    let mut spawned = 0;
+                    for _ in 0..PLANNING_REPETITIONS {
+                        PathManager::instance().clear_cache();
+                        let start = Instant::now();
+                        let routes = pool.install(|| energy::plan_routes(&grid, &queries, config.grid_mode, &archetype));
+                        total_ms += start.elapsed().as_secs_f64() * 1000.0;
+                        spawned = routes.iter().flatten().count();
+                    }
+                    let time_ms = total_ms / PLANNING_REPETITIONS as f64;
+                    let speedup = *sequential_ms.get_or_insert(time_ms) / time_ms;
+                    println!(
+                        "{}x{} com {:.0}% de obstáculos, {} de {} rotas: {} thread(s) {:.1} ms ({:.2}x)",
+                        width,
+                        height,
+                        density * 100.0,
+                        spawned,
+                        queries.len(),
+                        n,
+                        time_ms,
+                        speedup
+                    );
+                    writeln!(
+                        file,
+                        "{},{},{},{:.2},{:?},{},{},{},{},{:.3},{:.3}",
+                        width,
+                        height,
+                        config.map.name(),
+                        density,
+                        config.grid_mode,
+                        config.planner.name(),
+                        agents_count,
+                        n,
+                        spawned,
+                        time_ms,
+                        speedup
+                    )
+                    .map_err(|e| e.to_string())?;
+                }
+            }
+        }
+    }
+
+    file.flush().map_err(|e| e.to_string())?;
+    println!("Benchmark de planejamento salvo em {}", csv_path.display());
+    Ok(csv_path)
+}
+
+/// Números de threads medidos: os pedidos em `--threads` ou 1, 2, 4... até os núcleos da máquina
+fn thread_counts(config: &HeadlessConfig) -> Vec<usize> {
+    if config.threads.is_empty() {
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let mut threads: Vec<usize> = std::iter::successors(Some(1), |n| Some(n * 2)).take_while(|&n| n < cores).collect();
+        threads.push(cores);
+        threads
+    } else {
+        config.threads.clone()
+    }
+}
+
+/// Números de agentes do `--rvo-benchmark`
+const RVO_AGENTS: [usize; 4] = [1000, 2500, 5000, 10000];
+/// Passos simulados em cada medição (o CSV guarda a média por passo)
+const RVO_STEPS: usize = 60;
+/// Células do grid por agente (com células de 20 px, uns 40 px entre vizinhos) e obstáculos espalhados
+const RVO_CELLS_PER_AGENT: usize = 4;
+const RVO_OBSTACLE_DENSITY: f32 = 0.05;
+
+/// Cronometra o passo inteiro com o RVO (`simulation::step` e o `CollisionMonitor`, como no
+/// `--benchmark`) em pools do rayon com cada número de threads. Os agentes da stack padrão saem de
+/// células diferentes e vão em linha reta para destinos sorteados, sem A*; cada número de threads
+/// refaz os mesmos passos a partir do mesmo estado (e da mesma semente dos Decorators), e as
+/// posições finais são comparadas com as da primeira medição.
+pub fn run_rvo(config: &HeadlessConfig) -> Result<PathBuf, String> {
+    observer::set_pair_logs(false);
+    let threads = thread_counts(config);
+    fs::create_dir_all(&config.out_dir).map_err(|e| e.to_string())?;
+    let csv_path = config.out_dir.join(format!("rvo_scaling_seed{}.csv", config.seed));
+    let mut file = BufWriter::new(File::create(&csv_path).map_err(|e| e.to_string())?);
+    writeln!(file, "agents,grid_width,grid_height,threads,steps,step_ms,monitor_ms,steps_per_second,speedup,identical")
+        .map_err(|e| e.to_string())?;
+
+    println!(">>> BENCHMARK DO RVO: {} passos por medição | threads {:?}", RVO_STEPS, threads);
+    let mut all_identical = true;
+    for agents_count in RVO_AGENTS {
+        rand::srand(config.seed);
+        let side = ((agents_count * RVO_CELLS_PER_AGENT) as f32).sqrt().ceil() as usize;
+        let mut grid = init_system(side, side).grid.expect("Grid error");
+        for y in 0..side {
+            for x in 0..side {
+                if rand::gen_range(0.0, 1.0) < RVO_OBSTACLE_DENSITY {
+                    grid.set_cell(x, y, CellType::Obstacle);
+                }
+            }
+        }
+        let free: Vec<(usize, usize)> = (0..side)
+            .flat_map(|y| (0..side).map(move |x| (x, y)))
+            .filter(|&(x, y)| !grid.is_obstacle(x, y))
+            .collect();
+        let center = |(x, y): (usize, usize)| vec2(x as f32, y as f32) * CELL_SIZE + Vec2::splat(CELL_SIZE / 2.0);
+
+        let starts: Vec<Vec2> = scenario::sample_cells(free.clone(), agents_count).into_iter().map(center).collect();
+        let goals: Vec<Vec2> = starts.iter().map(|_| center(free[rand::gen_range(0, free.len())])).collect();
+
+        // Mesma Strategy em todas as medições: o contorno dos obstáculos é montado aqui, fora do tempo
+        let mut strategy = RvoStrategy::new();
+        strategy.velocities_for(&RvoStates::with_capacity(0), &grid, GridMode::Cardinal, config.dt);
+
+        let mut reference: Option<Vec<Vec2>> = None;
+        let mut sequential_ms = None;
+        for &n in &threads {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(n).build().map_err(|e| e.to_string())?;
+            // Os agentes não são `Send`: são criados e simulados dentro do pool
+            let (positions, total_ms, monitor_ms) = pool.install(|| {
+                rand::srand(config.seed);
+                let mut agents: Vec<Box<dyn AgentComponent>> = starts
+                    .iter()
+                    .zip(&goals)
+                    .enumerate()
+                    .map(|(id, (&start, &goal))| {
+                        let route = Route::direct(vec![goal]);
+                        scenario::build_agent_stack(&BlueAgentFactory, start, route, id, &DecoratorSpec::default_stack())
+                    })
+                    .collect();
+                let mut command_manager = CommandManager::new();
+                let mut monitor = CollisionMonitor::new();
+                let (mut total_ms, mut monitor_ms) = (0.0, 0.0);
+                for _ in 0..RVO_STEPS {
+                    let start = Instant::now();
+                    simulation::step(&mut agents, &mut grid, &mut command_manager, &mut strategy, GridMode::Cardinal, config.dt);
+                    let monitor_start = Instant::now();
+                    monitor.update(&agents);
+                    total_ms += start.elapsed().as_secs_f64() * 1000.0;
+                    monitor_ms += monitor_start.elapsed().as_secs_f64() * 1000.0;
+                }
+                (agents.iter().map(|a| a.get_pos()).collect::<Vec<_>>(), total_ms, monitor_ms)
+            });
+
+            let identical = *reference.get_or_insert_with(|| positions.clone()) == positions;
+            all_identical &= identical;
+            let step_ms = total_ms / RVO_STEPS as f64;
+            let monitor_ms = monitor_ms / RVO_STEPS as f64;
+            let speedup = *sequential_ms.get_or_insert(step_ms) / step_ms;
+            println!(
+                "{} agentes em {}x{}: {} thread(s) {:.2} ms/passo, {:.2} ms no monitor ({:.0} passos/s, {:.2}x){}",
+                agents_count,
+                side,
+                side,
+                n,
+                step_ms,
+                monitor_ms,
+                1000.0 / step_ms,
+                speedup,
+                if identical { "" } else { " -- RESULTADO DIFERENTE" }
+            );
+            writeln!(
+                file,
+                "{},{},{},{},{},{:.3},{:.3},{:.1},{:.3},{}",
+                agents_count,
+                side,
+                side,
+                n,
+                RVO_STEPS,
+                step_ms,
+                monitor_ms,
+                1000.0 / step_ms,
+                speedup,
+                identical
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    file.flush().map_err(|e| e.to_string())?;
+    println!("Benchmark do RVO salvo em {}", csv_path.display());
+    if all_identical {
+        Ok(csv_path)
+    } else {
+        Err(format!("O resultado mudou com o número de threads (ver {})", csv_path.display()))
+    }
+}
+
+/// Lado do grid quadrado do `--grid-benchmark`
+const GRID_BENCHMARK_SIDE: usize = 2000;
+/// Buscas A* medidas (pares sorteados na mesma componente)
+const GRID_BENCHMARK_SEARCHES: usize = 20;
+/// Execuções das medições rápidas (o CSV guarda a média)
+const GRID_BENCHMARK_REPETITIONS: u32 = 5;
+
+/// Cronometra o que depende do armazenamento do grid em um mapa de 2000x2000 (padrão: cavernas):
+/// gerar o mapa, consultar `is_obstacle` em todas as células, `clear`, buscas A* sem cache na
+/// vizinhança do `--grid` e a passada das células dos dois renderizadores com o grid inteiro visível
+/// (só os retângulos e hexágonos a pintar, sem desenhar: o headless não tem janela).
+/// A coluna `count` confere o resultado (obstáculos, células dos caminhos, figuras pintadas).
+pub fn run_grid(config: &HeadlessConfig) -> Result<PathBuf, String> {
+    HexLayout::set_current(config.hex_layout);
+    let map = match config.map {
+        MapGenerator::Empty => MapGenerator::Caves,
+        other => other,
+    };
+    let side = GRID_BENCHMARK_SIDE;
+    fs::create_dir_all(&config.out_dir).map_err(|e| e.to_string())?;
+    let csv_path = config.out_dir.join(format!("grid_{}_seed{}.csv", map.name(), config.seed));
+    let mut file = BufWriter::new(File::create(&csv_path).map_err(|e| e.to_string())?);
+    writeln!(file, "operation,grid_width,grid_height,map,grid,repetitions,time_ms,count").map_err(|e| e.to_string())?;
+    let mut record = |operation: &str, repetitions: u32, time_ms: f64, count: usize| -> Result<(), String> {
+        println!("{:<12} {:>10.2} ms  ({})", operation, time_ms, count);
+        writeln!(
+            file,
+            "{},{},{},{},{:?},{},{:.3},{}",
+            operation,
+            side,
+            side,
+            map.name(),
+            config.grid_mode,
+            repetitions,
+            time_ms,
+            count
+        )
+        .map_err(|e| e.to_string())
+    };
+    let average_ms = |start: Instant| start.elapsed().as_secs_f64() * 1000.0 / GRID_BENCHMARK_REPETITIONS as f64;
+
+    println!(">>> BENCHMARK DO GRID: {}x{} | mapa {} | {:?} | semente {}", side, side, map.name(), config.grid_mode, config.seed);
+    let start = Instant::now();
+    let grid = map.create_factory(config.seed).create(side, side);
+    let cells = side * side;
+    record("generate", 1, start.elapsed().as_secs_f64() * 1000.0, cells)?;
+
+    let start = Instant::now();
+    let mut blocked = 0;
+    for _ in 0..GRID_BENCHMARK_REPETITIONS {
+        blocked = (0..side).map(|y| (0..side).filter(|&x| grid.is_obstacle(x, y)).count()).sum();
+    }
+    record("is_obstacle", GRID_BENCHMARK_REPETITIONS, average_ms(start), blocked)?;
+
+    let mut scratch = Grid::new(side, side);
+    let start = Instant::now();
+    for _ in 0..GRID_BENCHMARK_REPETITIONS {
+        scratch.clear();
+    }
+    record("clear", GRID_BENCHMARK_REPETITIONS, average_ms(start), cells)?;
+    drop(scratch);
+
+    rand::srand(config.seed);
+    let pairs: Vec<PathKey> =
+        std::iter::repeat_with(|| benchmark::random_connected_pair(&grid, config.grid_mode)).flatten().take(GRID_BENCHMARK_SEARCHES).collect();
+    let start = Instant::now();
+    let path_cells: usize = crate::with_adapter(&grid, config.grid_mode, |adapter| {
+        pairs.iter().filter_map(|&(from, to)| a_star_with_adapter(adapter, from, to)).map(|path| path.len()).sum()
+    });
+    record("a_star", pairs.len() as u32, start.elapsed().as_secs_f64() * 1000.0, path_cells)?;
+
+    let view = Viewport {
+        rect: Rect::new(0.0, 0.0, side as f32 * CELL_SIZE, side as f32 * CELL_SIZE),
+        cols: 0..side,
+        rows: 0..side,
+        zoom: 1.0,
+    };
+    let start = Instant::now();
+    let mut shapes = 0;
+    for _ in 0..GRID_BENCHMARK_REPETITIONS {
+        shapes = 0;
+        renderer::fill_cells(&grid, &view, CELL_SIZE, |rect, _| {
+            shapes += 1;
+            std::hint::black_box(rect);
+        });
+    }
+    record("render", GRID_BENCHMARK_REPETITIONS, average_ms(start), shapes)?;
+
+    let start = Instant::now();
+    for _ in 0..GRID_BENCHMARK_REPETITIONS {
+        shapes = 0;
+        hexagonal_renderer::fill_hexagonal_cells(&grid, &view, |cell, _| {
+            shapes += 1;
+            std::hint::black_box(cell);
+        });
+    }
+    record("render_hex", GRID_BENCHMARK_REPETITIONS, average_ms(start), shapes)?;
+
+    file.flush().map_err(|e| e.to_string())?;
+    println!("Benchmark do grid salvo em {}", csv_path.display());
+    Ok(csv_path)
+}
+
+/// Executa o benchmark sem abrir janela e grava um CSV com o tempo de cada passo
+/// e outro (`_metrics.csv`) com as métricas de qualidade de cada agente.
+/// Só o passo da simulação é cronometrado; sensores e escrita do arquivo ficam de fora.
+pub fn run(config: &HeadlessConfig) -> std::io::Result<PathBuf> {
+    observer::set_pair_logs(false);
+    rand::srand(config.seed);
+    PathManager::instance().clear_cache();
+    PathManager::instance().set_planner(config.planner);
+    HexLayout::set_current(config.hex_layout);
+
+    let init_ctx = init_system_with_map(GRID_WIDTH, GRID_HEIGHT, config.map, config.seed);
+    let mut grid = init_ctx.grid.expect("Grid error");
+    let factory = init_ctx.factory.expect("Factory error");
+    let agent_creator = factory.create_blue_agent_factory();
+
+    let mut agents: Vec<Box<dyn AgentComponent>> = Vec::new();
+    let mut next_id = 0;
+
+    match &config.scenario {
+        Scenario::Opposing => benchmark::spawn_opposing_lanes(
+            &grid,
+            &mut agents,
+            agent_creator.as_ref(),
+            config.grid_mode,
+            &mut next_id,
+            config.agents,
+        ),
+        Scenario::Random => benchmark::spawn_random_scenario(
+            &grid,
+            &mut agents,
+            agent_creator.as_ref(),
+            config.grid_mode,
+            &mut next_id,
+            config.agents,
+        ),
+        Scenario::File(_, file) => {
+            let built = file.build(config.grid_mode, config.seed);
+            grid = built.grid;
+            agents = built.agents;
+        }
+    }
+
+    println!(
+        ">>> BENCHMARK HEADLESS: {} | {} | caminhos {} | {} agentes | seed {} | {} passos de {:.4}s",
+        config.scenario.name(),
+        config.avoidance.name(),
+        config.planner.name(),
+        agents.len(),
+        config.seed,
+        config.steps,
+        config.dt
+    );
+
+    let mut strategy = config.avoidance.create_strategy(grid.width, grid.height);
+    let mut command_manager = CommandManager::new();
+    if let Some(record_path) = &config.record {
+        let mut header = ReplayHeader::new(&grid, config.grid_mode, config.avoidance.name());
+        header.seed = Some(config.seed);
+        header.scenario = Some(config.scenario.replay_name());
+        header.requested_agents = Some(config.agents);
+        header.dt = Some(config.dt);
+        header.map = (config.map != MapGenerator::Empty).then(|| config.map.name().to_string());
+        command_manager.start_recording(ReplayRecorder::create(record_path, &header)?);
+    }
+    let mut monitor = CollisionMonitor::new();
+    let mut records = Vec::with_capacity(config.steps);
+    let mut metrics: Vec<AgentMetrics> = agents.iter().map(|_| AgentMetrics::default()).collect();
+
+    let frames_dir = config.out_dir.join(format!("{}_frames", config.file_stem()));
+    if !config.frames.is_empty() {
+        fs::create_dir_all(&frames_dir)?;
+    }
+    let mut recorder = match config.video {
+        Some(options) => {
+            let path = match options.format {
+                VideoFormat::Gif => config.out_dir.join(format!("{}.gif", config.file_stem())),
+                VideoFormat::PngSequence => config.out_dir.join(format!("{}_video", config.file_stem())),
+            };
+            let size = frame(config, &grid, &agents, 0.0).size();
+            Some(VideoRecorder::create(&path, options, size, 0.0).map_err(std::io::Error::other)?)
+        }
+        None => None,
+    };
+    // Imagens pedidas em --frames e quadros do vídeo; fora do tempo cronometrado
+    let mut export = |tick: usize, grid: &Grid, agents: &[Box<dyn AgentComponent>]| -> std::io::Result<()> {
+        if recorder.is_none() && !config.frames.contains(&tick) {
+            return Ok(());
+        }
+        let frame = frame(config, grid, agents, tick as f32 * config.dt);
+        if config.frames.contains(&tick) {
+            let path = frames_dir.join(format!("tick_{:06}.{}", tick, config.frame_format.extension()));
+            frame.save(&path, config.frame_format).map_err(std::io::Error::other)?;
+        }
+        if let Some(recorder) = &mut recorder {
+            recorder.capture(tick as f32 * config.dt, &frame).map_err(std::io::Error::other)?;
+        }
+        Ok(())
+    };
+    export(0, &grid, &agents)?;
+
+    for step in 0..config.steps {
+        let positions: Vec<_> = agents.iter().map(|a| a.get_pos()).collect();
+
+        let start = Instant::now();
+        simulation::step(
+            &mut agents,
+            &mut grid,
+            &mut command_manager,
+            strategy.as_mut(),
+            config.grid_mode,
+            config.dt,
+        );
+        let step_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let sim_time = (step + 1) as f32 * config.dt;
+        let new_collisions = monitor.update(&agents);
+        let finished = agents.iter().filter(|a| a.is_finished()).count();
+
+        for ((agent, m), old_pos) in agents.iter().zip(metrics.iter_mut()).zip(&positions) {
+            m.distance += agent.get_pos().distance(*old_pos);
+            if m.time_to_goal.is_none() && agent.is_finished() {
+                m.time_to_goal = Some(sim_time);
+            }
+        }
+        // Os ids dos agentes são os índices no vetor
+        for &(a, b) in &new_collisions {
+            metrics[a].collisions += 1;
+            metrics[b].collisions += 1;
+        }
+
+        export(step + 1, &grid, &agents)?;
+
+        records.push(StepRecord {
+            step,
+            sim_time,
+            step_ms,
+            active: agents.len() - finished,
+            finished,
+            new_collisions: new_collisions.len(),
+            contacts: monitor.active_contacts(),
+        });
+    }
+
+    command_manager.stop_recording()?;
+
+    fs::create_dir_all(&config.out_dir)?;
+    let stem = config.file_stem();
+    let path = config.out_dir.join(format!("{}.csv", stem));
+    write_csv(&path, config, agents.len(), &records)?;
+    let metrics_path = config.out_dir.join(format!("{}_metrics.csv", stem));
+    write_metrics_csv(&metrics_path, config, &metrics)?;
+
+    let total_ms: f64 = records.iter().map(|r| r.step_ms).sum();
+    let max_ms = records.iter().map(|r| r.step_ms).fold(0.0, f64::max);
+    let collisions: usize = records.iter().map(|r| r.new_collisions).sum();
+    println!(
+        "<<< FIM: passo médio {:.4} ms (máx {:.4} ms), {} colisões. Resultados em {}",
+        total_ms / records.len().max(1) as f64,
+        max_ms,
+        collisions,
+        path.display()
+    );
+    if !config.frames.is_empty() {
+        println!("    {} imagens em {}", config.frames.len(), frames_dir.display());
+    }
+    if let Some(recorder) = &recorder {
+        println!("    vídeo: {} quadros em {}", recorder.frames(), recorder.path().display());
+    }
+
+    Ok(path)
+}
+
+/// Estado atual para as imagens e o vídeo, com a legenda da execução
+fn frame<'a>(config: &HeadlessConfig, grid: &'a Grid, agents: &'a [Box<dyn AgentComponent>], time: f32) -> Frame<'a> {
+    Frame {
+        grid,
+        grid_mode: config.grid_mode,
+        agents,
+        style: config.frame_style,
+        caption: frame_export::caption(config.scenario.name(), config.avoidance.name(), Some(config.seed), time),
+    }
+}
+
+/// Refaz a execução gravada em `path` e compara, entrada por entrada,
+/// o novo log (`<nome>_verify.jsonl`) com o original.
+pub fn verify(path: &Path) -> Result<(), String> {
+    let original = ReplayLog::load(path)?;
+    let mut config = HeadlessConfig::from_replay(&original)?;
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let verify_path = dir.join(format!("{}_verify.jsonl", stem));
+    config.out_dir = dir.join("verify");
+    config.record = Some(verify_path.clone());
+
+    run(&config).map_err(|e| e.to_string())?;
+    let replayed = ReplayLog::load(&verify_path)?;
+
+    match original.first_difference(&replayed) {
+        None => {
+            println!(
+                "OK: {} ticks e {} entradas idênticas",
+                original.total_ticks(),
+                original.entries.len()
+            );
+            Ok(())
+        }
+        Some(diff) => Err(format!("As trajetórias mudaram. {}", diff)),
+    }
+}
+
+/// Cada linha repete os parâmetros da execução para que vários CSVs
+/// possam ser concatenados sem perder de onde cada linha veio.
+fn write_csv(
+    path: &Path,
+    config: &HeadlessConfig,
+    spawned: usize,
+    records: &[StepRecord],
+) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(
+        file,
+        "scenario,avoidance,grid,map,planner,hex,seed,agents,spawned,dt,step,sim_time,step_ms,active,finished,new_collisions,contacts"
+    )?;
+
+    let run = config.run_columns(spawned);
+    for r in records {
+        writeln!(
+            file,
+            "{},{},{:.4},{:.6},{},{},{},{}",
+            run,
+            r.step,
+            r.sim_time,
+            r.step_ms,
+            r.active,
+            r.finished,
+            r.new_collisions,
+            r.contacts
+        )?;
+    }
+
+    file.flush()
+}
+
+/// Uma linha por agente: tempo até o destino (vazio se não chegou),
+/// distância percorrida e quantas colisões sofreu.
+fn write_metrics_csv(
+    path: &Path,
+    config: &HeadlessConfig,
+    metrics: &[AgentMetrics],
+) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    writeln!(
+        file,
+        "scenario,avoidance,grid,map,planner,hex,seed,agents,spawned,dt,agent_id,reached_goal,time_to_goal,distance,collisions"
+    )?;
+
+    let run = config.run_columns(metrics.len());
+    for (id, m) in metrics.iter().enumerate() {
+        let time_to_goal = m
+            .time_to_goal
+            .map(|t| format!("{:.4}", t))
+            .unwrap_or_default();
+        writeln!(
+            file,
+            "{},{},{},{},{:.2},{}",
+            run,
+            id,
+            m.time_to_goal.is_some(),
+            time_to_goal,
+            m.distance,
+            m.collisions
+        )?;
+    }
+
+    file.flush()
+}
@@ -0,0 +1,319 @@
+use macroquad::prelude::*;
+use std::ops::{Add, Sub};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Raio padrão do hexágono (px, do centro a um vértice)
+pub const DEFAULT_HEX_SIZE: f32 = 15.0;
+const SQRT_3: f32 = 1.732_050_8;
+
+/// Layout hexagonal em uso (trocado pela tecla Q, pelo cenário ou pela linha de comando)
+static FLAT_TOP: AtomicBool = AtomicBool::new(false);
+static SIZE_BITS: AtomicU32 = AtomicU32::new(DEFAULT_HEX_SIZE.to_bits());
+
+/// Orientação dos hexágonos e como as coordenadas de linha/coluna do `Grid` são deslocadas
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum HexOrientation {
+    /// Vértice para cima; linhas ímpares deslocadas meia célula para a direita ("odd-r")
+    PointyTop,
+    /// Lado para cima; colunas ímpares deslocadas meia célula para baixo ("odd-q")
+    FlatTop,
+}
+
+impl HexOrientation {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "pointy" | "pointy_top" | "pointy-top" => Some(HexOrientation::PointyTop),
+            "flat" | "flat_top" | "flat-top" => Some(HexOrientation::FlatTop),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HexOrientation::PointyTop => "pointy",
+            HexOrientation::FlatTop => "flat",
+        }
+    }
+
+    /// Outra orientação (tecla Q)
+    pub fn next(&self) -> Self {
+        match self {
+            HexOrientation::PointyTop => HexOrientation::FlatTop,
+            HexOrientation::FlatTop => HexOrientation::PointyTop,
+        }
+    }
+}
+
+/// Coordenada axial (q, r) de um hexágono; a terceira coordenada cúbica é s = -q - r
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Add for Hex {
+    type Output = Hex;
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Hex {
+    /// As seis direções, começando no leste (q+1) e girando no sentido anti-horário do plano axial
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex::new(1, 0),
+        Hex::new(1, -1),
+        Hex::new(0, -1),
+        Hex::new(-1, 0),
+        Hex::new(-1, 1),
+        Hex::new(0, 1),
+    ];
+
+    pub const fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn scale(self, k: i32) -> Hex {
+        Hex::new(self.q * k, self.r * k)
+    }
+
+    /// Converte (coluna, linha) do grid para axial
+    pub fn from_offset(col: i32, row: i32, orientation: HexOrientation) -> Hex {
+        match orientation {
+            HexOrientation::PointyTop => Hex::new(col - (row - (row & 1)) / 2, row),
+            HexOrientation::FlatTop => Hex::new(col, row - (col - (col & 1)) / 2),
+        }
+    }
+
+    /// Converte para (coluna, linha) do grid; pode ser negativo fora do mapa
+    pub fn to_offset(self, orientation: HexOrientation) -> (i32, i32) {
+        match orientation {
+            HexOrientation::PointyTop => (self.q + (self.r - (self.r & 1)) / 2, self.r),
+            HexOrientation::FlatTop => (self.q, self.r + (self.q - (self.q & 1)) / 2),
+        }
+    }
+
+    /// Célula do grid, se estiver dentro de `width` x `height`
+    pub fn to_cell(self, orientation: HexOrientation, width: usize, height: usize) -> Option<(usize, usize)> {
+        let (col, row) = self.to_offset(orientation);
+        (col >= 0 && row >= 0 && (col as usize) < width && (row as usize) < height).then_some((col as usize, row as usize))
+    }
+
+    pub fn neighbors(self) -> [Hex; 6] {
+        Hex::DIRECTIONS.map(|direction| self + direction)
+    }
+
+    /// Número de passos entre dois hexágonos
+    pub fn distance(self, other: Hex) -> i32 {
+        let d = self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// Arredonda uma coordenada axial fracionária para o hexágono que a contém:
+    /// arredonda as três coordenadas cúbicas e corrige a que mais mudou, para manter q + r + s = 0
+    pub fn round(q: f32, r: f32) -> Hex {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(rq as i32, rr as i32)
+    }
+
+    /// Hexágonos a exatamente `radius` passos, em volta (raio 0 é o próprio hexágono)
+    pub fn ring(self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut results = Vec::with_capacity(6 * radius as usize);
+        let mut hex = self + Hex::DIRECTIONS[4].scale(radius as i32);
+        for direction in Hex::DIRECTIONS {
+            for _ in 0..radius {
+                results.push(hex);
+                hex = hex + direction;
+            }
+        }
+        results
+    }
+
+    /// Hexágonos atravessados pela reta entre os dois centros, do início ao fim
+    pub fn line_to(self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        // Um empurrão mínimo evita cair exatamente na divisa entre dois hexágonos
+        let (q0, r0) = (self.q as f32 + 1e-6, self.r as f32 + 1e-6);
+        let (q1, r1) = (other.q as f32 + 1e-6, other.r as f32 + 1e-6);
+        (0..=n)
+            .map(|i| {
+                let t = if n == 0 { 0.0 } else { i as f32 / n as f32 };
+                Hex::round(q0 + (q1 - q0) * t, r0 + (r1 - r0) * t)
+            })
+            .collect()
+    }
+}
+
+/// Orientação e tamanho dos hexágonos: converte entre células, coordenadas axiais e pixels do mundo.
+/// A célula (0, 0) fica encostada no canto superior esquerdo.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct HexLayout {
+    pub orientation: HexOrientation,
+    /// Raio (px, do centro a um vértice)
+    pub size: f32,
+}
+
+impl Default for HexLayout {
+    fn default() -> Self {
+        Self::new(HexOrientation::PointyTop, DEFAULT_HEX_SIZE)
+    }
+}
+
+impl HexLayout {
+    pub fn new(orientation: HexOrientation, size: f32) -> Self {
+        Self { orientation, size }
+    }
+
+    /// Layout em uso
+    pub fn current() -> Self {
+        let orientation = if FLAT_TOP.load(Ordering::Relaxed) { HexOrientation::FlatTop } else { HexOrientation::PointyTop };
+        Self::new(orientation, f32::from_bits(SIZE_BITS.load(Ordering::Relaxed)))
+    }
+
+    /// Troca o layout em uso (quem guarda dados derivados dele compara com `current`)
+    pub fn set_current(layout: HexLayout) {
+        FLAT_TOP.store(layout.orientation == HexOrientation::FlatTop, Ordering::Relaxed);
+        SIZE_BITS.store(layout.size.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Largura e altura de um hexágono
+    pub fn width(&self) -> f32 {
+        match self.orientation {
+            HexOrientation::PointyTop => SQRT_3 * self.size,
+            HexOrientation::FlatTop => 2.0 * self.size,
+        }
+    }
+
+    pub fn height(&self) -> f32 {
+        match self.orientation {
+            HexOrientation::PointyTop => 2.0 * self.size,
+            HexOrientation::FlatTop => SQRT_3 * self.size,
+        }
+    }
+
+    /// Distância entre colunas e entre linhas vizinhas do grid
+    pub fn column_spacing(&self) -> f32 {
+        match self.orientation {
+            HexOrientation::PointyTop => self.width(),
+            HexOrientation::FlatTop => 1.5 * self.size,
+        }
+    }
+
+    pub fn row_spacing(&self) -> f32 {
+        match self.orientation {
+            HexOrientation::PointyTop => 1.5 * self.size,
+            HexOrientation::FlatTop => self.height(),
+        }
+    }
+
+    /// Tamanho (px) de um mapa com `width` x `height` células
+    pub fn world_size(&self, width: usize, height: usize) -> Vec2 {
+        let (columns, rows) = (width.saturating_sub(1) as f32, height.saturating_sub(1) as f32);
+        match self.orientation {
+            HexOrientation::PointyTop => {
+                let shift = if height > 1 { self.width() / 2.0 } else { 0.0 };
+                vec2(width as f32 * self.width() + shift, rows * self.row_spacing() + self.height())
+            }
+            HexOrientation::FlatTop => {
+                let shift = if width > 1 { self.height() / 2.0 } else { 0.0 };
+                vec2(columns * self.column_spacing() + self.width(), height as f32 * self.height() + shift)
+            }
+        }
+    }
+
+    /// Centro do hexágono em pixels
+    pub fn hex_to_pixel(&self, hex: Hex) -> Vec2 {
+        let (q, r) = (hex.q as f32, hex.r as f32);
+        let center = match self.orientation {
+            HexOrientation::PointyTop => vec2(SQRT_3 * q + SQRT_3 / 2.0 * r, 1.5 * r),
+            HexOrientation::FlatTop => vec2(1.5 * q, SQRT_3 / 2.0 * q + SQRT_3 * r),
+        };
+        center * self.size + self.origin()
+    }
+
+    /// Hexágono que contém o ponto (inversa de `hex_to_pixel` seguida de `Hex::round`)
+    pub fn pixel_to_hex(&self, point: Vec2) -> Hex {
+        let p = (point - self.origin()) / self.size;
+        let (q, r) = match self.orientation {
+            HexOrientation::PointyTop => (SQRT_3 / 3.0 * p.x - p.y / 3.0, 2.0 / 3.0 * p.y),
+            HexOrientation::FlatTop => (2.0 / 3.0 * p.x, -p.x / 3.0 + SQRT_3 / 3.0 * p.y),
+        };
+        Hex::round(q, r)
+    }
+
+    /// Centro da célula (coluna, linha) do grid
+    pub fn cell_center(&self, cell: (usize, usize)) -> Vec2 {
+        self.hex_to_pixel(Hex::from_offset(cell.0 as i32, cell.1 as i32, self.orientation))
+    }
+
+    /// Célula (coluna, linha) sob o ponto; negativa à esquerda ou acima do mapa
+    pub fn cell_at(&self, point: Vec2) -> (i32, i32) {
+        self.pixel_to_hex(point).to_offset(self.orientation)
+    }
+
+    /// Vértices do hexágono, em sentido horário na tela
+    pub fn corners(&self, center: Vec2) -> [Vec2; 6] {
+        let start = match self.orientation {
+            HexOrientation::PointyTop => 30.0_f32,
+            HexOrientation::FlatTop => 0.0,
+        };
+        std::array::from_fn(|i| {
+            let angle = (start + 60.0 * i as f32).to_radians();
+            center + vec2(angle.cos(), angle.sin()) * self.size
+        })
+    }
+
+    /// Centro da célula (0, 0)
+    fn origin(&self) -> Vec2 {
+        vec2(self.width() / 2.0, self.height() / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_round_trips_through_axial() {
+        for orientation in [HexOrientation::PointyTop, HexOrientation::FlatTop] {
+            for row in -5..=5 {
+                for col in -5..=5 {
+                    assert_eq!(Hex::from_offset(col, row, orientation).to_offset(orientation), (col, row));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cell_centers_map_back_to_their_cell() {
+        for orientation in [HexOrientation::PointyTop, HexOrientation::FlatTop] {
+            let layout = HexLayout::new(orientation, 15.0);
+            for row in 0..6 {
+                for col in 0..6 {
+                    assert_eq!(layout.cell_at(layout.cell_center((col, row))), (col as i32, row as i32));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::agent_decorator::AgentComponent;
+use crate::camera::Viewport;
+use crate::debug_overlay::SearchTrace;
+use crate::search_visualizer::SearchVisualizer;
+use crate::grid::{CellType, Grid};
+use crate::{GridMode, InputMode};
+use crate::hex::HexLayout;
+use macroquad::prelude::*;
+
+/// Desenha um hexágono do layout em uso
+pub fn draw_hexagon(layout: &HexLayout, center: Vec2, color: Color, filled: bool) {
+    let points = layout.corners(center);
+
+    if filled {
+        // Desenha hexágono preenchido usando triângulos
+        for i in 0..6 {
+            let next = (i + 1) % 6;
+            draw_triangle(center, points[i], points[next], color);
+        }
+    } else {
+        // Desenha apenas as bordas
+        for i in 0..6 {
+            let next = (i + 1) % 6;
+            draw_line(
+                points[i].x,
+                points[i].y,
+                points[next].x,
+                points[next].y,
+                1.0,
+                color,
+            );
+        }
+    }
+}
+
+/// Desenha o contorno dos hexágonos visíveis; com o zoom muito afastado, não desenha
+pub fn draw_hexagonal_grid(view: &Viewport) {
+    let layout = HexLayout::current();
+    if !view.show_outlines(layout.size) {
+        return;
+    }
+    for y in view.rows.clone() {
+        for x in view.cols.clone() {
+            draw_hexagon(&layout, layout.cell_center((x, y)), GRAY, false);
+        }
+    }
+}
+
+/// Células visíveis a pintar no grid hexagonal: obstáculos, estações e terreno, cada uma com a sua cor
+pub fn fill_hexagonal_cells(grid: &Grid, view: &Viewport, mut fill: impl FnMut((usize, usize), Color)) {
+    for y in view.rows.clone() {
+        let cells = grid.row(y)[view.cols.clone()].iter().zip(&grid.terrain_row(y)[view.cols.clone()]);
+        for (x, (&cell, &cost)) in view.cols.clone().zip(cells) {
+            let color = match cell {
+                CellType::Obstacle => BLACK,
+                CellType::ChargingStation => crate::renderer::STATION_COLOR,
+                CellType::Empty => match crate::renderer::terrain_color(cost as usize) {
+                    Some(color) => color,
+                    None => continue,
+                },
+            };
+            fill((x, y), color);
+        }
+    }
+}
+
+/// Desenha as células de obstáculo, as estações e o terreno visíveis no grid hexagonal
+pub fn draw_hexagonal_cells(grid: &Grid, view: &Viewport) {
+    let layout = HexLayout::current();
+    fill_hexagonal_cells(grid, view, |cell, color| draw_hexagon(&layout, layout.cell_center(cell), color, true));
+}
+
+/// Rastro da última busca no grid hexagonal
+pub fn draw_hexagonal_search_trace(trace: &SearchTrace, view: &Viewport) {
+    let layout = HexLayout::current();
+    crate::renderer::draw_trace(trace, view, |cell, color| {
+        draw_hexagon(&layout, layout.cell_center(cell), color, true);
+    });
+}
+
+/// Visualizador da busca no grid hexagonal
+pub fn draw_hexagonal_search_visualizer(visualizer: &SearchVisualizer, view: &Viewport) {
+    let layout = HexLayout::current();
+    crate::renderer::draw_search_steps(
+        visualizer,
+        view,
+        |cell, color| draw_hexagon(&layout, layout.cell_center(cell), color, true),
+        |cell| layout.cell_center(cell),
+    );
+}
+
+/// Componentes conexas no grid hexagonal
+pub fn draw_hexagonal_components(grid: &Grid, view: &Viewport) {
+    let layout = HexLayout::current();
+    crate::renderer::draw_components(grid, GridMode::Hexagonal, view, |cell, color| {
+        draw_hexagon(&layout, layout.cell_center(cell), color, true);
+    });
+}
+
+/// Células bloqueadas pelo feromônio (grid hexagonal)
+pub fn draw_hexagonal_blocked_cells(cells: &[(usize, usize)], view: &Viewport) {
+    let layout = HexLayout::current();
+    for &cell in cells.iter().filter(|(x, y)| view.cols.contains(x) && view.rows.contains(y)) {
+        let center = layout.cell_center(cell);
+        draw_hexagon(&layout, center, crate::renderer::BLOCKED_COLOR, false);
+        draw_circle(center.x, center.y, layout.size * 0.25, crate::renderer::BLOCKED_COLOR);
+    }
+}
+
+/// Desenha os agentes visíveis no grid hexagonal
+pub fn draw_hexagonal_agents(agents: &Vec<Box<dyn AgentComponent>>, view: &Viewport) {
+    for agent in agents {
+        let pos = agent.get_pos();
+        if !view.contains_circle(pos, agent.get_detection_radius().max(agent.get_physical_radius())) {
+            continue;
+        }
+
+        let detection_color = agent.get_detection_color();
+
+        draw_circle_lines(
+            pos.x,
+            pos.y,
+            agent.get_detection_radius(),
+            2.0,
+            detection_color,
+        );
+
+        let radius = agent.get_physical_radius();
+        draw_circle(pos.x, pos.y, radius, agent.get_color());
+        crate::renderer::draw_energy_bar(agent.as_ref(), pos, radius);
+    }
+}
+
+/// Prévia da pincelada em andamento (o grid só muda quando o botão é solto)
+pub fn draw_hexagonal_stroke_preview(cells: &[(usize, usize)], cell_type: CellType) {
+    let color = crate::renderer::stroke_preview_color(cell_type);
+    let layout = HexLayout::current();
+    for &cell in cells {
+        draw_hexagon(&layout, layout.cell_center(cell), color, true);
+    }
+}
+
+/// Desenha feedback visual para input no grid hexagonal
+pub fn draw_hexagonal_input_feedback(
+    mode: &InputMode,
+    pending_start: Option<(usize, usize)>,
+    mouse_grid_pos: (usize, usize),
+    mouse_over_obstacle: bool,
+) {
+    let layout = HexLayout::current();
+    let center = layout.cell_center(mouse_grid_pos);
+
+    let color = match mode {
+        InputMode::DrawObstacle => {
+            if mouse_over_obstacle {
+                RED
+            } else {
+                Color::new(0.3, 0.3, 0.3, 0.6)
+            }
+        }
+        InputMode::EraseObstacle => Color::new(1.0, 0.4, 0.4, 0.5),
+        InputMode::DrawStation => crate::renderer::stroke_preview_color(CellType::ChargingStation),
+        InputMode::Select => {
+            let c = crate::renderer::SELECTION_COLOR;
+            Color::new(c.r, c.g, c.b, 0.3)
+        }
+        InputMode::Targets => {
+            let c = crate::renderer::TARGET_COLOR;
+            Color::new(c.r, c.g, c.b, 0.4)
+        }
+        InputMode::SetStart => {
+            if mouse_over_obstacle {
+                RED
+            } else {
+                Color::new(0.0, 1.0, 0.0, 0.4)
+            }
+        }
+        InputMode::SetEnd => {
+            // Desenha o ponto inicial pendente
+            if let Some(start) = pending_start {
+                draw_hexagon(&layout, layout.cell_center(start), Color::new(0.0, 1.0, 0.0, 0.6), true);
+            }
+
+            if mouse_over_obstacle {
+                RED
+            } else {
+                Color::new(1.0, 0.0, 0.0, 0.4)
+            }
+        }
+    };
+
+    draw_hexagon(&layout, center, color, true);
+}
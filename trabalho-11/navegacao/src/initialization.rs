@@ -1,5 +1,6 @@
 use crate::abstract_factory::{CardinalSimulationFactory, SimulationFactory};
 use crate::grid::Grid;
+use crate::grid_factory::MapGenerator;
 
 /// Contexto compartilhado passado pela corrente
 pub struct InitContext {
@@ -7,6 +8,9 @@ pub struct InitContext {
     pub factory: Option<Box<dyn SimulationFactory>>,
     pub width: usize,
     pub height: usize,
+    /// Gerador do mapa inicial e a semente dele
+    pub map: MapGenerator,
+    pub map_seed: u64,
 }
 
 /// Interface do Handler da Chain
@@ -30,8 +34,9 @@ impl InitHandler for ConfigInitHandler {
     }
     fn handle(&mut self, context: &mut InitContext) {
         println!("[CHAIN] Configurando simulação...");
-        // Define a fábrica padrão
-        context.factory = Some(Box::new(CardinalSimulationFactory::new()));
+        // Define a fábrica padrão, com a fábrica de grid do gerador de mapa pedido
+        let grid_factory = context.map.create_factory(context.map_seed);
+        context.factory = Some(Box::new(CardinalSimulationFactory::new(grid_factory)));
 
         if let Some(next) = &mut self.next {
             next.handle(context);
@@ -53,7 +58,10 @@ impl InitHandler for GridInitHandler {
         self.next = Some(next);
     }
     fn handle(&mut self, context: &mut InitContext) {
-        println!("[CHAIN] Inicializando Grid...");
+        match context.map {
+            MapGenerator::Empty => println!("[CHAIN] Inicializando Grid..."),
+            map => println!("[CHAIN] Inicializando Grid (mapa {}, semente {})...", map.name(), context.map_seed),
+        }
         if let Some(factory) = &context.factory {
             context.grid = Some(factory.create_grid(context.width, context.height));
         }
@@ -66,11 +74,18 @@ impl InitHandler for GridInitHandler {
 
 // --- Helper para montar a corrente ---
 pub fn init_system(width: usize, height: usize) -> InitContext {
+    init_system_with_map(width, height, MapGenerator::Empty, 0)
+}
+
+/// Mesma corrente, com o grid inicial gerado por `map` a partir de `seed`
+pub fn init_system_with_map(width: usize, height: usize, map: MapGenerator, seed: u64) -> InitContext {
     let mut ctx = InitContext {
         grid: None,
         factory: None,
         width,
         height,
+        map,
+        map_seed: seed,
     };
 
     let mut step1 = ConfigInitHandler::new();
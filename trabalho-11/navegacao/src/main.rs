@@ -0,0 +1,1152 @@
+use macroquad::prelude::*;
+
+// --- Módulos do Projeto ---
+mod agent;
+mod benchmark;
+mod grid;
+mod components;
+mod renderer;
+mod camera;
+mod debug_overlay;
+mod simulation;
+mod headless;
+mod frame_export;
+mod video;
+
+// --- Strategies de Prevenção de Colisão ---
+mod collision_avoidance;
+mod pheromone;
+mod rvo;
+mod obstacle;
+
+// --- Módulos de Fábrica ---
+mod abstract_factory;
+mod agent_factory;
+mod grid_factory;
+
+// --- Módulos do Decorator ---
+mod agent_decorator;
+
+// --- Singleton e Adapter ---
+mod grid_adapter; 
+mod path_manager; 
+mod pathfinding_adapter; 
+mod navmesh;
+
+// --- Renderização Hexagonal ---
+mod hexagonal_renderer;
+mod hex;
+
+// --- Command, CoR, Observer ---
+mod command;
+mod energy;
+mod formation;
+mod allocation;
+mod initialization;
+mod observer;
+mod replay;
+mod scenario;
+mod scenario_picker;
+mod search_visualizer;
+
+use agent_decorator::AgentComponent;
+use grid::{CellType, Grid};
+use hex::HexLayout;
+
+use grid_adapter::{HexagonalAdapter, RectangularCardinalAdapter, RectangularDiagonalAdapter};
+use navmesh::PathPlanner;
+use path_manager::PathManager;
+use pathfinding_adapter::{a_star_traced, a_star_with_adapter};
+
+use allocation::AllocationMethod;
+use camera::ViewCamera;
+use debug_overlay::{DebugCapture, DebugOverlays, SearchNode, SearchTrace};
+use frame_export::{Frame, FrameStyle};
+use video::{VideoFormat, VideoOptions, VideoRecorder};
+use collision_avoidance::AvoidanceMode;
+use energy::Route;
+use command::{Command, CommandManager, EraseCellsCommand, PaintCellsCommand};
+use initialization::init_system_with_map;
+use grid_factory::MapGenerator;
+use scenario::{DecoratorSpec, ScenarioFile};
+use scenario_picker::{PickerAction, SCENARIO_DIR, ScenarioPicker};
+use search_visualizer::SearchVisualizer;
+use simulation::CollisionMonitor;
+
+// --- Constantes da Simulação ---
+const CELL_SIZE: f32 = 20.0;
+const GRID_WIDTH: usize = 60;
+const GRID_HEIGHT: usize = 36;
+/// Maior janela aberta; mapas maiores que isso são vistos pela câmera (zoom e arrasto)
+const MAX_WINDOW_WIDTH: f32 = 1600.0;
+const MAX_WINDOW_HEIGHT: f32 = 1000.0;
+
+#[derive(PartialEq, Debug)]
+enum InputMode {
+    DrawObstacle,
+    EraseObstacle,
+    DrawStation,
+    SetStart,
+    SetEnd,
+    /// Arrastar um retângulo seleciona agentes para a alocação de tarefas
+    Select,
+    /// Cada clique liga/desliga uma célula como alvo da alocação
+    Targets,
+}
+
+/// Pincelada em andamento: do clique até soltar o botão.
+/// Vira um único comando no histórico (um undo desfaz a pincelada inteira).
+/// `cell_type` é o que a pincelada deixa nas células (`Empty` apaga).
+struct BrushStroke {
+    cell_type: CellType,
+    button: MouseButton,
+    cells: Vec<(usize, usize)>,
+    /// Célula do mouse no quadro anterior: um arrasto rápido pinta a linha entre as duas
+    last_cell: Option<(usize, usize)>,
+}
+
+/// Segurar Z/Y: depois de um toque, espera `KEY_REPEAT_DELAY` antes de repetir
+const KEY_REPEAT_DELAY: f64 = 0.4;
+/// Intervalo entre as repetições (um tick da simulação)
+const KEY_REPEAT_INTERVAL: f64 = 1.0 / 60.0;
+
+/// Tecla que dispara ao ser apertada e, segurada, repete depois de uma pausa
+struct KeyRepeat {
+    key: KeyCode,
+    next_repeat: f64,
+}
+
+impl KeyRepeat {
+    fn new(key: KeyCode) -> Self {
+        Self { key, next_repeat: 0.0 }
+    }
+
+    fn triggered(&mut self) -> bool {
+        let now = get_time();
+        if is_key_pressed(self.key) {
+            self.next_repeat = now + KEY_REPEAT_DELAY;
+            return true;
+        }
+        if is_key_down(self.key) && now >= self.next_repeat {
+            self.next_repeat = now + KEY_REPEAT_INTERVAL;
+            return true;
+        }
+        false
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum GridMode {
+    Cardinal,  
+    Diagonal,  
+    Hexagonal, 
+}
+
+impl GridMode {
+    /// Converte o nome usado na linha de comando e nos logs
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "cardinal" => Some(GridMode::Cardinal),
+            "diagonal" => Some(GridMode::Diagonal),
+            "hexagonal" | "hex" => Some(GridMode::Hexagonal),
+            _ => None,
+        }
+    }
+}
+
+pub fn screen_to_grid(x: f32, y: f32, grid_mode: GridMode) -> (usize, usize) {
+    match grid_mode {
+        GridMode::Hexagonal => {
+            // À esquerda ou acima do mapa a célula fica presa na borda
+            let (col, row) = HexLayout::current().cell_at(vec2(x, y));
+            (col.max(0) as usize, row.max(0) as usize)
+        }
+        _ => (
+            (x / CELL_SIZE).floor() as usize,
+            (y / CELL_SIZE).floor() as usize,
+        ),
+    }
+}
+
+fn grid_to_screen_center(pos: (usize, usize), grid_mode: GridMode) -> Vec2 {
+    match grid_mode {
+        GridMode::Hexagonal => HexLayout::current().cell_center(pos),
+        _ => vec2(
+            pos.0 as f32 * CELL_SIZE + CELL_SIZE / 2.0,
+            pos.1 as f32 * CELL_SIZE + CELL_SIZE / 2.0,
+        ),
+    }
+}
+
+/// Células na reta entre duas células, pelo centro delas (pinceladas com o mouse rápido)
+fn cell_line(from: (usize, usize), to: (usize, usize), grid_mode: GridMode) -> Vec<(usize, usize)> {
+    match grid_mode {
+        GridMode::Hexagonal => {
+            let orientation = HexLayout::current().orientation;
+            let [a, b] = [from, to].map(|(x, y)| hex::Hex::from_offset(x as i32, y as i32, orientation));
+            a.line_to(b)
+                .into_iter()
+                .filter_map(|hex| hex.to_cell(orientation, usize::MAX, usize::MAX))
+                .collect()
+        }
+        _ => {
+            // Mesma ideia da linha hexagonal: interpola e arredonda a cada passo
+            let steps = from.0.abs_diff(to.0).max(from.1.abs_diff(to.1));
+            (0..=steps)
+                .map(|i| {
+                    let t = if steps == 0 { 0.0 } else { i as f32 / steps as f32 };
+                    let x = from.0 as f32 + (to.0 as f32 - from.0 as f32) * t;
+                    let y = from.1 as f32 + (to.1 as f32 - from.1 as f32) * t;
+                    (x.round() as usize, y.round() as usize)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Helper: Calcula caminho usando Adapter e Singleton
+fn calculate_path(
+    grid: &Grid,
+    start: (usize, usize),
+    end: (usize, usize),
+    grid_mode: GridMode,
+) -> Option<Vec<(usize, usize)>> {
+    let path_manager = PathManager::instance();
+
+    path_manager.get_or_calculate(start, end, || {
+        with_adapter(grid, grid_mode, |adapter| search_cells(adapter, start, end, grid_mode))
+    })
+}
+
+/// Chama `f` com o adapter da vizinhança do tipo de grid (e da orientação atual dos hexágonos)
+fn with_adapter<R>(grid: &Grid, grid_mode: GridMode, f: impl FnOnce(&dyn grid_adapter::GridAdapter) -> R) -> R {
+    match grid_mode {
+        GridMode::Cardinal => f(&RectangularCardinalAdapter::new(grid)),
+        GridMode::Diagonal => f(&RectangularDiagonalAdapter::new(grid)),
+        GridMode::Hexagonal => f(&HexagonalAdapter::new(grid, HexLayout::current().orientation)),
+    }
+}
+
+/// A* nas células; com a camada de depuração do A* ligada, guarda o rastro da busca
+fn search_cells(
+    adapter: &dyn grid_adapter::GridAdapter,
+    start: (usize, usize),
+    end: (usize, usize),
+    grid_mode: GridMode,
+) -> Option<Vec<(usize, usize)>> {
+    let capture = DebugCapture::instance();
+    if !capture.captures_search() {
+        return a_star_with_adapter(adapter, start, end);
+    }
+
+    let (path, trace) = a_star_traced(adapter, start, end);
+    capture.record_search(SearchTrace {
+        expanded: trace.expanded.into_iter().map(SearchNode::Cell).collect(),
+        open: trace.open.into_iter().map(SearchNode::Cell).collect(),
+        path: path.iter().flatten().map(|&p| grid_to_screen_center(p, grid_mode)).collect(),
+    });
+    path
+}
+
+/// Helper: caminho em pixels entre duas células, pelo planejador escolhido
+/// (A* nas células, passando pelos centros, ou A* na malha de navegação com o funil,
+/// que afasta dos cantos conforme o raio do agente)
+fn calculate_waypoints(
+    grid: &Grid,
+    start: (usize, usize),
+    end: (usize, usize),
+    grid_mode: GridMode,
+    radius: f32,
+) -> Option<Vec<Vec2>> {
+    // Início e destino em componentes diferentes: nem chega a buscar
+    if !grid.is_reachable(start, end, grid_mode) {
+        return None;
+    }
+    let path_manager = PathManager::instance();
+    match path_manager.planner() {
+        PathPlanner::Grid => {
+            let cells = calculate_path(grid, start, end, grid_mode)?;
+            Some(cells.into_iter().map(|p| grid_to_screen_center(p, grid_mode)).collect())
+        }
+        PathPlanner::NavMesh => path_manager.navmesh_path(
+            grid,
+            grid_mode,
+            grid_to_screen_center(start, grid_mode),
+            grid_to_screen_center(end, grid_mode),
+            radius,
+        ),
+    }
+}
+
+/// Helper para criar a stack de Decorators de um agente.
+/// A stack é a mesma para todos os métodos de prevenção de colisão.
+fn create_agent_stack(
+    factory: &dyn agent_factory::AgentFactory,
+    start: Vec2,
+    route: Route,
+    id: usize,
+) -> Box<dyn AgentComponent> {
+    // Agente Base -> Desvio de Direção -> Velocidade -> Visual, com o Observer por fora
+    scenario::build_agent_stack(factory, start, route, id, &DecoratorSpec::default_stack())
+}
+
+/// Helper para planejar o caminho de um agente da stack padrão
+fn plan_agent_route(
+    grid: &Grid,
+    start: (usize, usize),
+    end: (usize, usize),
+    grid_mode: GridMode,
+    factory: &dyn agent_factory::AgentFactory,
+) -> Option<Route> {
+    scenario::plan_agent_route(grid, start, end, grid_mode, factory, &DecoratorSpec::default_stack())
+}
+
+/// Rotas de vários pares (início, destino) planejadas em paralelo, na ordem dos pares
+fn plan_agent_routes(
+    grid: &Grid,
+    queries: &[path_manager::PathKey],
+    grid_mode: GridMode,
+    factory: &dyn agent_factory::AgentFactory,
+) -> Vec<Option<Route>> {
+    scenario::plan_agent_routes(grid, queries, grid_mode, factory, &DecoratorSpec::default_stack())
+}
+
+/// Gera agentes aleatórios 
+fn spawn_random_agents(
+    n: usize,
+    grid: &Grid,
+    agents: &mut Vec<Box<dyn AgentComponent>>,
+    agent_creator: &dyn agent_factory::AgentFactory,
+    grid_mode: GridMode,
+    next_id: &mut usize,
+) {
+    // Sorteia todos os pares antes (destino na componente do início) e planeja as rotas em lote
+    let queries: Vec<_> = (0..n).filter_map(|_| benchmark::random_connected_pair(grid, grid_mode)).collect();
+    let mut count = 0;
+    for (&(start_pos, _), route) in queries.iter().zip(plan_agent_routes(grid, &queries, grid_mode, agent_creator)) {
+        if let Some(route) = route {
+            let start_pixel_pos = grid_to_screen_center(start_pos, grid_mode);
+
+            agents.push(create_agent_stack(agent_creator, start_pixel_pos, route, *next_id));
+
+            *next_id += 1;
+            count += 1;
+        }
+    }
+    println!("Gerado {} agentes aleatórios", count);
+}
+
+/// Tamanho da janela para um grid (espaço extra embaixo para o HUD), limitado a `MAX_WINDOW_*`
+fn window_size(width: usize, height: usize) -> (f32, f32) {
+    (
+        (width as f32 * CELL_SIZE).min(MAX_WINDOW_WIDTH),
+        (height as f32 * CELL_SIZE + 100.0).min(MAX_WINDOW_HEIGHT),
+    )
+}
+
+fn window_conf(width: usize, height: usize) -> Conf {
+    let (window_width, window_height) = window_size(width, height);
+    Conf {
+        window_title: "Trabalho 11 - Evitando Colisão".to_owned(),
+        window_width: window_width as i32,
+        window_height: window_height as i32,
+        fullscreen: false,
+        sample_count: 8,
+        ..Default::default()
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Tempo do planejamento em lote com cada número de threads
+    if args.iter().any(|a| a == "--plan-benchmark") {
+        let result = headless::HeadlessConfig::from_args(&args).and_then(|config| headless::run_planning(&config));
+        if let Err(e) = result {
+            eprintln!("Erro: {}\n{}", e, headless::USAGE);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Tempo do cálculo de velocidades do RVO com cada número de threads
+    if args.iter().any(|a| a == "--rvo-benchmark") {
+        let result = headless::HeadlessConfig::from_args(&args).and_then(|config| headless::run_rvo(&config));
+        if let Err(e) = result {
+            eprintln!("Erro: {}\n{}", e, headless::USAGE);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Custo do armazenamento do grid em um mapa grande
+    if args.iter().any(|a| a == "--grid-benchmark") {
+        let result = headless::HeadlessConfig::from_args(&args).and_then(|config| headless::run_grid(&config));
+        if let Err(e) = result {
+            eprintln!("Erro: {}\n{}", e, headless::USAGE);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Modo headless: roda o benchmark sem abrir janela
+    if args.iter().any(|a| a == "--benchmark") {
+        let config = match headless::HeadlessConfig::from_args(&args) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Erro: {}\n{}\n\n{}", e, headless::USAGE, video::USAGE);
+                std::process::exit(1);
+            }
+        };
+        if let Err(e) = headless::run(&config) {
+            eprintln!("Erro ao gravar o benchmark: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Confere se o código atual ainda gera as mesmas trajetórias de um log
+    if let Some(i) = args.iter().position(|a| a == "--verify") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("Erro: use --verify <arquivo>");
+            std::process::exit(1);
+        };
+        if let Err(e) = headless::verify(std::path::Path::new(path)) {
+            eprintln!("Erro: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Reproduz um log gravado, sem rodar a prevenção de colisão
+    if let Some(i) = args.iter().position(|a| a == "--replay") {
+        let log = match args.get(i + 1).map(|path| replay::ReplayLog::load(std::path::Path::new(path))) {
+            Some(Ok(log)) => log,
+            Some(Err(e)) => {
+                eprintln!("Erro: {}", e);
+                std::process::exit(1);
+            }
+            None => {
+                eprintln!("Erro: use --replay <arquivo>");
+                std::process::exit(1);
+            }
+        };
+        match log.header.hex_layout() {
+            Ok(layout) => HexLayout::set_current(layout),
+            Err(e) => {
+                eprintln!("Erro: {}", e);
+                std::process::exit(1);
+            }
+        }
+        let conf = window_conf(log.header.width, log.header.height);
+        macroquad::Window::from_config(conf, run_replay(replay::ReplayPlayer::new(log)));
+        return;
+    }
+
+    // Método inicial da janela: --avoidance <rvo|pheromone|none|off>
+    let mut avoidance = AvoidanceMode::Rvo;
+    if let Some(i) = args.iter().position(|a| a == "--avoidance") {
+        match args.get(i + 1).and_then(|name| AvoidanceMode::parse(name)) {
+            Some(mode) => avoidance = mode,
+            None => {
+                eprintln!("Erro: use --avoidance <rvo|pheromone|none|off>");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Mapa inicial da janela: --map <empty|maze|caves|rooms|rectangles|terrain> (semente: --seed, padrão 42)
+    let mut map = MapGenerator::Empty;
+    if let Some(i) = args.iter().position(|a| a == "--map") {
+        match args.get(i + 1).and_then(|name| MapGenerator::parse(name)) {
+            Some(generator) => map = generator,
+            None => {
+                eprintln!("Erro: use --map <empty|maze|caves|rooms|rectangles|terrain>");
+                std::process::exit(1);
+            }
+        }
+    }
+    let mut map_seed = 42;
+    if let Some(i) = args.iter().position(|a| a == "--seed") {
+        match args.get(i + 1).and_then(|n| n.parse().ok()) {
+            Some(seed) => map_seed = seed,
+            None => {
+                eprintln!("Erro: use --seed <N>");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Cenário inicial: --scenario <arquivo.toml|arquivo.json> (o --avoidance explícito vale por cima)
+    let mut scenario = None;
+    if let Some(i) = args.iter().position(|a| a == "--scenario") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("Erro: use --scenario <arquivo.toml|arquivo.json>");
+            std::process::exit(1);
+        };
+        match ScenarioFile::load(std::path::Path::new(path)) {
+            Ok(mut file) => {
+                if args.iter().any(|a| a == "--avoidance") {
+                    file.avoidance = avoidance.name().to_string();
+                }
+                scenario = Some(Box::new(file));
+            }
+            Err(e) => {
+                eprintln!("Erro: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Vídeo: com --video a gravação começa ao abrir a janela; as outras opções valem também para a tecla I
+    let mut video_options = VideoOptions::default();
+    let mut frame_style = FrameStyle::default();
+    for pair in args.windows(2) {
+        if let Err(e) = video::parse_option(&pair[0], &pair[1], &mut video_options, &mut frame_style) {
+            eprintln!("Erro: {}\n{}", e, video::USAGE);
+            std::process::exit(1);
+        }
+    }
+    let record_video = args.iter().any(|a| a == "--video");
+
+    let (width, height) = scenario
+        .as_ref()
+        .map_or((GRID_WIDTH, GRID_HEIGHT), |s| (s.grid.width, s.grid.height));
+    macroquad::Window::from_config(
+        window_conf(width, height),
+        run_app(avoidance, (map, map_seed), scenario, video_options, frame_style, record_video),
+    );
+}
+
+/// Arquétipos que a tecla T alterna para os agentes colocados com o mouse
+const MANUAL_AGENT_KINDS: [&str; 3] = ["blue", "scout", "carrier"];
+
+async fn run_app(
+    initial_avoidance: AvoidanceMode,
+    (map, map_seed): (MapGenerator, u64),
+    initial_scenario: Option<Box<ScenarioFile>>,
+    video_options: VideoOptions,
+    frame_style: FrameStyle,
+    record_video: bool,
+) {
+    let mut benchmark_manager = benchmark::BenchmarkManager::new();
+    let init_ctx = init_system_with_map(GRID_WIDTH, GRID_HEIGHT, map, map_seed);
+
+    let mut grid = init_ctx.grid.expect("Grid error");
+    let factory = init_ctx.factory.expect("Factory error");
+
+    let blue_agent_creator = factory.create_blue_agent_factory();
+    let red_agent_creator = factory.create_red_agent_factory();
+
+    // Tipo dos agentes colocados à mão (tecla T alterna)
+    let mut manual_kind = 0;
+    let mut manual_agent_creator = factory.create_agent_factory(MANUAL_AGENT_KINDS[manual_kind]).expect("Factory error");
+
+    let mut avoidance = initial_avoidance;
+    let mut strategy = avoidance.create_strategy(grid.width, grid.height);
+    let mut show_pheromones = true;
+    let mut paused = false;
+
+    let mut command_manager = CommandManager::new();
+    let mut collision_monitor = CollisionMonitor::new();
+
+    let mut agents: Vec<Box<dyn AgentComponent>> = Vec::new();
+    let mut mode = InputMode::DrawObstacle;
+    let mut grid_mode = GridMode::Cardinal;
+    let mut pending_start: Option<(usize, usize)> = None;
+    let mut stroke: Option<BrushStroke> = None;
+    let mut benchmark_message = String::new();
+    let mut undo_key = KeyRepeat::new(KeyCode::Z);
+    let mut redo_key = KeyRepeat::new(KeyCode::Y);
+
+    let mut next_agent_id: usize = 0;
+
+    // Cenário carregado (linha de comando ou tecla F) e tempo simulado desde o carregamento
+    let mut pending_scenario = initial_scenario;
+    let mut scenario_info: Option<(String, f32)> = None;
+    let mut scenario_seed: Option<u64> = None;
+    let mut scenario_time = 0.0;
+    let mut picker: Option<ScenarioPicker> = None;
+
+    // Alocação de tarefas: agentes selecionados (ids), células-alvo e o método (tecla H)
+    let mut selected: Vec<usize> = Vec::new();
+    let mut targets: Vec<(usize, usize)> = Vec::new();
+    let mut drag_start: Option<Vec2> = None;
+    let mut allocation_method = AllocationMethod::Hungarian;
+
+    let mut view_camera = ViewCamera::new();
+    let mut debug_overlays = DebugOverlays::default();
+    // Busca passo a passo (tecla B); enquanto aberta, o mouse marca início e destino da busca
+    let mut visualizer: Option<SearchVisualizer> = None;
+
+    // Vídeo (tecla I), medido no tempo simulado desde o carregamento do cenário
+    let mut video_recorder: Option<VideoRecorder> = None;
+    let mut start_video = record_video;
+
+    loop {
+        let dt = get_frame_time();
+
+        if let Some(file) = pending_scenario.take() {
+            PathManager::instance().set_planner(file.planner());
+            HexLayout::set_current(file.hex_layout());
+            let built = file.build(file.grid_mode(), file.seed);
+            grid = built.grid;
+            agents = built.agents;
+            next_agent_id = agents.len();
+            grid_mode = file.grid_mode();
+            avoidance = file.avoidance();
+            strategy = avoidance.create_strategy(grid.width, grid.height);
+            command_manager.clear();
+            collision_monitor.clear();
+            pending_start = None;
+            stroke = None;
+            selected.clear();
+            targets.clear();
+            paused = false;
+            scenario_time = 0.0;
+            scenario_info = Some((file.name().to_string(), file.duration));
+            scenario_seed = Some(file.seed);
+            // Um vídeo em andamento fecha e recomeça com o cenário novo
+            if video_recorder.is_some() {
+                stop_video(&mut video_recorder);
+                start_video = true;
+            }
+
+            let (window_width, window_height) = window_size(grid.width, grid.height);
+            request_new_screen_size(window_width, window_height);
+            view_camera.reset();
+            println!("Cenário '{}' carregado: {} agentes.", file.name(), agents.len());
+        }
+
+        // Mouse no mundo (pela câmera); fora do mapa a célula fica fora dos limites do grid
+        view_camera.update(camera::world_size(grid_mode, grid.width, grid.height), picker.is_none());
+        if picker.is_none() {
+            debug_overlays.update();
+        }
+        let mouse = view_camera.screen_to_world(Vec2::from(mouse_position()));
+        let mouse_cell = view_camera.mouse_cell(grid_mode, grid.width, grid.height);
+        let (grid_x, grid_y) = mouse_cell.unwrap_or((grid.width, grid.height));
+
+        // --- Seletor de Cenários (tecla F) ---
+        // Enter confirma o seletor: só vira "enviar agentes" se ele já estava fechado neste quadro
+        let picker_was_open = picker.is_some();
+        if let Some(current) = &mut picker {
+            match current.update() {
+                PickerAction::None => {}
+                PickerAction::Cancel => picker = None,
+                PickerAction::Loaded(file) => {
+                    pending_scenario = Some(file);
+                    picker = None;
+                }
+            }
+        } else if is_key_pressed(KeyCode::F) {
+            picker = Some(ScenarioPicker::open(std::path::Path::new(SCENARIO_DIR)));
+        }
+
+        // --- Busca passo a passo (tecla B) ---
+        if is_key_pressed(KeyCode::B) && picker.is_none() {
+            visualizer = match visualizer {
+                Some(_) => None,
+                None => Some(SearchVisualizer::new()),
+            };
+            stroke = None;
+            drag_start = None;
+        }
+        if let Some(current) = &mut visualizer
+            && picker.is_none()
+        {
+            with_adapter(&grid, grid_mode, |adapter| current.update(adapter, grid.revision(), mouse_cell, get_frame_time()));
+        }
+
+        // --- Inputs (Teclado) ---
+        if is_key_pressed(KeyCode::O) { mode = InputMode::DrawObstacle; pending_start = None; }
+        if is_key_pressed(KeyCode::E) { mode = InputMode::EraseObstacle; pending_start = None; }
+        if is_key_pressed(KeyCode::K) { mode = InputMode::DrawStation; pending_start = None; }
+        if is_key_pressed(KeyCode::A) { mode = InputMode::SetStart; pending_start = None; }
+        if is_key_pressed(KeyCode::S) { mode = InputMode::Select; pending_start = None; }
+        if is_key_pressed(KeyCode::X) { mode = InputMode::Targets; pending_start = None; }
+        if is_key_pressed(KeyCode::H) {
+            allocation_method = allocation_method.next();
+            println!("Alocação de tarefas: {}", allocation_method.name());
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            selected.clear();
+            targets.clear();
+        }
+        // Enter manda os agentes selecionados para os alvos
+        if is_key_pressed(KeyCode::Enter) && !picker_was_open {
+            match allocation::assign_agents_to_targets(&grid, &agents, &selected, &targets, grid_mode, allocation_method) {
+                Some(cmd) => command_manager.execute_now(cmd, &mut agents, &mut grid),
+                None => println!("Alocação: selecione agentes [S] e marque alvos [X] antes."),
+            }
+        }
+        if is_key_pressed(KeyCode::T) {
+            manual_kind = (manual_kind + 1) % MANUAL_AGENT_KINDS.len();
+            manual_agent_creator = factory.create_agent_factory(MANUAL_AGENT_KINDS[manual_kind]).expect("Factory error");
+        }
+        
+        // --- CLEAR GERAL ---
+        if is_key_pressed(KeyCode::C) {
+            grid.clear();
+            agents.clear();
+            pending_start = None;
+            stroke = None;
+            selected.clear();
+            targets.clear();
+            PathManager::instance().clear_cache();
+            strategy.reset();
+            command_manager.clear(); 
+            collision_monitor.clear();
+            next_agent_id = 0; // Reset ID
+            println!("Simulação Resetada.");
+        }
+        
+        if is_key_pressed(KeyCode::R) {
+            spawn_random_agents(20, &grid, &mut agents, red_agent_creator.as_ref(), grid_mode, &mut next_agent_id);
+        }
+        if is_key_pressed(KeyCode::M) {
+            avoidance = avoidance.next();
+            strategy = avoidance.create_strategy(grid.width, grid.height);
+            println!("Prevenção de colisão: {}", strategy.name());
+        }
+        if is_key_pressed(KeyCode::P) {
+            show_pheromones = !show_pheromones;
+        }
+        // Planejador das próximas rotas; as rotas já dadas continuam as mesmas
+        if is_key_pressed(KeyCode::N) {
+            let planner = PathManager::instance().planner().next();
+            PathManager::instance().set_planner(planner);
+            println!("Caminhos: {}", planner.name());
+        }
+        if is_key_pressed(KeyCode::G) {
+             grid_mode = match grid_mode {
+                GridMode::Cardinal => GridMode::Diagonal,
+                GridMode::Diagonal => GridMode::Hexagonal,
+                GridMode::Hexagonal => GridMode::Cardinal,
+            };
+            PathManager::instance().clear_cache();
+            strategy.reset();
+            if let Some(current) = &mut visualizer { current.invalidate(); }
+        }
+        // Orientação dos hexágonos: as células mudam de lugar e de vizinhos
+        if is_key_pressed(KeyCode::Q) {
+            let layout = HexLayout::current();
+            HexLayout::set_current(HexLayout::new(layout.orientation.next(), layout.size));
+            PathManager::instance().clear_cache();
+            strategy.reset();
+            if let Some(current) = &mut visualizer { current.invalidate(); }
+            println!("Hexágonos: {}", layout.orientation.next().name());
+        }
+        if is_key_pressed(KeyCode::Space) { paused = !paused; }
+        // Desfazer/refazer andam um tick inteiro; segurar a tecla continua andando depois de uma pausa
+        if undo_key.triggered() { paused = true; command_manager.undo_last(&mut agents, &mut grid); }
+        if redo_key.triggered() { paused = true; command_manager.redo_last(&mut agents, &mut grid); }
+        if is_key_pressed(KeyCode::L) {
+            toggle_recording(&mut command_manager, &grid, grid_mode, strategy.name());
+        }
+        if is_key_pressed(KeyCode::I) {
+            if video_recorder.is_some() {
+                stop_video(&mut video_recorder);
+            } else {
+                start_video = true;
+            }
+        }
+
+        // --- Inputs Benchmark ---
+        
+        // Benchmark 1
+        if is_key_pressed(KeyCode::Key1) {
+             grid.clear(); agents.clear(); strategy.reset(); command_manager.clear(); collision_monitor.clear(); next_agent_id = 0;
+             benchmark::spawn_opposing_rows(&grid, &mut agents, blue_agent_creator.as_ref(), grid_mode, &mut next_agent_id);
+             benchmark_manager.start_test(&format!("{}_1_Row_Opposing", strategy.name()));
+        }
+        
+        // Benchmark 2
+        if is_key_pressed(KeyCode::Key2) {
+             grid.clear(); agents.clear(); strategy.reset(); command_manager.clear(); collision_monitor.clear(); next_agent_id = 0;
+             benchmark::spawn_double_opposing_rows(&grid, &mut agents, blue_agent_creator.as_ref(), grid_mode, &mut next_agent_id);
+             benchmark_manager.start_test(&format!("{}_2_Rows_Opposing", strategy.name()));
+        }
+        
+        // Benchmark 3
+        if is_key_pressed(KeyCode::Key3) {
+             grid.clear(); agents.clear(); strategy.reset(); command_manager.clear(); collision_monitor.clear(); next_agent_id = 0;
+             benchmark::spawn_random_scenario(&grid, &mut agents, blue_agent_creator.as_ref(), grid_mode, &mut next_agent_id, 100);
+             benchmark_manager.start_test(&format!("{}_Random_100", strategy.name()));
+        }
+
+        let benchmark_was_running = benchmark_manager.is_running();
+        benchmark_manager.update(agents.len());
+        if benchmark_manager.is_running() {
+            benchmark_message.clear();
+        } else if benchmark_was_running {
+            benchmark_message = "Benchmark salvo em benchmark_results.csv".to_string();
+        }
+
+        // --- Inputs Mouse ---
+        // Pincel: botão esquerdo pinta (ou apaga no modo borracha), botão direito sempre apaga
+        if stroke.is_none() && picker.is_none() && visualizer.is_none() {
+            if is_mouse_button_pressed(MouseButton::Right) {
+                stroke = Some(BrushStroke { cell_type: CellType::Empty, button: MouseButton::Right, cells: Vec::new(), last_cell: None });
+            } else if is_mouse_button_pressed(MouseButton::Left) {
+                let cell_type = match mode {
+                    InputMode::DrawObstacle => Some(CellType::Obstacle),
+                    InputMode::EraseObstacle => Some(CellType::Empty),
+                    InputMode::DrawStation => Some(CellType::ChargingStation),
+                    InputMode::SetStart | InputMode::SetEnd | InputMode::Select | InputMode::Targets => None,
+                };
+                if let Some(cell_type) = cell_type {
+                    stroke = Some(BrushStroke { cell_type, button: MouseButton::Left, cells: Vec::new(), last_cell: None });
+                }
+            }
+        }
+        if let Some(current) = &mut stroke
+            && let Some(cell) = mouse_cell
+        {
+            // Só guarda células que a pincelada realmente muda
+            for (x, y) in cell_line(current.last_cell.unwrap_or(cell), cell, grid_mode) {
+                if x < grid.width
+                    && y < grid.height
+                    && grid.get_cell(x, y) != Some(current.cell_type)
+                    && !current.cells.contains(&(x, y))
+                {
+                    current.cells.push((x, y));
+                }
+            }
+            current.last_cell = Some(cell);
+        }
+        if let Some(current) = &stroke
+            && !is_mouse_button_down(current.button)
+        {
+            let current = stroke.take().unwrap();
+            if !current.cells.is_empty() {
+                let cmd: Box<dyn Command> = if current.cell_type == CellType::Empty {
+                    Box::new(EraseCellsCommand::new(current.cells))
+                } else {
+                    Box::new(PaintCellsCommand::new(current.cells, current.cell_type))
+                };
+                command_manager.execute_now(cmd, &mut agents, &mut grid);
+            }
+        }
+
+        match mode {
+            _ if picker.is_some() || visualizer.is_some() => {}
+            InputMode::DrawObstacle | InputMode::EraseObstacle | InputMode::DrawStation => {}
+            InputMode::Select => {
+                if is_mouse_button_pressed(MouseButton::Left) {
+                    drag_start = Some(mouse);
+                }
+                if let Some(from) = drag_start
+                    && !is_mouse_button_down(MouseButton::Left)
+                {
+                    // Um clique sem arrastar vira um retângulo mínimo em volta do cursor
+                    let (min, max) = (from.min(mouse) - vec2(2.0, 2.0), from.max(mouse) + vec2(2.0, 2.0));
+                    selected = agents
+                        .iter()
+                        .filter(|a| {
+                            let pos = a.get_pos();
+                            let r = a.get_physical_radius();
+                            pos.x + r >= min.x && pos.x - r <= max.x && pos.y + r >= min.y && pos.y - r <= max.y
+                        })
+                        .map(|a| a.get_id())
+                        .collect();
+                    drag_start = None;
+                }
+            }
+            InputMode::Targets => {
+                if is_mouse_button_pressed(MouseButton::Left)
+                    && grid_x < grid.width
+                    && grid_y < grid.height
+                    && !grid.is_obstacle(grid_x, grid_y)
+                {
+                    let cell = (grid_x, grid_y);
+                    match targets.iter().position(|&t| t == cell) {
+                        Some(i) => { targets.remove(i); }
+                        None => targets.push(cell),
+                    }
+                }
+            }
+            InputMode::SetStart => {
+                if is_mouse_button_pressed(MouseButton::Left) && !grid.is_obstacle(grid_x, grid_y) {
+                    pending_start = Some((grid_x, grid_y));
+                    mode = InputMode::SetEnd;
+                }
+            }
+            InputMode::SetEnd => {
+                if is_mouse_button_pressed(MouseButton::Left)
+                    && !grid.is_obstacle(grid_x, grid_y)
+                    && let Some(start_pos) = pending_start
+                {
+                    let end_pos = (grid_x, grid_y);
+                    if let Some(route) = plan_agent_route(&grid, start_pos, end_pos, grid_mode, manual_agent_creator.as_ref()) {
+                        let start_screen = grid_to_screen_center(start_pos, grid_mode);
+                        agents.push(create_agent_stack(manual_agent_creator.as_ref(), start_screen, route, next_agent_id));
+                        next_agent_id += 1;
+                    }
+                    mode = InputMode::SetStart;
+                    pending_start = None;
+                }
+            }
+        }
+
+        // --- Simulação (mesmo passo usado pelo benchmark headless) ---
+        if !paused {
+            simulation::step(&mut agents, &mut grid, &mut command_manager, strategy.as_mut(), grid_mode, dt);
+
+            // Pausa quando o cenário atinge a duração pedida no arquivo
+            scenario_time += dt;
+            if let Some((name, duration)) = &scenario_info
+                && scenario_time >= *duration
+                && scenario_time - dt < *duration
+            {
+                paused = true;
+                println!("Cenário '{}' chegou ao fim ({:.1}s).", name, duration);
+            }
+        }
+        collision_monitor.update(&agents);
+
+        // --- Vídeo (desenhado pelo frame_export, igual ao headless) ---
+        let draw_pheromones = show_pheromones && avoidance == AvoidanceMode::Pheromone;
+        if start_video || video_recorder.is_some() {
+            let name = scenario_info.as_ref().map_or("janela", |(name, _)| name.as_str());
+            let mut style = frame_style;
+            style.overlays.pheromones &= draw_pheromones;
+            let frame = Frame {
+                grid: &grid,
+                grid_mode,
+                agents: &agents,
+                style,
+                caption: frame_export::caption(name, strategy.name(), scenario_seed, scenario_time),
+            };
+            if start_video {
+                start_video = false;
+                video_recorder = create_video(video_options, &frame, name, scenario_time);
+            }
+            if let Some(recorder) = &mut video_recorder {
+                if let Err(e) = recorder.capture(scenario_time, &frame) {
+                    eprintln!("Erro ao gravar o vídeo: {}", e);
+                    stop_video(&mut video_recorder);
+                } else if recorder.is_past_end(scenario_time) {
+                    stop_video(&mut video_recorder);
+                }
+            }
+        }
+
+        // --- Renderização ---
+        clear_background(Color::from_hex(0x111111));
+
+        // O mundo é desenhado pela câmera, só o que está visível; o HUD volta para a tela
+        set_camera(&view_camera.camera());
+        let view = view_camera.viewport(grid_mode, grid.width, grid.height);
+        let search_trace = if debug_overlays.search { DebugCapture::instance().last_search() } else { None };
+        let blocked_cells = if debug_overlays.blocked_cells { pheromone::PheromoneManager::instance().blocked_cells() } else { Vec::new() };
+        match grid_mode {
+            GridMode::Hexagonal => {
+                hexagonal_renderer::draw_hexagonal_grid(&view);
+                hexagonal_renderer::draw_hexagonal_cells(&grid, &view);
+                if debug_overlays.components { hexagonal_renderer::draw_hexagonal_components(&grid, &view); }
+                if let Some(trace) = &search_trace { hexagonal_renderer::draw_hexagonal_search_trace(trace, &view); }
+                if let Some(current) = &visualizer { hexagonal_renderer::draw_hexagonal_search_visualizer(current, &view); }
+                if let Some(current) = &stroke { hexagonal_renderer::draw_hexagonal_stroke_preview(&current.cells, current.cell_type); }
+                if draw_pheromones { renderer::draw_pheromones(&view, CELL_SIZE); }
+                hexagonal_renderer::draw_hexagonal_blocked_cells(&blocked_cells, &view);
+                hexagonal_renderer::draw_hexagonal_agents(&agents, &view);
+                if mouse_cell.is_some() {
+                    hexagonal_renderer::draw_hexagonal_input_feedback(&mode, pending_start, (grid_x, grid_y), grid.is_obstacle(grid_x, grid_y));
+                }
+            }
+            _ => {
+                renderer::draw_grid(&view, CELL_SIZE);
+                renderer::draw_cells(&grid, &view, CELL_SIZE);
+                if debug_overlays.components { renderer::draw_component_cells(&grid, grid_mode, &view, CELL_SIZE); }
+                if let Some(trace) = &search_trace { renderer::draw_search_trace(trace, &view, CELL_SIZE); }
+                if let Some(current) = &visualizer { renderer::draw_search_visualizer(current, &view, CELL_SIZE); }
+                if let Some(current) = &stroke { renderer::draw_stroke_preview(&current.cells, current.cell_type, CELL_SIZE); }
+                if draw_pheromones { renderer::draw_pheromones(&view, CELL_SIZE); }
+                renderer::draw_blocked_cells(&blocked_cells, &view, CELL_SIZE);
+                renderer::draw_agents(&agents, &view);
+                if mouse_cell.is_some() {
+                    renderer::draw_input_feedback(&mode, pending_start, (grid_x, grid_y), CELL_SIZE, grid.is_obstacle(grid_x, grid_y));
+                }
+            }
+        }
+
+        let planner = PathManager::instance().planner();
+        if planner == PathPlanner::NavMesh {
+            PathManager::instance().with_navmesh(&grid, grid_mode, |mesh| renderer::draw_navmesh(mesh, &view));
+        }
+        renderer::draw_agent_debug(&agents, &debug_overlays, &view);
+
+        let target_points: Vec<Vec2> = targets.iter().map(|&t| grid_to_screen_center(t, grid_mode)).collect();
+        let drag = drag_start.map(|from| (from, mouse));
+        renderer::draw_allocation(&agents, &selected, &target_points, drag);
+        set_default_camera();
+
+        let scenario_text = scenario_info
+            .as_ref()
+            .map(|(name, duration)| format!("Cenário: {} ({:.1}/{:.0}s)", name, scenario_time.min(*duration), duration))
+            .unwrap_or_default();
+        let mode_text = format!(
+            "Modo: {:?} | Tipo: {} | Alocação: {} ({} agentes, {} alvos)",
+            mode,
+            MANUAL_AGENT_KINDS[manual_kind],
+            allocation_method.name(),
+            selected.len(),
+            targets.len()
+        );
+        let hex_text = match grid_mode {
+            GridMode::Hexagonal => format!(" ({})", HexLayout::current().orientation.name()),
+            _ => String::new(),
+        };
+        let grid_text = format!("Grid: {:?}{} | Caminhos: {} | Zoom: {:.0}%", grid_mode, hex_text, planner.name(), view.zoom * 100.0);
+        let status_text = match (&visualizer, &video_recorder) {
+            (Some(current), _) => current.status(),
+            (None, Some(recorder)) => format!("REC vídeo: {} quadros [I] para parar", recorder.frames()),
+            (None, None) => benchmark_message.clone(),
+        };
+        let components_text = if debug_overlays.components {
+            format!(" ({} componentes)", grid.with_components(grid_mode, |components| components.count()))
+        } else {
+            String::new()
+        };
+        let algorithm_text = format!("{} | Depuração: {}{}", strategy.name(), debug_overlays.summary(), components_text);
+        draw_hud_extended(&mode_text, &grid_text, &algorithm_text, agents.len(), paused, &scenario_text, &status_text);
+        if let Some(current) = &picker {
+            current.draw();
+        }
+        next_frame().await
+    }
+}
+
+/// Começa um vídeo em `videos/` (GIF ou pasta de PNGs), do tamanho do quadro atual
+fn create_video(options: VideoOptions, frame: &Frame, name: &str, now: f32) -> Option<VideoRecorder> {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = match options.format {
+        VideoFormat::Gif => std::path::PathBuf::from(format!("videos/{}_{}.gif", name, stamp)),
+        VideoFormat::PngSequence => std::path::PathBuf::from(format!("videos/{}_{}", name, stamp)),
+    };
+    match VideoRecorder::create(&path, options, frame.size(), now) {
+        Ok(recorder) => {
+            println!(">>> Gravando vídeo em {}", path.display());
+            Some(recorder)
+        }
+        Err(e) => {
+            eprintln!("Erro ao criar o vídeo: {}", e);
+            None
+        }
+    }
+}
+
+/// Fecha o vídeo em andamento (o GIF termina de ser escrito quando o gravador é descartado)
+fn stop_video(recorder: &mut Option<VideoRecorder>) {
+    if let Some(recorder) = recorder.take() {
+        println!("<<< Vídeo encerrado: {} quadros em {}", recorder.frames(), recorder.path().display());
+    }
+}
+
+/// Liga/desliga a gravação do log de replay em `replays/`
+fn toggle_recording(command_manager: &mut CommandManager, grid: &Grid, grid_mode: GridMode, algorithm: &str) {
+    if command_manager.is_recording() {
+        match command_manager.stop_recording() {
+            Ok(()) => println!("<<< Gravação do replay encerrada."),
+            Err(e) => eprintln!("Erro ao fechar o replay: {}", e),
+        }
+        return;
+    }
+
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = std::path::PathBuf::from(format!("replays/replay_{}.jsonl", stamp));
+    let header = replay::ReplayHeader::new(grid, grid_mode, algorithm);
+
+    match replay::ReplayRecorder::create(&path, &header) {
+        Ok(recorder) => {
+            command_manager.start_recording(recorder);
+            println!(">>> Gravando replay em {}", path.display());
+        }
+        Err(e) => eprintln!("Erro ao criar o replay: {}", e),
+    }
+}
+
+/// Janela do modo replay: [Espaço] pausa, [←]/[→] volta/avança um tick, [Home] reinicia
+async fn run_replay(mut player: replay::ReplayPlayer) {
+    let grid_mode = GridMode::parse(&player.header().grid_mode).unwrap_or(GridMode::Cardinal);
+    let mut paused = false;
+    let mut view_camera = ViewCamera::new();
+
+    loop {
+        view_camera.update(camera::world_size(grid_mode, player.grid.width, player.grid.height), true);
+        if is_key_pressed(KeyCode::Space) { paused = !paused; }
+        if is_key_pressed(KeyCode::Home) { player.restart(); }
+        if is_key_pressed(KeyCode::Left) {
+            paused = true;
+            player.seek(player.tick().saturating_sub(1));
+        }
+        if is_key_pressed(KeyCode::Right) {
+            paused = true;
+            player.step_forward();
+        }
+        if !paused {
+            player.step_forward();
+        }
+
+        clear_background(Color::from_hex(0x111111));
+
+        set_camera(&view_camera.camera());
+        let view = view_camera.viewport(grid_mode, player.grid.width, player.grid.height);
+        match grid_mode {
+            GridMode::Hexagonal => {
+                hexagonal_renderer::draw_hexagonal_grid(&view);
+                hexagonal_renderer::draw_hexagonal_cells(&player.grid, &view);
+                hexagonal_renderer::draw_hexagonal_agents(&player.agents, &view);
+            }
+            _ => {
+                renderer::draw_grid(&view, CELL_SIZE);
+                renderer::draw_cells(&player.grid, &view, CELL_SIZE);
+                renderer::draw_agents(&player.agents, &view);
+            }
+        }
+        set_default_camera();
+
+        let status = format!(
+            "REPLAY ({}) | Tick {}/{}{}",
+            player.header().avoidance,
+            player.tick(),
+            player.total_ticks(),
+            if paused { " | PAUSADO" } else { "" }
+        );
+        draw_text("[Espaço] Pausa | [←/→] Tick anterior/próximo | [Home] Reinicia | [Roda] Zoom | [Botão do meio] Mover | [V] Enquadrar", 10.0, 25.0, 20.0, WHITE);
+        draw_text(&status, 10.0, 50.0, 24.0, YELLOW);
+        draw_text(&format!("Agentes: {}", player.agents.len()), 10.0, 75.0, 24.0, WHITE);
+
+        next_frame().await
+    }
+}
+
+fn draw_hud_extended(mode_text: &str, grid_text: &str, algorithm: &str, agent_count: usize, paused: bool, scenario: &str, benchmark_msg: &str) {
+    let algo_text = format!("Algoritmo: {}", algorithm);
+    let help_text = "[O] Obstáculo | [E] Borracha | [K] Recarga | [A] Agente | [R] Random | [C] Clear | [G] Grid | [Q] Hexágonos | [M] Método | [P] Feromônios | [L] Gravar | [B] Busca";
+    let history_text = "[Espaço] Pausa | [Z] Desfazer | [Y] Refazer | [Botão direito] Apagar | [F] Abrir cenário | [T] Tipo de agente | [N] Navmesh | [I] Vídeo | [F1-F5] Depuração";
+    let allocation_text = "[S] Selecionar | [X] Alvos | [H] Alocação | [Enter] Enviar | [Backspace] Limpar | [Roda] Zoom | [Botão do meio] Mover | [V] Enquadrar";
+    let agents_text = format!("Agentes: {}{}", agent_count, if paused { " | PAUSADO" } else { "" });
+    
+    draw_text(help_text, 10.0, 25.0, 20.0, WHITE);
+    draw_text(history_text, 10.0, 45.0, 20.0, WHITE);
+    draw_text(allocation_text, 10.0, 65.0, 20.0, WHITE);
+    draw_text(mode_text, 10.0, 90.0, 24.0, YELLOW);
+    draw_text(grid_text, 10.0, 115.0, 24.0, BLUE);
+    draw_text(&algo_text, 10.0, 140.0, 24.0, ORANGE);
+    draw_text(&agents_text, 10.0, 165.0, 24.0, WHITE);
+
+    if !scenario.is_empty() {
+        draw_text(scenario, 10.0, 190.0, 24.0, GREEN);
+    }
+    if !benchmark_msg.is_empty() {
+        draw_text(benchmark_msg, 10.0, 215.0, 20.0, GREEN);
+    }
+}
\ No newline at end of file
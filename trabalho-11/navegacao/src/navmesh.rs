@@ -0,0 +1,731 @@
+use crate::grid::Grid;
+use crate::debug_overlay::{DebugCapture, SearchNode, SearchTrace};
+use crate::hex::HexLayout;
+use crate::{CELL_SIZE, GridMode, screen_to_grid};
+use macroquad::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Margem (px) entre o corpo do agente e os cantos das paredes: a folga do caminho é o raio mais ela
+const PORTAL_MARGIN: f32 = 2.0;
+/// Resolução (px) das coordenadas inteiras usadas na triangulação e para juntar vértices
+const SNAP: f32 = 0.01;
+
+/// Contornos dos polígonos livres e o polígono que contém cada célula livre
+type Polygons = (Vec<Vec<Vec2>>, HashMap<(usize, usize), usize>);
+
+/// Como as rotas dos agentes são planejadas
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PathPlanner {
+    /// A* célula a célula (caminho pelos centros das células)
+    Grid,
+    /// A* sobre os portais da malha de navegação, suavizado pelo funil
+    NavMesh,
+}
+
+impl PathPlanner {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "grid" | "cells" => Some(PathPlanner::Grid),
+            "navmesh" | "mesh" => Some(PathPlanner::NavMesh),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PathPlanner::Grid => "grid",
+            PathPlanner::NavMesh => "navmesh",
+        }
+    }
+
+    /// Próximo planejador (tecla N)
+    pub fn next(&self) -> Self {
+        match self {
+            PathPlanner::Grid => PathPlanner::NavMesh,
+            PathPlanner::NavMesh => PathPlanner::Grid,
+        }
+    }
+}
+
+/// Ligação entre dois polígonos vizinhos: o trecho de borda que eles compartilham
+#[derive(Debug, Clone, Copy)]
+struct Portal {
+    neighbor: usize,
+    a: Vec2,
+    b: Vec2,
+    /// Direção para longe das paredes nas pontas que encostam no contorno dos obstáculos
+    a_normal: Option<Vec2>,
+    b_normal: Option<Vec2>,
+}
+
+impl Portal {
+    /// O agente passa: o portal tem pelo menos o diâmetro dele ou não fica entre duas paredes.
+    /// Os portais de canto do modo diagonal (sem normais) seguem o A* das células e sempre passam.
+    fn fits(&self, radius: f32) -> bool {
+        self.a_normal.is_none() || self.b_normal.is_none() || self.a.distance(self.b) >= 2.0 * radius
+    }
+}
+
+/// Malha de navegação: o espaço livre do grid em polígonos convexos, triangulados.
+/// A busca anda no grafo de adjacência dos polígonos, passando pelos portais.
+pub struct NavMesh {
+    /// Polígonos convexos (retângulos de células livres ou hexágonos), em pixels
+    polygons: Vec<Vec<Vec2>>,
+    /// Triângulos de todos os polígonos, já em pixels
+    triangles: Vec<[Vec2; 3]>,
+    /// Portais de cada polígono
+    portals: Vec<Vec<Portal>>,
+    /// Polígono de cada célula livre
+    cell_polygon: HashMap<(usize, usize), usize>,
+    revision: u64,
+    grid_mode: GridMode,
+    hex_layout: HexLayout,
+}
+
+impl NavMesh {
+    pub fn build(grid: &Grid, grid_mode: GridMode) -> Self {
+        let hex_layout = HexLayout::current();
+        let (polygons, cell_polygon) = match grid_mode {
+            GridMode::Hexagonal => hexagonal_polygons(grid, &hex_layout),
+            _ => rectangular_polygons(grid),
+        };
+
+        // A mesma posição (na resolução de SNAP) vira um vértice só, compartilhado pelos polígonos vizinhos
+        let mut vertices = Vec::new();
+        let mut vertex_keys = Vec::new();
+        let mut vertex_ids: HashMap<(i64, i64), usize> = HashMap::new();
+        // Triângulos por índice de vértice e o polígono de onde cada um saiu
+        let mut triangles: Vec<([usize; 3], usize)> = Vec::new();
+
+        for (polygon_index, polygon) in polygons.iter().enumerate() {
+            // Cantos calculados a partir de centros diferentes podem cair em lados opostos
+            // de um arredondamento: um vértice já existente ao lado também serve
+            let ids: Vec<usize> = polygon
+                .iter()
+                .map(|&p| {
+                    let (kx, ky) = snap(p);
+                    let existing = (-1..=1)
+                        .flat_map(|dx| (-1..=1).map(move |dy| (kx + dx, ky + dy)))
+                        .find_map(|key| vertex_ids.get(&key).copied());
+                    existing.unwrap_or_else(|| {
+                        vertex_ids.insert((kx, ky), vertices.len());
+                        vertex_keys.push((kx, ky));
+                        vertices.push(unsnap((kx, ky)));
+                        vertices.len() - 1
+                    })
+                })
+                .collect();
+            let fixed: Vec<(i64, i64)> = ids.iter().map(|&id| vertex_keys[id]).collect();
+
+            for [a, b, c] in bowyer_watson(&fixed) {
+                triangles.push(([ids[a], ids[b], ids[c]], polygon_index));
+            }
+        }
+
+        // Cada aresta aparece em um triângulo (parede) ou em dois (interna ou entre polígonos).
+        // Guarda o polígono e o vértice oposto de cada triângulo dono da aresta.
+        let mut edge_owners: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+        for (triangle, polygon) in &triangles {
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                edge_owners.entry((a.min(b), a.max(b))).or_default().push((*polygon, triangle[(i + 2) % 3]));
+            }
+        }
+        // Normal de um vértice de parede: soma das normais (para o lado livre) das paredes que chegam nele
+        let mut wall_normals: Vec<Option<Vec2>> = vec![None; vertices.len()];
+        let mut shared: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (&(a, b), owners) in &edge_owners {
+            match owners[..] {
+                [(_, opposite)] => {
+                    let along = vertices[b] - vertices[a];
+                    let mut normal = along.perp().normalize_or_zero();
+                    if normal.dot(vertices[opposite] - vertices[a]) < 0.0 {
+                        normal = -normal;
+                    }
+                    for v in [a, b] {
+                        *wall_normals[v].get_or_insert(Vec2::ZERO) += normal;
+                    }
+                }
+                [(p, _), (q, _)] if p != q => shared.entry((p.min(q), p.max(q))).or_default().extend([a, b]),
+                _ => {}
+            }
+        }
+        let wall_normals: Vec<Option<Vec2>> = wall_normals.into_iter().map(|n| n.map(|n| n.normalize_or_zero())).collect();
+
+        // Polígonos convexos vizinhos dividem um trecho reto só: as pontas são os vértices mais afastados
+        let mut portals = vec![Vec::new(); polygons.len()];
+        let mut pairs: Vec<_> = shared.into_iter().collect();
+        pairs.sort_unstable_by_key(|(pair, _)| *pair); // ordem fixa: mesma busca em toda execução
+        for ((p, q), ends) in pairs {
+            let (a, b) = farthest_pair(&ends, &vertices);
+            let (a_normal, b_normal) = (wall_normals[a], wall_normals[b]);
+            let (a, b) = (vertices[a], vertices[b]);
+            portals[p].push(Portal { neighbor: q, a, b, a_normal, b_normal });
+            portals[q].push(Portal { neighbor: p, a, b, a_normal, b_normal });
+        }
+        if grid_mode == GridMode::Diagonal {
+            for (p, q, corner) in corner_links(grid, &cell_polygon) {
+                let portal = |neighbor| Portal { neighbor, a: corner, b: corner, a_normal: None, b_normal: None };
+                portals[p].push(portal(q));
+                portals[q].push(portal(p));
+            }
+        }
+
+        Self {
+            polygons,
+            triangles: triangles.into_iter().map(|(t, _)| t.map(|v| vertices[v])).collect(),
+            portals,
+            cell_polygon,
+            revision: grid.revision(),
+            grid_mode,
+            hex_layout,
+        }
+    }
+
+    /// Ainda corresponde ao grid (nenhuma célula mudou desde a construção, nem o layout hexagonal)
+    pub fn is_current(&self, grid: &Grid, grid_mode: GridMode) -> bool {
+        self.revision == grid.revision() && self.grid_mode == grid_mode && self.hex_layout == HexLayout::current()
+    }
+
+    pub fn polygons(&self) -> &[Vec<Vec2>] {
+        &self.polygons
+    }
+
+    pub fn triangles(&self) -> &[[Vec2; 3]] {
+        &self.triangles
+    }
+
+    /// Polígono que contém o ponto (o da célula em que ele cai)
+    fn locate(&self, point: Vec2) -> Option<usize> {
+        let cell = screen_to_grid(point.x, point.y, self.grid_mode);
+        self.cell_polygon.get(&cell).copied()
+    }
+
+    /// Caminho em pixels de `start` até `goal` para um agente de raio `radius`: A* nos polígonos e o
+    /// funil nos portais. `None` quando um dos pontos está fora da malha ou não há ligação entre eles.
+    pub fn find_path(&self, start: Vec2, goal: Vec2, radius: f32) -> Option<Vec<Vec2>> {
+        let (from, to) = (self.locate(start)?, self.locate(goal)?);
+        let capture = DebugCapture::instance();
+        if capture.captures_search() {
+            let mut trace = MeshTrace::default();
+            let path = self.search(from, to, start, goal, radius, Some(&mut trace)).and_then(|corridor| self.smooth(&corridor, start, goal, radius));
+            let polygons = |ids: Vec<usize>| ids.into_iter().map(|p| SearchNode::Polygon(self.polygons[p].clone())).collect();
+            capture.record_search(SearchTrace {
+                expanded: polygons(trace.expanded),
+                open: polygons(trace.open),
+                path: path.clone().unwrap_or_default(),
+            });
+            return path;
+        }
+
+        let corridor = self.search(from, to, start, goal, radius, None)?;
+        self.smooth(&corridor, start, goal, radius)
+    }
+
+    /// Funil pelos portais do corredor de polígonos
+    fn smooth(&self, corridor: &[usize], start: Vec2, goal: Vec2, radius: f32) -> Option<Vec<Vec2>> {
+        let clearance = radius + PORTAL_MARGIN;
+
+        // Portais (esquerda, direita) vistos de dentro de cada polígono do corredor
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let portal = self.portals[pair[0]].iter().find(|p| p.neighbor == pair[1])?;
+            let inside = polygon_center(&self.polygons[pair[0]]);
+            let (left, right) = if (portal.a - inside).perp_dot(portal.b - inside) > 0.0 {
+                ((portal.b, portal.b_normal), (portal.a, portal.a_normal))
+            } else {
+                ((portal.a, portal.a_normal), (portal.b, portal.b_normal))
+            };
+            portals.push(narrow_portal(left, right, clearance));
+        }
+        portals.push((goal, goal));
+
+        Some(funnel(&portals))
+    }
+
+    /// A* com os polígonos como nós; cada nó fica no meio do portal por onde se entrou nele.
+    /// Portais entre duas paredes mais estreitos que o agente ficam de fora.
+    fn search(
+        &self,
+        from: usize,
+        to: usize,
+        start: Vec2,
+        goal: Vec2,
+        radius: f32,
+        mut trace: Option<&mut MeshTrace>,
+    ) -> Option<Vec<usize>> {
+        let mut g_costs = vec![f32::INFINITY; self.polygons.len()];
+        let mut entry = vec![start; self.polygons.len()];
+        let mut came_from: Vec<Option<usize>> = vec![None; self.polygons.len()];
+        let mut closed = HashSet::new();
+        let mut open_set = BinaryHeap::new();
+
+        g_costs[from] = 0.0;
+        open_set.push(MeshNode { polygon: from, f_cost: start.distance(goal) });
+
+        while let Some(current) = open_set.pop() {
+            let p = current.polygon;
+            if p == to {
+                if let Some(trace) = trace {
+                    trace.expanded.push(p);
+                    let mut open: Vec<_> = open_set.iter().map(|n| n.polygon).filter(|n| !closed.contains(n)).collect();
+                    open.sort_unstable();
+                    open.dedup();
+                    trace.open = open;
+                }
+                let mut corridor = vec![p];
+                while let Some(prev) = came_from[*corridor.last().unwrap()] {
+                    corridor.push(prev);
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+            if !closed.insert(p) {
+                continue;
+            }
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.expanded.push(p);
+            }
+
+            for portal in &self.portals[p] {
+                let next = portal.neighbor;
+                if closed.contains(&next) || !portal.fits(radius) {
+                    continue;
+                }
+                let point = if next == to { goal } else { (portal.a + portal.b) / 2.0 };
+                let new_g_cost = g_costs[p] + entry[p].distance(point);
+                if new_g_cost < g_costs[next] {
+                    g_costs[next] = new_g_cost;
+                    entry[next] = point;
+                    came_from[next] = Some(p);
+                    open_set.push(MeshNode { polygon: next, f_cost: new_g_cost + point.distance(goal) });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Afasta das paredes, por `clearance`, as pontas do portal que encostam nelas, para o agente não
+/// raspar nos cantos: ao longo do portal (sem passar do meio) e, quando o portal segue a linha de
+/// uma parede, também para o lado livre
+fn narrow_portal(left: (Vec2, Option<Vec2>), right: (Vec2, Option<Vec2>), clearance: f32) -> (Vec2, Vec2) {
+    let shrink = clearance.min(left.0.distance(right.0) / 2.0);
+    let direction = (right.0 - left.0).normalize_or_zero();
+    let pull = |(point, normal): (Vec2, Option<Vec2>), along: Vec2| match normal {
+        Some(normal) => {
+            let across = normal - along * normal.dot(along);
+            point + along * shrink + across * (clearance / across.length().max(0.5))
+        }
+        None => point,
+    };
+    (pull(left, direction), pull(right, -direction))
+}
+
+/// Os dois vértices mais afastados entre si (as pontas de um trecho reto)
+fn farthest_pair(ids: &[usize], vertices: &[Vec2]) -> (usize, usize) {
+    let mut best = (ids[0], ids[0]);
+    let mut best_distance = -1.0;
+    for (i, &a) in ids.iter().enumerate() {
+        for &b in &ids[i + 1..] {
+            let distance = vertices[a].distance_squared(vertices[b]);
+            if distance > best_distance {
+                best = (a, b);
+                best_distance = distance;
+            }
+        }
+    }
+    best
+}
+
+/// Média dos cantos: fica dentro de qualquer polígono convexo
+fn polygon_center(polygon: &[Vec2]) -> Vec2 {
+    polygon.iter().copied().sum::<Vec2>() / polygon.len() as f32
+}
+
+/// Polígonos que a busca visitou (camada de depuração do A*)
+#[derive(Default)]
+struct MeshTrace {
+    expanded: Vec<usize>,
+    open: Vec<usize>,
+}
+
+/// Nó da fila de prioridade do A* da malha (menor custo primeiro)
+#[derive(Copy, Clone, PartialEq)]
+struct MeshNode {
+    polygon: usize,
+    f_cost: f32,
+}
+
+impl Eq for MeshNode {}
+
+impl Ord for MeshNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f_cost
+            .total_cmp(&self.f_cost)
+            .then_with(|| self.polygon.cmp(&other.polygon))
+    }
+}
+
+impl PartialOrd for MeshNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn snap(p: Vec2) -> (i64, i64) {
+    ((p.x / SNAP).round() as i64, (p.y / SNAP).round() as i64)
+}
+
+fn unsnap(key: (i64, i64)) -> Vec2 {
+    vec2(key.0 as f32 * SNAP, key.1 as f32 * SNAP)
+}
+
+/// Grids retangulares: células livres agrupadas em retângulos (cada um cresce para a direita
+/// e depois para baixo enquanto der). Cada retângulo leva os cantos dos vizinhos que caem
+/// na sua borda, para que os triângulos dos dois lados tenham as mesmas arestas.
+fn rectangular_polygons(grid: &Grid) -> Polygons {
+    let mut cell_polygon = HashMap::new();
+    let mut rectangles = Vec::new();
+
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            if grid.is_obstacle(x, y) || cell_polygon.contains_key(&(x, y)) {
+                continue;
+            }
+            let free = |cx: usize, cy: usize| !grid.is_obstacle(cx, cy) && !cell_polygon.contains_key(&(cx, cy));
+            let mut x_end = x + 1;
+            while x_end < grid.width && free(x_end, y) {
+                x_end += 1;
+            }
+            let mut y_end = y + 1;
+            while y_end < grid.height && (x..x_end).all(|cx| free(cx, y_end)) {
+                y_end += 1;
+            }
+
+            for cy in y..y_end {
+                for cx in x..x_end {
+                    cell_polygon.insert((cx, cy), rectangles.len());
+                }
+            }
+            rectangles.push((x, y, x_end, y_end));
+        }
+    }
+
+    let corners: HashSet<(usize, usize)> = rectangles
+        .iter()
+        .flat_map(|&(x0, y0, x1, y1)| [(x0, y0), (x1, y0), (x1, y1), (x0, y1)])
+        .collect();
+
+    let polygons = rectangles
+        .iter()
+        .map(|&(x0, y0, x1, y1)| {
+            // Borda no sentido horário (na tela): topo, direita, base, esquerda
+            let mut boundary: Vec<(usize, usize)> = Vec::new();
+            boundary.extend((x0..x1).map(|x| (x, y0)));
+            boundary.extend((y0..y1).map(|y| (x1, y)));
+            boundary.extend((x0 + 1..=x1).rev().map(|x| (x, y1)));
+            boundary.extend((y0 + 1..=y1).rev().map(|y| (x0, y)));
+            boundary
+                .into_iter()
+                .filter(|p| corners.contains(p))
+                .map(|(x, y)| vec2(x as f32 * CELL_SIZE, y as f32 * CELL_SIZE))
+                .collect()
+        })
+        .collect();
+
+    (polygons, cell_polygon)
+}
+
+/// Modo diagonal: o A* das células passa entre duas células que só se tocam no canto (as duas
+/// vizinhas ortogonais bloqueadas), e os componentes também as juntam. Cada par de polígonos
+/// ligado assim ganha um portal de largura zero nesse canto: (polígono, polígono, canto em pixels).
+fn corner_links(grid: &Grid, cell_polygon: &HashMap<(usize, usize), usize>) -> Vec<(usize, usize, Vec2)> {
+    let mut links = Vec::new();
+    for y in 0..grid.height {
+        for x in 0..grid.width.saturating_sub(1) {
+            let Some(&p) = cell_polygon.get(&(x, y)) else { continue };
+            // Para baixo e para cima à direita: a outra célula, a vizinha ao lado e a linha do canto
+            let diagonals = [(y + 1 < grid.height).then_some((y + 1, y + 1)), (y > 0).then(|| (y - 1, y))];
+            for (other_y, corner_y) in diagonals.into_iter().flatten() {
+                if !grid.is_obstacle(x + 1, y) || !grid.is_obstacle(x, other_y) {
+                    continue;
+                }
+                if let Some(&q) = cell_polygon.get(&(x + 1, other_y)) {
+                    links.push((p, q, vec2((x + 1) as f32 * CELL_SIZE, corner_y as f32 * CELL_SIZE)));
+                }
+            }
+        }
+    }
+    links
+}
+
+/// Grids hexagonais: hexágonos vizinhos juntos não formam polígonos convexos,
+/// então cada célula livre é um polígono
+fn hexagonal_polygons(grid: &Grid, layout: &HexLayout) -> Polygons {
+    let mut polygons = Vec::new();
+    let mut cell_polygon = HashMap::new();
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            if grid.is_obstacle(x, y) {
+                continue;
+            }
+            cell_polygon.insert((x, y), polygons.len());
+            polygons.push(layout.corners(layout.cell_center((x, y))).to_vec());
+        }
+    }
+    (polygons, cell_polygon)
+}
+
+//==============================================================================
+// TRIANGULAÇÃO DE DELAUNAY (BOWYER-WATSON, ADAPTADO DO TRABALHO 2)
+//==============================================================================
+
+/// Orientação de (a, b, c): positivo, negativo ou zero (colineares)
+fn orient(a: (i64, i64), b: (i64, i64), c: (i64, i64)) -> i128 {
+    (b.0 - a.0) as i128 * (c.1 - a.1) as i128 - (b.1 - a.1) as i128 * (c.0 - a.0) as i128
+}
+
+/// `p` está estritamente dentro da circunferência circunscrita de (a, b, c).
+/// As contas são exatas (inteiros): pontos na circunferência, como os cantos de um
+/// retângulo ou de um hexágono, nunca mudam de lado por arredondamento.
+fn circumcircle_contains(a: (i64, i64), b: (i64, i64), c: (i64, i64), p: (i64, i64)) -> bool {
+    let (adx, ady) = ((a.0 - p.0) as i128, (a.1 - p.1) as i128);
+    let (bdx, bdy) = ((b.0 - p.0) as i128, (b.1 - p.1) as i128);
+    let (cdx, cdy) = ((c.0 - p.0) as i128, (c.1 - p.1) as i128);
+    let det = (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy) - (bdx * bdx + bdy * bdy) * (adx * cdy - cdx * ady)
+        + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady);
+    det * orient(a, b, c).signum() > 0
+}
+
+/// Triangulação de Delaunay dos pontos (índices em `points`).
+/// Para os pontos da borda de um polígono convexo, cobre exatamente o polígono.
+pub fn bowyer_watson(points: &[(i64, i64)]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // --- 1. Super triângulo bem maior que os pontos ---
+    let min = points.iter().fold((i64::MAX, i64::MAX), |m, p| (m.0.min(p.0), m.1.min(p.1)));
+    let max = points.iter().fold((i64::MIN, i64::MIN), |m, p| (m.0.max(p.0), m.1.max(p.1)));
+    let delta = (max.0 - min.0).max(max.1 - min.1).max(1) * 100;
+    let mid = ((min.0 + max.0) / 2, (min.1 + max.1) / 2);
+
+    let n = points.len();
+    let mut all = points.to_vec();
+    all.push((mid.0 - 2 * delta, mid.1 - delta));
+    all.push((mid.0, mid.1 + 2 * delta));
+    all.push((mid.0 + 2 * delta, mid.1 - delta));
+    let mut triangles = vec![[n, n + 1, n + 2]];
+
+    // --- Loop principal: insere um ponto de cada vez ---
+    for (i, &p) in points.iter().enumerate() {
+        // --- 2. Triângulos "ruins": a circunferência circunscrita contém o novo ponto ---
+        let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) =
+            triangles.into_iter().partition(|t| circumcircle_contains(all[t[0]], all[t[1]], all[t[2]], p));
+
+        // --- 3. Fronteira da cavidade: arestas que aparecem em um só triângulo ruim ---
+        let mut edges: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut shared = HashSet::new();
+        for t in &bad {
+            for k in 0..3 {
+                let (a, b) = (t[k], t[(k + 1) % 3]);
+                let key = (a.min(b), a.max(b));
+                if edges.insert(key, (a, b)).is_some() {
+                    shared.insert(key);
+                }
+            }
+        }
+
+        // --- 4. Retriangula a cavidade ligando o ponto às arestas da fronteira ---
+        triangles = good;
+        let mut boundary: Vec<_> = edges.into_iter().filter(|(key, _)| !shared.contains(key)).collect();
+        boundary.sort_unstable_by_key(|(key, _)| *key); // ordem fixa: mesma malha em toda execução
+        triangles.extend(boundary.into_iter().map(|(_, (a, b))| [a, b, i]));
+    }
+
+    // --- 5. Limpeza: sai tudo que usa o super triângulo (e triângulos sem área) ---
+    triangles
+        .into_iter()
+        .filter(|t| t.iter().all(|&v| v < n) && orient(all[t[0]], all[t[1]], all[t[2]]) != 0)
+        .collect()
+}
+
+//==============================================================================
+// ALGORITMO DO FUNIL ("SIMPLE STUPID FUNNEL ALGORITHM")
+//==============================================================================
+
+/// Caminho mais curto pelo corredor de portais (esquerda, direita).
+/// O primeiro portal é o ponto de partida repetido e o último, o destino.
+pub fn funnel(portals: &[(Vec2, Vec2)]) -> Vec<Vec2> {
+    let Some(&(start, _)) = portals.first() else { return Vec::new() };
+    let mut path = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (portal_left, portal_right) = portals[i];
+
+        // Tenta fechar o lado direito do funil (uma ponta no próprio ápice não restringe nada)
+        if portal_right != apex && (right - apex).perp_dot(portal_right - apex) >= 0.0 {
+            if apex == right || apex == left || (portal_right - apex).perp_dot(left - apex) > 0.0 {
+                right = portal_right;
+                right_index = i;
+            } else {
+                // Passou por cima do lado esquerdo: o canto esquerdo entra no caminho
+                path.push(left);
+                apex = left;
+                let apex_index = left_index;
+                (left, right) = (apex, apex);
+                (left_index, right_index) = (apex_index, apex_index);
+                i = apex_index + 1;
+                continue;
+            }
+        }
+
+        // Tenta fechar o lado esquerdo do funil
+        if portal_left != apex && (portal_left - apex).perp_dot(left - apex) >= 0.0 {
+            if apex == left || apex == right || (right - apex).perp_dot(portal_left - apex) > 0.0 {
+                left = portal_left;
+                left_index = i;
+            } else {
+                path.push(right);
+                apex = right;
+                let apex_index = right_index;
+                (left, right) = (apex, apex);
+                (left_index, right_index) = (apex_index, apex_index);
+                i = apex_index + 1;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    if let Some(&(goal, _)) = portals.last()
+        && path.last() != Some(&goal)
+    {
+        path.push(goal);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::CellType;
+
+    /// Grid só de obstáculos, com as células livres dadas
+    fn carve(width: usize, height: usize, free: impl Fn(usize, usize) -> bool) -> Grid {
+        let mut grid = Grid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if !free(x, y) {
+                    grid.set_cell(x, y, CellType::Obstacle);
+                }
+            }
+        }
+        grid
+    }
+
+    fn center(x: usize, y: usize) -> Vec2 {
+        vec2(x as f32 + 0.5, y as f32 + 0.5) * CELL_SIZE
+    }
+
+    #[test]
+    fn funnel_goes_straight_through_a_corridor() {
+        // Corredor de 3 células de altura; o nicho em cima da coluna 10 parte o corredor em três retângulos
+        let grid = carve(20, 5, |x, y| (1..19).contains(&x) && (1..4).contains(&y) || (x, y) == (10, 0));
+        let mesh = NavMesh::build(&grid, GridMode::Cardinal);
+        assert_eq!(mesh.polygons().len(), 3);
+
+        let (start, goal) = (center(1, 2), center(18, 2));
+        assert_eq!(mesh.find_path(start, goal, 8.0), Some(vec![start, goal]));
+    }
+
+    #[test]
+    fn funnel_turns_an_l_corridor_clear_of_the_inner_corner() {
+        // Braço horizontal (linhas 1-3) e vertical (colunas 8-10), os dois com 3 células de largura
+        let grid = carve(12, 12, |x, y| (1..11).contains(&x) && (1..4).contains(&y) || (8..11).contains(&x) && (1..11).contains(&y));
+        let mesh = NavMesh::build(&grid, GridMode::Cardinal);
+        let inner_corner = vec2(8.0, 4.0) * CELL_SIZE;
+
+        let (start, goal) = (center(1, 2), center(9, 10));
+        for radius in [8.0, 12.0] {
+            let path = mesh.find_path(start, goal, radius).expect("caminho pelo L");
+            assert_eq!(path.len(), 3, "{:?}", path);
+            assert_eq!((path[0], path[2]), (start, goal));
+            // A curva fica do lado livre do canto, a pelo menos um raio das duas paredes
+            assert!(path[1].x - inner_corner.x >= radius, "{:?}", path);
+            assert!(inner_corner.y - path[1].y >= radius, "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn diagonal_mode_links_polygons_touching_at_a_corner() {
+        // Dois blocos de 2x2 que só se tocam no canto (40, 40)
+        let grid = carve(4, 4, |x, y| (x < 2 && y < 2) || (x >= 2 && y >= 2));
+        let (start, goal) = (center(0, 0), center(3, 3));
+        assert_eq!(NavMesh::build(&grid, GridMode::Cardinal).find_path(start, goal, 8.0), None);
+
+        let path = NavMesh::build(&grid, GridMode::Diagonal).find_path(start, goal, 8.0).expect("caminho pelo canto");
+        assert_eq!(path, vec![start, vec2(40.0, 40.0), goal]);
+    }
+
+    #[test]
+    fn portals_narrower_than_the_agent_are_rejected() {
+        // Duas salas separadas pela coluna 5, com uma porta de uma célula (20 px) na linha 2
+        let grid = carve(11, 5, |x, y| x != 5 || y == 2);
+        let mesh = NavMesh::build(&grid, GridMode::Cardinal);
+        let (start, goal) = (center(1, 2), center(9, 2));
+        assert!(mesh.find_path(start, goal, 8.0).is_some());
+        assert_eq!(mesh.find_path(start, goal, 12.0), None);
+    }
+
+    #[test]
+    fn funnel_paths_stay_inside_the_mesh() {
+        // Obstáculos espalhados em um padrão fixo
+        let (width, height) = (24, 16);
+        let grid = carve(width, height, |x, y| (x * 7 + y * 13) % 5 != 0 || x == 0 || y == 0);
+        let mesh = NavMesh::build(&grid, GridMode::Cardinal);
+        let free: Vec<(usize, usize)> =
+            (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).filter(|&(x, y)| !grid.is_obstacle(x, y)).collect();
+
+        let mut found = 0;
+        for (i, &from) in free.iter().enumerate().step_by(7) {
+            let to = free[(i * 31 + 17) % free.len()];
+            let Some(path) = mesh.find_path(center(from.0, from.1), center(to.0, to.1), 8.0) else { continue };
+            found += 1;
+            for segment in path.windows(2) {
+                let steps = (segment[0].distance(segment[1]) / 2.0).ceil().max(1.0) as usize;
+                for k in 0..=steps {
+                    let point = segment[0].lerp(segment[1], k as f32 / steps as f32);
+                    let (x, y) = screen_to_grid(point.x, point.y, GridMode::Cardinal);
+                    assert!(!grid.is_obstacle(x, y), "{:?} sai da malha em {:?}", path, point);
+                }
+            }
+        }
+        assert!(found > 10, "só {} caminhos", found);
+    }
+
+    #[test]
+    fn bowyer_watson_splits_a_convex_polygon_with_collinear_points() {
+        // Retângulo 4x4 com pontos no meio de duas arestas: 6 pontos, 4 triângulos
+        let points = [(0, 0), (2, 0), (4, 0), (4, 2), (4, 4), (0, 4)];
+        let triangles = bowyer_watson(&points);
+        assert_eq!(triangles.len(), points.len() - 2);
+        let doubled_area: i128 = triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let area = orient(points[a], points[b], points[c]).abs();
+                assert!(area > 0, "triângulo degenerado {:?}", [a, b, c]);
+                area
+            })
+            .sum();
+        assert_eq!(doubled_area, 2 * 16);
+    }
+}
@@ -1,7 +1,11 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 /// Eventos que podem ocorrer com um agente
 #[derive(Debug, Clone)]
 pub enum AgentEvent {
     OutOfFuel,             // O agente ficou sem energia
+    LowEnergy,             // A energia caiu abaixo do limite de aviso
+    Recharged,             // O tanque encheu de novo em uma estação
     Finished,              // O agente chegou ao destino
     ProximityAlert(usize), // Sensor detectou algo (Antes de bater)
     CollisionHit(usize),   // Colisão física (Durante a batida)
@@ -12,6 +16,15 @@ pub trait Observer {
     fn on_notify(&self, agent_id: usize, event: AgentEvent);
 }
 
+/// Logs de sensor e de colisão, um por par que entra em alcance (ligados na janela)
+static PAIR_LOGS: AtomicBool = AtomicBool::new(true);
+
+/// Liga/desliga os logs por par: com milhares de agentes seriam milhares de linhas por passo,
+/// então os modos headless desligam (os contatos vão para o CSV)
+pub fn set_pair_logs(enabled: bool) {
+    PAIR_LOGS.store(enabled, Ordering::Relaxed);
+}
+
 /// Um Observer Concreto que gerencia o Respawn e Logs de Colisão
 pub struct RespawnHandler;
 
@@ -24,16 +37,28 @@ impl Observer for RespawnHandler {
                     agent_id
                 );
             }
+            AgentEvent::LowEnergy => {
+                println!("[OBSERVER] Agente {} está com pouca energia.", agent_id);
+            }
+            AgentEvent::Recharged => {
+                println!("[OBSERVER] Agente {} recarregou e seguiu viagem.", agent_id);
+            }
             AgentEvent::Finished => {
                 println!("[OBSERVER] Agente {} chegou ao destino.", agent_id);
             }
             AgentEvent::ProximityAlert(other_id) => {
+                if !PAIR_LOGS.load(Ordering::Relaxed) {
+                    return;
+                }
                 println!(
                     "[SENSOR] Agente {} detectou risco de colisão com Agente {}",
                     agent_id, other_id
                 );
             }
             AgentEvent::CollisionHit(other_id) => {
+                if !PAIR_LOGS.load(Ordering::Relaxed) {
+                    return;
+                }
                 println!(
                     "[COLISÃO] Agente {} colidiu fisicamente com Agente {}",
                     agent_id, other_id
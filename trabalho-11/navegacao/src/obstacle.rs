@@ -0,0 +1,217 @@
+use crate::grid::Grid;
+use crate::hex::HexLayout;
+use crate::{CELL_SIZE, GridMode};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+/// Lado (px) dos baldes do índice espacial das arestas
+const BUCKET_SIZE: f32 = 64.0;
+/// Cruzamentos a menos disto (px) de um vértice contam como passar pelo vértice
+const VERTEX_TOLERANCE: f32 = 1e-3;
+
+/// Aresta entre uma célula livre e um obstáculo (ou a borda do mapa), em pixels.
+/// `normal` aponta para o lado livre: atravessar a aresta contra a normal é entrar no obstáculo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObstacleEdge {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub normal: Vec2,
+}
+
+impl ObstacleEdge {
+    /// Menor distância de `point` até a aresta
+    pub fn distance_to(&self, point: Vec2) -> f32 {
+        let segment = self.b - self.a;
+        let t = ((point - self.a).dot(segment) / segment.length_squared()).clamp(0.0, 1.0);
+        point.distance(self.a + segment * t)
+    }
+
+    /// Menor distância entre o segmento `from`-`to` e a aresta
+    pub fn distance_to_segment(&self, from: Vec2, to: Vec2) -> f32 {
+        if self.crosses(from, to) {
+            return 0.0;
+        }
+        let movement = ObstacleEdge { a: from, b: to, normal: Vec2::ZERO };
+        let ends = [self.distance_to(from), self.distance_to(to)];
+        let own_ends = if from == to {
+            ends[0]
+        } else {
+            movement.distance_to(self.a).min(movement.distance_to(self.b))
+        };
+        ends[0].min(ends[1]).min(own_ends)
+    }
+
+    /// O segmento `from`-`to` cruza o interior da aresta. Passar exatamente por um vértice
+    /// não conta: é o que fazem os caminhos diagonais do A* entre dois obstáculos.
+    fn crosses(&self, from: Vec2, to: Vec2) -> bool {
+        let r = to - from;
+        let s = self.b - self.a;
+        let denominator = r.perp_dot(s);
+        if denominator.abs() < 1e-6 {
+            return false;
+        }
+        let t = (self.a - from).perp_dot(s) / denominator;
+        // Ponto do cruzamento ao longo da aresta, em pixels a partir de `a`
+        let along = (self.a - from).perp_dot(r) / denominator * s.length();
+        (0.0..=1.0).contains(&t) && along > VERTEX_TOLERANCE && along < s.length() - VERTEX_TOLERANCE
+    }
+
+    /// Andar de `from` até `to` leva o centro para dentro do obstáculo
+    pub fn enters(&self, from: Vec2, to: Vec2) -> bool {
+        (to - from).dot(self.normal) < 0.0 && (from - self.a).dot(self.normal) >= 0.0 && self.crosses(from, to)
+    }
+}
+
+/// Contorno dos obstáculos do grid, com um índice espacial por baldes.
+/// Fica guardado na Strategy e só é refeito quando o grid ou a vizinhança mudam.
+pub struct ObstacleMap {
+    edges: Vec<ObstacleEdge>,
+    buckets: HashMap<(i32, i32), Vec<usize>>,
+    revision: u64,
+    grid_mode: GridMode,
+    hex_layout: HexLayout,
+}
+
+impl ObstacleMap {
+    pub fn build(grid: &Grid, grid_mode: GridMode) -> Self {
+        let hex_layout = HexLayout::current();
+        let edges = match grid_mode {
+            GridMode::Hexagonal => hexagonal_edges(grid, &hex_layout),
+            _ => rectangular_edges(grid),
+        };
+
+        let mut buckets: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        for (i, edge) in edges.iter().enumerate() {
+            let (min, max) = (bucket_of(edge.a.min(edge.b)), bucket_of(edge.a.max(edge.b)));
+            for bx in min.0..=max.0 {
+                for by in min.1..=max.1 {
+                    buckets.entry((bx, by)).or_default().push(i);
+                }
+            }
+        }
+
+        Self { edges, buckets, revision: grid.revision(), grid_mode, hex_layout }
+    }
+
+    /// Ainda corresponde ao grid (nenhuma célula mudou desde a construção, nem o layout hexagonal)
+    pub fn is_current(&self, grid: &Grid, grid_mode: GridMode) -> bool {
+        self.revision == grid.revision() && self.grid_mode == grid_mode && self.hex_layout == HexLayout::current()
+    }
+
+    /// Arestas que podem estar a menos de `range` pixels de `pos`
+    pub fn edges_near(&self, pos: Vec2, range: f32) -> Vec<ObstacleEdge> {
+        let (min, max) = (bucket_of(pos - Vec2::splat(range)), bucket_of(pos + Vec2::splat(range)));
+        let mut indices: Vec<usize> = Vec::new();
+        for bx in min.0..=max.0 {
+            for by in min.1..=max.1 {
+                if let Some(bucket) = self.buckets.get(&(bx, by)) {
+                    indices.extend(bucket);
+                }
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|i| self.edges[i]).filter(|e| e.distance_to(pos) <= range).collect()
+    }
+}
+
+fn bucket_of(point: Vec2) -> (i32, i32) {
+    ((point.x / BUCKET_SIZE).floor() as i32, (point.y / BUCKET_SIZE).floor() as i32)
+}
+
+/// Fora do grid conta como obstáculo: a borda do mapa também vira parede
+fn blocked(grid: &Grid, x: i64, y: i64) -> bool {
+    x < 0 || y < 0 || grid.is_obstacle(x as usize, y as usize)
+}
+
+/// Grids retangulares: arestas entre células vizinhas com um lado bloqueado e o outro livre.
+/// Arestas seguidas na mesma linha e com o mesmo lado livre viram uma só.
+fn rectangular_edges(grid: &Grid) -> Vec<ObstacleEdge> {
+    let (width, height) = (grid.width as i64, grid.height as i64);
+    let mut edges = Vec::new();
+
+    // Linhas horizontais: entre a célula (x, y - 1) e (x, y)
+    for y in 0..=height {
+        let mut run: Option<(i64, Vec2)> = None;
+        for x in 0..=width {
+            let side = if x < width {
+                match (blocked(grid, x, y - 1), blocked(grid, x, y)) {
+                    (true, false) => Some(vec2(0.0, 1.0)),
+                    (false, true) => Some(vec2(0.0, -1.0)),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            if let Some((start, normal)) = run
+                && side != Some(normal)
+            {
+                let line = y as f32 * CELL_SIZE;
+                edges.push(ObstacleEdge {
+                    a: vec2(start as f32 * CELL_SIZE, line),
+                    b: vec2(x as f32 * CELL_SIZE, line),
+                    normal,
+                });
+                run = None;
+            }
+            if run.is_none() {
+                run = side.map(|normal| (x, normal));
+            }
+        }
+    }
+
+    // Linhas verticais: entre a célula (x - 1, y) e (x, y)
+    for x in 0..=width {
+        let mut run: Option<(i64, Vec2)> = None;
+        for y in 0..=height {
+            let side = if y < height {
+                match (blocked(grid, x - 1, y), blocked(grid, x, y)) {
+                    (true, false) => Some(vec2(1.0, 0.0)),
+                    (false, true) => Some(vec2(-1.0, 0.0)),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            if let Some((start, normal)) = run
+                && side != Some(normal)
+            {
+                let line = x as f32 * CELL_SIZE;
+                edges.push(ObstacleEdge {
+                    a: vec2(line, start as f32 * CELL_SIZE),
+                    b: vec2(line, y as f32 * CELL_SIZE),
+                    normal,
+                });
+                run = None;
+            }
+            if run.is_none() {
+                run = side.map(|normal| (y, normal));
+            }
+        }
+    }
+    edges
+}
+
+/// Grids hexagonais: cada lado de uma célula livre cujo vizinho (o hexágono refletido
+/// pelo meio do lado) é obstáculo ou está fora do grid
+fn hexagonal_edges(grid: &Grid, layout: &HexLayout) -> Vec<ObstacleEdge> {
+    let mut edges = Vec::new();
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            if grid.is_obstacle(x, y) {
+                continue;
+            }
+            let center = layout.cell_center((x, y));
+            let corners = layout.corners(center);
+            for i in 0..6 {
+                let (a, b) = (corners[i], corners[(i + 1) % 6]);
+                let middle = (a + b) / 2.0;
+                let neighbor = layout.pixel_to_hex(center + (middle - center) * 2.0).to_cell(layout.orientation, grid.width, grid.height);
+                if neighbor.is_none_or(|(nx, ny)| grid.is_obstacle(nx, ny)) {
+                    edges.push(ObstacleEdge { a, b, normal: (center - middle).normalize() });
+                }
+            }
+        }
+    }
+    edges
+}
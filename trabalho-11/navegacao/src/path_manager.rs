@@ -0,0 +1,120 @@
+use crate::GridMode;
+use crate::grid::Grid;
+use crate::navmesh::{NavMesh, PathPlanner};
+use macroquad::prelude::Vec2;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Chave do cache e consulta de caminho: (início, fim)
+pub type PathKey = ((usize, usize), (usize, usize));
+
+/// Pedaços do cache, cada um com o seu Mutex: buscas em paralelo quase nunca disputam o mesmo
+const CACHE_SHARDS: usize = 16;
+
+type CacheShard = Mutex<HashMap<PathKey, Vec<(usize, usize)>>>;
+
+/// Gerenciador Singleton que mantém cache de caminhos calculados.
+/// Garante que apenas uma instância exista durante toda a execução.
+pub struct PathManager {
+    /// Cache de caminhos: key = (start, end), value = caminho calculado.
+    /// Dividido em pedaços pelo hash da chave para o planejamento em lote não ficar em fila num Mutex só.
+    cache: Vec<CacheShard>,
+    /// Planejador usado nas rotas dos agentes
+    planner: Mutex<PathPlanner>,
+    /// Malha de navegação do último grid consultado (refeita quando o grid muda).
+    /// As consultas usam uma cópia do `Arc`, sem segurar o Mutex durante a busca.
+    navmesh: Mutex<Option<Arc<NavMesh>>>,
+}
+
+impl PathManager {
+    /// Retorna a instância única do PathManager (Singleton)
+    pub fn instance() -> &'static PathManager {
+        static INSTANCE: OnceLock<PathManager> = OnceLock::new();
+        INSTANCE.get_or_init(|| PathManager {
+            cache: (0..CACHE_SHARDS).map(|_| Mutex::new(HashMap::new())).collect(),
+            planner: Mutex::new(PathPlanner::Grid),
+            navmesh: Mutex::new(None),
+        })
+    }
+
+    /// Busca um caminho no cache ou calcula se necessário
+    pub fn get_or_calculate<F>(
+        &self,
+        start: (usize, usize),
+        end: (usize, usize),
+        calculator: F,
+    ) -> Option<Vec<(usize, usize)>>
+    where
+        F: FnOnce() -> Option<Vec<(usize, usize)>>,
+    {
+        let key = (start, end);
+        let shard = self.shard(&key);
+
+        // Tenta buscar no cache primeiro
+        {
+            let cache = shard.lock().unwrap();
+            if let Some(path) = cache.get(&key) {
+                return Some(path.clone());
+            }
+        }
+
+        // Cache miss - calcula o caminho (fora do Mutex: outras buscas continuam enquanto isso)
+        if let Some(path) = calculator() {
+            // Armazena no cache
+            let mut cache = shard.lock().unwrap();
+            cache.insert(key, path.clone());
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Remove do cache só os caminhos que passam por alguma das células.
+    /// Serve quando células viram obstáculo: os outros caminhos continuam válidos e ótimos.
+    /// Quando um obstáculo é removido qualquer caminho pode ficar mais curto, então use `clear_cache`.
+    pub fn invalidate_cells(&self, cells: &[(usize, usize)]) {
+        for shard in &self.cache {
+            shard.lock().unwrap().retain(|_, path| !path.iter().any(|p| cells.contains(p)));
+        }
+    }
+
+    /// Limpa o cache (útil quando o grid é modificado)
+    pub fn clear_cache(&self) {
+        for shard in &self.cache {
+            shard.lock().unwrap().clear();
+        }
+    }
+
+    fn shard(&self, key: &PathKey) -> &CacheShard {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.cache[hasher.finish() as usize % CACHE_SHARDS]
+    }
+
+    pub fn planner(&self) -> PathPlanner {
+        *self.planner.lock().unwrap()
+    }
+
+    pub fn set_planner(&self, planner: PathPlanner) {
+        *self.planner.lock().unwrap() = planner;
+    }
+
+    /// Usa a malha de navegação do grid, construindo-a se o grid mudou desde a última
+    pub fn with_navmesh<R>(&self, grid: &Grid, grid_mode: GridMode, f: impl FnOnce(&NavMesh) -> R) -> R {
+        let mesh = {
+            let mut navmesh = self.navmesh.lock().unwrap();
+            if !navmesh.as_ref().is_some_and(|mesh| mesh.is_current(grid, grid_mode)) {
+                *navmesh = Some(Arc::new(NavMesh::build(grid, grid_mode)));
+            }
+            Arc::clone(navmesh.as_ref().expect("malha de navegação"))
+        };
+        f(&mesh)
+    }
+
+    /// Caminho em pixels pela malha de navegação, com folga para um agente de raio `radius`
+    pub fn navmesh_path(&self, grid: &Grid, grid_mode: GridMode, start: Vec2, goal: Vec2, radius: f32) -> Option<Vec<Vec2>> {
+        self.with_navmesh(grid, grid_mode, |mesh| mesh.find_path(start, goal, radius))
+    }
+}
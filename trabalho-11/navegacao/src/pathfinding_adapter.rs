@@ -0,0 +1,341 @@
+use crate::grid::{CellIndex, Grid};
+use crate::grid_adapter::GridAdapter;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+
+/// Estrutura que representa um Nó usado pelo A* (e pelo Dijkstra) na fila de prioridade.
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct Node {
+    pos: (usize, usize),
+    f_cost: usize,
+    g_cost: usize,
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f_cost
+            .cmp(&self.f_cost)
+            .then_with(|| self.pos.cmp(&other.pos))
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Custo e predecessor de cada célula vista pela busca, em tabelas do tamanho do grid indexadas por
+/// `CellIndex` em vez de mapas de hash. As tabelas nascem zeradas (0 = ainda não vista; os valores
+/// guardados são custo + 1 e índice + 1): o sistema só entrega as páginas das células que a busca toca.
+struct SearchTable {
+    width: usize,
+    height: usize,
+    g_costs: Vec<usize>,
+    came_from: Vec<usize>,
+}
+
+impl SearchTable {
+    fn new(grid: &Grid) -> Self {
+        Self {
+            width: grid.width,
+            height: grid.height,
+            g_costs: vec![0; grid.cell_count()],
+            came_from: vec![0; grid.cell_count()],
+        }
+    }
+
+    fn index(&self, pos: (usize, usize)) -> Option<CellIndex> {
+        (pos.0 < self.width && pos.1 < self.height).then_some(CellIndex(pos.1 * self.width + pos.0))
+    }
+
+    /// Menor custo conhecido até a célula
+    fn g_cost(&self, pos: (usize, usize)) -> Option<usize> {
+        self.index(pos).and_then(|index| self.g_costs[index.0].checked_sub(1))
+    }
+
+    fn set(&mut self, pos: (usize, usize), g_cost: usize, from: Option<(usize, usize)>) {
+        if let Some(index) = self.index(pos) {
+            self.g_costs[index.0] = g_cost + 1;
+            self.came_from[index.0] = from.and_then(|from| self.index(from)).map_or(0, |from| from.0 + 1);
+        }
+    }
+
+    /// Reconstrói o caminho final seguindo os predecessores até o início.
+    fn path_to(&self, mut current: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut path = vec![current];
+        while let Some(previous) = self.index(current).and_then(|index| self.came_from[index.0].checked_sub(1)) {
+            current = (previous % self.width, previous / self.width);
+            path.push(current);
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Algoritmos que o `SearchStepper` sabe rodar sobre qualquer `GridAdapter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchAlgorithm {
+    /// Fila de prioridade por custo + heurística do adapter
+    AStar,
+    /// Mesma fila, sem heurística
+    Dijkstra,
+    /// Fila FIFO: conta passos e ignora o custo de movimento
+    Bfs,
+}
+
+impl SearchAlgorithm {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchAlgorithm::AStar => "A*",
+            SearchAlgorithm::Dijkstra => "Dijkstra",
+            SearchAlgorithm::Bfs => "BFS",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            SearchAlgorithm::AStar => SearchAlgorithm::Dijkstra,
+            SearchAlgorithm::Dijkstra => SearchAlgorithm::Bfs,
+            SearchAlgorithm::Bfs => SearchAlgorithm::AStar,
+        }
+    }
+}
+
+/// O que a busca fez em um passo. `g` é o custo acumulado desde o início e `f` a prioridade na fronteira.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchEvent {
+    /// Célula tirada da fronteira para ser expandida
+    Pop { cell: (usize, usize), g: usize },
+    /// Vizinho visto pela primeira vez: entrou na fronteira
+    Push { cell: (usize, usize), from: (usize, usize), g: usize, f: usize },
+    /// Vizinho já visto, mas agora por um caminho mais barato: volta à fronteira com o custo menor
+    Relax { cell: (usize, usize), from: (usize, usize), g: usize, f: usize },
+    /// O destino saiu da fronteira: caminho do início ao destino
+    Found { path: Vec<(usize, usize)> },
+    /// A fronteira acabou sem chegar ao destino
+    Exhausted,
+}
+
+enum Frontier {
+    Heap(BinaryHeap<Node>),
+    Queue(VecDeque<(usize, usize)>),
+}
+
+/// Busca que pode ser pausada e retomada: cada chamada de `next_event` avança só até o próximo evento.
+/// O stepper não guarda o adapter (o grid da janela muda entre um quadro e outro); quem chama passa
+/// o adapter a cada passo, ou usa `events` para percorrer os eventos como um `Iterator`.
+pub struct SearchStepper {
+    algorithm: SearchAlgorithm,
+    end: (usize, usize),
+    frontier: Frontier,
+    table: SearchTable,
+    /// Eventos da expansão em andamento que ainda não foram entregues
+    pending: VecDeque<SearchEvent>,
+    /// Sem ninguém olhando os eventos (`run`), as expansões não os guardam
+    quiet: bool,
+    finished: bool,
+}
+
+impl SearchStepper {
+    pub fn new(algorithm: SearchAlgorithm, adapter: &dyn GridAdapter, start: (usize, usize), end: (usize, usize)) -> Self {
+        let mut table = SearchTable::new(adapter.grid());
+        let frontier = match algorithm {
+            SearchAlgorithm::Bfs => Frontier::Queue(VecDeque::from([start])),
+            _ => {
+                let f_cost = if algorithm == SearchAlgorithm::AStar { adapter.heuristic(start, end) } else { 0 };
+                Frontier::Heap(BinaryHeap::from([Node { pos: start, f_cost, g_cost: 0 }]))
+            }
+        };
+        table.set(start, 0, None);
+
+        let mut stepper = Self {
+            algorithm,
+            end,
+            frontier,
+            table,
+            pending: VecDeque::new(),
+            quiet: false,
+            finished: false,
+        };
+        // Início ou destino fora do grid (ou em obstáculo): a busca termina no primeiro evento
+        if !adapter.is_valid_position(start) || !adapter.is_valid_position(end) {
+            stepper.finish(SearchEvent::Exhausted);
+        }
+        stepper
+    }
+
+    /// Células na fronteira agora (podem se repetir na fila de prioridade)
+    pub fn frontier(&self) -> Vec<(usize, usize)> {
+        match &self.frontier {
+            Frontier::Heap(heap) => heap.iter().map(|n| n.pos).collect(),
+            Frontier::Queue(queue) => queue.iter().copied().collect(),
+        }
+    }
+
+    /// Percorre os eventos que faltam como um `Iterator` (pode parar no meio e continuar depois)
+    pub fn events<'a>(&'a mut self, adapter: &'a dyn GridAdapter) -> SearchEvents<'a> {
+        SearchEvents { stepper: self, adapter }
+    }
+
+    pub fn next_event(&mut self, adapter: &dyn GridAdapter) -> Option<SearchEvent> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+        if self.finished {
+            return None;
+        }
+
+        let Some((pos, g_cost)) = self.pop() else {
+            self.finish(SearchEvent::Exhausted);
+            return self.pending.pop_front();
+        };
+        if pos == self.end {
+            self.finish(SearchEvent::Found { path: self.table.path_to(pos) });
+            return self.pending.pop_front();
+        }
+        self.pending.push_back(SearchEvent::Pop { cell: pos, g: g_cost });
+        self.expand(adapter, pos, g_cost);
+        self.pending.pop_front()
+    }
+
+    /// Roda a busca até o fim sem entregar os eventos (o caminho das rotas, sem o custo de guardá-los)
+    pub fn run(mut self, adapter: &dyn GridAdapter) -> Option<Vec<(usize, usize)>> {
+        self.quiet = true;
+        while !self.finished {
+            let (pos, g_cost) = self.pop()?;
+            if pos == self.end {
+                return Some(self.table.path_to(pos));
+            }
+            self.expand(adapter, pos, g_cost);
+        }
+        None
+    }
+
+    fn finish(&mut self, event: SearchEvent) {
+        self.pending.push_back(event);
+        self.finished = true;
+    }
+
+    /// Próxima célula a expandir. Na fila de prioridade, as entradas que ficaram velhas
+    /// (a célula já saiu por um custo menor) são descartadas sem virar evento.
+    fn pop(&mut self) -> Option<((usize, usize), usize)> {
+        match &mut self.frontier {
+            Frontier::Heap(heap) => {
+                while let Some(node) = heap.pop() {
+                    if self.table.g_cost(node.pos).is_some_and(|g| node.g_cost <= g) {
+                        return Some((node.pos, node.g_cost));
+                    }
+                }
+                None
+            }
+            Frontier::Queue(queue) => queue.pop_front().map(|pos| (pos, self.table.g_cost(pos).unwrap_or(0))),
+        }
+    }
+
+    fn expand(&mut self, adapter: &dyn GridAdapter, pos: (usize, usize), g_cost: usize) {
+        // USA O ADAPTER para obter os vizinhos
+        // O algoritmo não sabe se está trabalhando com grid retangular ou hexagonal.
+        for neighbor in adapter.get_neighbors(pos) {
+            // USA O ADAPTER para calcular o custo de movimento (a BFS conta passos)
+            let new_g_cost = match self.algorithm {
+                SearchAlgorithm::Bfs => g_cost + 1,
+                _ => g_cost + adapter.movement_cost(pos, neighbor),
+            };
+            let known = self.table.g_cost(neighbor);
+            let improves = match self.algorithm {
+                // Na BFS a primeira vez que a célula é vista já é pelo menor número de passos
+                SearchAlgorithm::Bfs => known.is_none(),
+                _ => known.is_none_or(|g| new_g_cost < g),
+            };
+            if !improves {
+                continue;
+            }
+
+            let f_cost = match self.algorithm {
+                SearchAlgorithm::AStar => new_g_cost + adapter.heuristic(neighbor, self.end),
+                _ => new_g_cost,
+            };
+            self.table.set(neighbor, new_g_cost, Some(pos));
+            match &mut self.frontier {
+                Frontier::Heap(heap) => heap.push(Node { pos: neighbor, f_cost, g_cost: new_g_cost }),
+                Frontier::Queue(queue) => queue.push_back(neighbor),
+            }
+            if self.quiet {
+                continue;
+            }
+            self.pending.push_back(match known {
+                None => SearchEvent::Push { cell: neighbor, from: pos, g: new_g_cost, f: f_cost },
+                Some(_) => SearchEvent::Relax { cell: neighbor, from: pos, g: new_g_cost, f: f_cost },
+            });
+        }
+    }
+}
+
+/// Os eventos de um `SearchStepper` como `Iterator`, com o adapter do grid atual
+pub struct SearchEvents<'a> {
+    stepper: &'a mut SearchStepper,
+    adapter: &'a dyn GridAdapter,
+}
+
+impl Iterator for SearchEvents<'_> {
+    type Item = SearchEvent;
+
+    fn next(&mut self) -> Option<SearchEvent> {
+        self.stepper.next_event(self.adapter)
+    }
+}
+
+/// Células que uma busca visitou (camada de depuração do A*)
+#[derive(Default)]
+pub struct CellTrace {
+    /// Células expandidas, na ordem (cada uma uma vez)
+    pub expanded: Vec<(usize, usize)>,
+    /// Células que ficaram na fronteira quando a busca terminou
+    pub open: Vec<(usize, usize)>,
+}
+
+/// A* Search que usa o GridAdapter para ser agnóstico ao tipo de grid.
+/// Funciona com qualquer implementação de GridAdapter (retangular, hexagonal, etc.)
+pub fn a_star_with_adapter(
+    adapter: &dyn GridAdapter,
+    start: (usize, usize),
+    end: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    SearchStepper::new(SearchAlgorithm::AStar, adapter, start, end).run(adapter)
+}
+
+/// Mesmo A*, guardando também a ordem de expansão e a fronteira final
+pub fn a_star_traced(
+    adapter: &dyn GridAdapter,
+    start: (usize, usize),
+    end: (usize, usize),
+) -> (Option<Vec<(usize, usize)>>, CellTrace) {
+    let mut trace = CellTrace::default();
+    let mut stepper = SearchStepper::new(SearchAlgorithm::AStar, adapter, start, end);
+    // Com heurística inconsistente a mesma célula pode ser expandida de novo
+    let mut expanded = HashSet::new();
+
+    while let Some(event) = stepper.next_event(adapter) {
+        match event {
+            SearchEvent::Pop { cell, .. } => {
+                if expanded.insert(cell) {
+                    trace.expanded.push(cell);
+                }
+            }
+            SearchEvent::Found { path } => {
+                expanded.insert(end);
+                trace.expanded.push(end);
+                let mut open: Vec<_> = stepper.frontier().into_iter().filter(|p| !expanded.contains(p)).collect();
+                open.sort_unstable();
+                open.dedup();
+                trace.open = open;
+                return (Some(path), trace);
+            }
+            SearchEvent::Exhausted => break,
+            SearchEvent::Push { .. } | SearchEvent::Relax { .. } => {}
+        }
+    }
+    (None, trace)
+}
@@ -0,0 +1,143 @@
+use crate::hex::HexLayout;
+use macroquad::prelude::*;
+use std::sync::{Mutex, OnceLock};
+
+/// Decaimento do rastro
+const DECAY_RATE: f32 = 5.0; 
+/// Emissão para marcar a célula como ocupada
+const AGENT_EMISSION: f32 = 100.0; 
+/// Limiar de perigo
+const DANGER_THRESHOLD: f32 = 0.5; 
+/// Teto máximo
+const MAX_INTENSITY: f32 = 10.0;
+
+/// Gerenciador Singleton de Feromônios
+pub struct PheromoneManager {
+    grid: Mutex<Vec<Vec<f32>>>,
+    /// Duração do passo atual, usada na emissão (definida em `update`)
+    step_dt: Mutex<f32>,
+}
+
+impl PheromoneManager {
+    pub fn instance() -> &'static PheromoneManager {
+        static INSTANCE: OnceLock<PheromoneManager> = OnceLock::new();
+        INSTANCE.get_or_init(|| PheromoneManager {
+            grid: Mutex::new(Vec::new()),
+            step_dt: Mutex::new(0.0),
+        })
+    }
+
+    /// Inicializa o grid de feromônios
+    pub fn init(&self, width: usize, height: usize) {
+        let mut grid = self.grid.lock().unwrap();
+        *grid = vec![vec![0.0; width]; height];
+    }
+
+    /// Um agente deposita feromônio em todas as células cobertas pelo seu corpo
+    pub fn deposit(&self, pos: Vec2, radius: f32, grid_mode: crate::GridMode) {
+        let dt = *self.step_dt.lock().unwrap();
+        let mut grid = self.grid.lock().unwrap();
+
+        for (gx, gy) in footprint(pos, radius, grid_mode) {
+            if gy < grid.len() && gx < grid[0].len() {
+                // Soma valor com um teto
+                let new_val = grid[gy][gx] + AGENT_EMISSION * dt;
+                grid[gy][gx] = new_val.min(MAX_INTENSITY);
+            }
+        }
+    }
+
+    /// Verifica se a célula está "bloqueada" pela comunicação indireta
+    pub fn is_blocked(&self, gx: usize, gy: usize) -> bool {
+        let grid = self.grid.lock().unwrap();
+        if gy < grid.len() && gx < grid[0].len() {
+            return grid[gy][gx] > DANGER_THRESHOLD;
+        }
+        false
+    }
+
+    /// Atualiza o sistema (Evaporação dos feromônios)
+    /// Deve ser chamado uma vez por passo, antes dos agentes depositarem.
+    pub fn update(&self, dt: f32) {
+        *self.step_dt.lock().unwrap() = dt;
+        let mut grid = self.grid.lock().unwrap();
+        for row in grid.iter_mut() {
+            for cell in row.iter_mut() {
+                if *cell > 0.0 {
+                    *cell -= DECAY_RATE * dt;
+                    if *cell < 0.0 {
+                        *cell = 0.0;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Células acima do limiar de perigo (as que `is_blocked` recusa), para a camada de depuração
+    pub fn blocked_cells(&self) -> Vec<(usize, usize)> {
+        let grid = self.grid.lock().unwrap();
+        let mut cells = Vec::new();
+        for (y, row) in grid.iter().enumerate() {
+            for (x, &intensity) in row.iter().enumerate() {
+                if intensity > DANGER_THRESHOLD {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+
+    /// Retorna uma cópia do grid para renderização
+    pub fn get_grid_snapshot(&self) -> Vec<Vec<f32>> {
+        let grid = self.grid.lock().unwrap();
+        grid.clone()
+    }
+    
+    pub fn clear(&self) {
+        let mut grid = self.grid.lock().unwrap();
+        for row in grid.iter_mut() {
+            for cell in row.iter_mut() {
+                *cell = 0.0;
+            }
+        }
+    }
+}
+
+/// Células cobertas pelo corpo de um agente: a célula em que ele está
+/// e as vizinhas cujo centro fica dentro do raio (agentes grandes marcam mais células)
+pub fn footprint(pos: Vec2, radius: f32, grid_mode: crate::GridMode) -> Vec<(usize, usize)> {
+    let (cx, cy) = crate::screen_to_grid(pos.x, pos.y, grid_mode);
+    let mut cells = vec![(cx, cy)];
+
+    // No hexagonal, os anéis em volta do hexágono do agente (o vizinho mais perto fica a √3 raios)
+    if grid_mode == crate::GridMode::Hexagonal {
+        let layout = HexLayout::current();
+        let center = layout.pixel_to_hex(pos);
+        let reach = (radius / layout.width().min(layout.height())).ceil() as u32 + 1;
+        for ring in 1..=reach {
+            for hex in center.ring(ring) {
+                if let Some(cell) = hex.to_cell(layout.orientation, usize::MAX, usize::MAX)
+                    && layout.cell_center(cell).distance(pos) <= radius
+                {
+                    cells.push(cell);
+                }
+            }
+        }
+        return cells;
+    }
+
+    let reach = (radius / crate::CELL_SIZE).ceil() as isize + 1;
+    for dy in -reach..=reach {
+        for dx in -reach..=reach {
+            let (x, y) = (cx as isize + dx, cy as isize + dy);
+            if (dx == 0 && dy == 0) || x < 0 || y < 0 {
+                continue;
+            }
+            let cell = (x as usize, y as usize);
+            if crate::grid_to_screen_center(cell, grid_mode).distance(pos) <= radius {
+                cells.push(cell);
+            }
+        }
+    }
+    cells
+}
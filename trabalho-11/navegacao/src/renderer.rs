@@ -0,0 +1,453 @@
+use crate::{GridMode, InputMode};
+use crate::agent_decorator::AgentComponent;
+use crate::camera::Viewport;
+use crate::debug_overlay::{DebugCapture, DebugOverlays, SearchNode, SearchTrace};
+use crate::grid::{CellType, Grid};
+use crate::navmesh::NavMesh;
+use crate::pheromone::PheromoneManager;
+use crate::pathfinding_adapter::SearchEvent;
+use crate::search_visualizer::{SearchVisualizer, StepCell};
+use macroquad::prelude::*;
+
+/// Cor das estações de recarga (nos dois tipos de grid)
+pub const STATION_COLOR: Color = GOLD;
+
+/// Desenha o mapa de feromônios (só a parte visível)
+pub fn draw_pheromones(view: &Viewport, cell_size: f32) {
+    let grid_snap = PheromoneManager::instance().get_grid_snapshot();
+
+    for (y, row) in grid_snap.iter().enumerate().take(view.rows.end).skip(view.rows.start) {
+        for (x, &intensity) in row.iter().enumerate().take(view.cols.end).skip(view.cols.start) {
+            if intensity > 0.1 {
+                let alpha = (intensity / 5.0).min(0.6);
+                let color = Color::new(1.0, 0.0, 1.0, alpha);
+
+                draw_rectangle(
+                    x as f32 * cell_size,
+                    y as f32 * cell_size,
+                    cell_size,
+                    cell_size,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+/// Desenha a malha de navegação por cima do grid (serve para os dois tipos de grid):
+/// triângulos em traço fino e o contorno dos polígonos convexos mais forte
+pub fn draw_navmesh(mesh: &NavMesh, view: &Viewport) {
+    let triangle_color = Color::new(0.2, 0.8, 0.6, 0.25);
+    let polygon_color = Color::new(0.2, 0.8, 0.6, 0.7);
+    let visible = |points: &[Vec2]| {
+        let (min, max) = points.iter().fold((Vec2::MAX, Vec2::MIN), |(min, max), &p| (min.min(p), max.max(p)));
+        view.rect.overlaps(&Rect::new(min.x, min.y, max.x - min.x, max.y - min.y))
+    };
+
+    for triangle in mesh.triangles().iter().filter(|t| visible(&t[..])) {
+        let [a, b, c] = *triangle;
+        draw_triangle_lines(a, b, c, 1.0, triangle_color);
+    }
+    for polygon in mesh.polygons().iter().filter(|p| visible(p)) {
+        for (i, &a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            draw_line(a.x, a.y, b.x, b.y, 1.5, polygon_color);
+        }
+    }
+}
+
+/// Cor das células fechadas pela ordem de expansão: azul nas primeiras, amarelo nas últimas
+fn expansion_color(order: usize, total: usize) -> Color {
+    let t = order as f32 / total.saturating_sub(1).max(1) as f32;
+    Color::new(0.2 + 0.8 * t, 0.3 + 0.6 * t, 1.0 - 0.8 * t, 0.45)
+}
+
+const OPEN_COLOR: Color = Color::new(0.2, 1.0, 0.4, 0.35);
+const TRACE_PATH_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.9);
+
+/// Desenha o rastro da última busca: fechados coloridos pela ordem de expansão, abertos em verde e
+/// o caminho encontrado por cima. `fill_cell` preenche uma célula do grid (quadrado ou hexágono);
+/// os polígonos da malha de navegação são iguais nos dois tipos de grid.
+pub fn draw_trace(trace: &SearchTrace, view: &Viewport, fill_cell: impl Fn((usize, usize), Color)) {
+    let fill = |node: &SearchNode, color: Color| match node {
+        SearchNode::Cell((x, y)) => {
+            if view.cols.contains(x) && view.rows.contains(y) {
+                fill_cell((*x, *y), color);
+            }
+        }
+        SearchNode::Polygon(points) => {
+            for i in 1..points.len().saturating_sub(1) {
+                draw_triangle(points[0], points[i], points[i + 1], color);
+            }
+        }
+    };
+
+    for (order, node) in trace.expanded.iter().enumerate() {
+        fill(node, expansion_color(order, trace.expanded.len()));
+    }
+    for node in &trace.open {
+        fill(node, OPEN_COLOR);
+    }
+    for pair in trace.path.windows(2) {
+        draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 2.0 / view.zoom, TRACE_PATH_COLOR);
+    }
+}
+
+/// Rastro da última busca no grid retangular
+pub fn draw_search_trace(trace: &SearchTrace, view: &Viewport, cell_size: f32) {
+    draw_trace(trace, view, |(x, y), color| {
+        draw_rectangle(x as f32 * cell_size, y as f32 * cell_size, cell_size, cell_size, color);
+    });
+}
+
+const RELAX_COLOR: Color = Color::new(1.0, 0.6, 0.1, 0.8);
+const START_COLOR: Color = Color::new(0.2, 1.0, 0.2, 1.0);
+const GOAL_COLOR: Color = Color::new(1.0, 0.3, 0.3, 1.0);
+
+/// Desenha o visualizador da busca passo a passo: fechadas pela ordem de expansão, fronteira em verde,
+/// a célula do último evento destacada (branca ao sair da fronteira, laranja ao ser relaxada),
+/// início e destino e, no fim, o caminho. `fill_cell` preenche uma célula e `center` dá o centro dela.
+pub fn draw_search_steps(
+    visualizer: &SearchVisualizer,
+    view: &Viewport,
+    fill_cell: impl Fn((usize, usize), Color),
+    center: impl Fn((usize, usize)) -> Vec2,
+) {
+    let visible = |(x, y): (usize, usize)| view.cols.contains(&x) && view.rows.contains(&y);
+    for (&cell, &state) in visualizer.cells().iter().filter(|(cell, _)| visible(**cell)) {
+        let color = match state {
+            StepCell::Open => OPEN_COLOR,
+            StepCell::Closed(order) => expansion_color(order, visualizer.expansions()),
+        };
+        fill_cell(cell, color);
+    }
+
+    let highlight = match visualizer.last_event() {
+        Some(SearchEvent::Pop { cell, .. }) => Some((*cell, WHITE)),
+        Some(SearchEvent::Push { cell, .. }) => Some((*cell, OPEN_COLOR)),
+        Some(SearchEvent::Relax { cell, .. }) => Some((*cell, RELAX_COLOR)),
+        _ => None,
+    };
+    if let Some((cell, color)) = highlight {
+        fill_cell(cell, Color::new(color.r, color.g, color.b, 0.8));
+    }
+    // O vizinho que acabou de entrar na fronteira ganha uma seta de quem o descobriu
+    if let Some(SearchEvent::Push { cell, from, .. } | SearchEvent::Relax { cell, from, .. }) = visualizer.last_event() {
+        draw_arrow(center(*from), center(*cell), 2.0 / view.zoom, WHITE);
+    }
+
+    for pair in visualizer.path().windows(2) {
+        let (a, b) = (center(pair[0]), center(pair[1]));
+        draw_line(a.x, a.y, b.x, b.y, 3.0 / view.zoom, TRACE_PATH_COLOR);
+    }
+    for (cell, color) in [(visualizer.start(), START_COLOR), (visualizer.goal(), GOAL_COLOR)] {
+        if let Some(cell) = cell {
+            let p = center(cell);
+            draw_circle(p.x, p.y, 5.0, color);
+        }
+    }
+}
+
+/// Visualizador da busca no grid retangular
+pub fn draw_search_visualizer(visualizer: &SearchVisualizer, view: &Viewport, cell_size: f32) {
+    draw_search_steps(
+        visualizer,
+        view,
+        |(x, y), color| draw_rectangle(x as f32 * cell_size, y as f32 * cell_size, cell_size, cell_size, color),
+        |(x, y)| vec2((x as f32 + 0.5) * cell_size, (y as f32 + 0.5) * cell_size),
+    );
+}
+
+/// Contorno vermelho das células que o feromônio bloqueia
+pub const BLOCKED_COLOR: Color = Color::new(1.0, 0.2, 0.2, 0.9);
+
+/// Células bloqueadas pelo feromônio (grid retangular)
+pub fn draw_blocked_cells(cells: &[(usize, usize)], view: &Viewport, cell_size: f32) {
+    for &(x, y) in cells.iter().filter(|(x, y)| view.cols.contains(x) && view.rows.contains(y)) {
+        let (px, py) = (x as f32 * cell_size, y as f32 * cell_size);
+        draw_rectangle_lines(px, py, cell_size, cell_size, 2.0, BLOCKED_COLOR);
+        draw_line(px, py, px + cell_size, py + cell_size, 1.0, BLOCKED_COLOR);
+    }
+}
+
+/// Cor de uma componente conexa: matizes espalhados pela razão áurea, para vizinhas não ficarem parecidas
+fn component_color(component: u32) -> Color {
+    let hue = (component as f32 * 0.618_034).fract();
+    let color = macroquad::color::hsl_to_rgb(hue, 0.7, 0.55);
+    Color::new(color.r, color.g, color.b, 0.35)
+}
+
+/// Pinta as células livres visíveis pela componente conexa na vizinhança do modo de grid.
+/// `fill_cell` preenche uma célula (quadrado ou hexágono).
+pub fn draw_components(grid: &Grid, grid_mode: GridMode, view: &Viewport, fill_cell: impl Fn((usize, usize), Color)) {
+    grid.with_components(grid_mode, |components| {
+        for y in view.rows.clone() {
+            for x in view.cols.clone() {
+                if let Some(component) = components.component_of((x, y)) {
+                    fill_cell((x, y), component_color(component));
+                }
+            }
+        }
+    });
+}
+
+/// Componentes conexas no grid retangular
+pub fn draw_component_cells(grid: &Grid, grid_mode: GridMode, view: &Viewport, cell_size: f32) {
+    draw_components(grid, grid_mode, view, |(x, y), color| {
+        draw_rectangle(x as f32 * cell_size, y as f32 * cell_size, cell_size, cell_size, color);
+    });
+}
+
+/// Segundos de movimento que as setas de velocidade representam
+const ARROW_SECONDS: f32 = 0.25;
+
+fn draw_arrow(from: Vec2, to: Vec2, thickness: f32, color: Color) {
+    draw_line(from.x, from.y, to.x, to.y, thickness, color);
+    let dir = (to - from).normalize_or_zero();
+    if dir == Vec2::ZERO {
+        return;
+    }
+    let head = (to - from).length().min(6.0);
+    let side = dir.perp() * head * 0.5;
+    draw_triangle(to, to - dir * head + side, to - dir * head - side, color);
+}
+
+/// Camadas de depuração dos agentes (iguais nos dois tipos de grid, tudo em pixels):
+/// caminho planejado com o waypoint atual, setas de velocidade atual (branca) e preferida (ciano)
+/// e as candidatas do RVO, de verde (penalidade baixa) a vermelho, com a escolhida destacada
+pub fn draw_agent_debug(agents: &[Box<dyn AgentComponent>], overlays: &DebugOverlays, view: &Viewport) {
+    let capture = DebugCapture::instance();
+    let preferred = if overlays.velocities { capture.preferred() } else { Vec::new() };
+    let candidates = if overlays.rvo_candidates { capture.candidates() } else { Vec::new() };
+    let thickness = 1.5 / view.zoom;
+
+    for (id, agent) in agents.iter().enumerate() {
+        let pos = agent.get_pos();
+
+        if overlays.paths {
+            let (path, current) = agent.get_planned_path();
+            let color = agent.get_color();
+            for (i, pair) in path.windows(2).enumerate() {
+                // O trecho já percorrido fica mais apagado
+                let alpha = if i + 1 < current { 0.25 } else { 0.8 };
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, thickness, Color::new(color.r, color.g, color.b, alpha));
+            }
+            if let Some(&waypoint) = path.get(current) {
+                draw_line(pos.x, pos.y, waypoint.x, waypoint.y, thickness, Color::new(color.r, color.g, color.b, 0.8));
+                draw_circle_lines(waypoint.x, waypoint.y, 4.0, thickness, WHITE);
+            }
+        }
+
+        if !view.contains_circle(pos, agent.get_detection_radius()) {
+            continue;
+        }
+
+        if let Some(scored) = candidates.get(id).filter(|scored| !scored.is_empty()) {
+            // Penalidades em escala logarítmica: as de colisão são ordens de grandeza maiores
+            let (min, max) = scored.iter().fold((f32::MAX, f32::MIN), |(min, max), &(_, p)| (min.min(p), max.max(p)));
+            let span = (max - min).ln_1p().max(f32::EPSILON);
+            for &(velocity, penalty) in scored {
+                let t = (penalty - min).ln_1p() / span;
+                let tip = pos + velocity * ARROW_SECONDS;
+                draw_circle(tip.x, tip.y, 1.5, Color::new(t, 1.0 - t, 0.2, 0.8));
+            }
+            let best = pos + crate::rvo::RvoManager::best_velocity(scored) * ARROW_SECONDS;
+            draw_circle_lines(best.x, best.y, 3.0, thickness, WHITE);
+        }
+
+        if overlays.velocities {
+            if let Some(&pref) = preferred.get(id) {
+                draw_arrow(pos, pos + pref * ARROW_SECONDS, thickness, SKYBLUE);
+            }
+            draw_arrow(pos, pos + agent.get_velocity() * ARROW_SECONDS, thickness, WHITE);
+        }
+    }
+}
+
+/// Desenha as linhas de grade (cinza claro) na parte visível; com o zoom muito afastado, não desenha
+pub fn draw_grid(view: &Viewport, cell_size: f32) {
+    if !view.show_outlines(cell_size) {
+        return;
+    }
+    let (left, right) = (view.cols.start as f32 * cell_size, view.cols.end as f32 * cell_size);
+    let (top, bottom) = (view.rows.start as f32 * cell_size, view.rows.end as f32 * cell_size);
+
+    // Linhas verticais
+    for i in view.cols.start..=view.cols.end {
+        let x = i as f32 * cell_size;
+        draw_line(x, top, x, bottom, 1.0, GRAY);
+    }
+    // Linhas horizontais
+    for i in view.rows.start..=view.rows.end {
+        let y = i as f32 * cell_size;
+        draw_line(left, y, right, y, 1.0, GRAY);
+    }
+}
+
+/// Cor das células livres com terreno mais caro (marrom, mais forte quanto maior o custo)
+pub fn terrain_color(cost: usize) -> Option<Color> {
+    (cost > 1).then(|| Color::new(0.55, 0.4, 0.2, (0.1 * cost as f32).min(0.6)))
+}
+
+/// Retângulos visíveis a pintar: obstáculos (pretos), estações de recarga e terreno, cada um com a sua cor.
+/// Os obstáculos saem do conjunto de bits do grid, um retângulo por trecho seguido de cada linha.
+pub fn fill_cells(grid: &Grid, view: &Viewport, cell_size: f32, mut fill: impl FnMut(Rect, Color)) {
+    for y in view.rows.clone() {
+        let py = y as f32 * cell_size;
+        for run in grid.obstacle_runs(y, view.cols.clone()) {
+            fill(Rect::new(run.start as f32 * cell_size, py, run.len() as f32 * cell_size, cell_size), BLACK);
+        }
+        let cells = grid.row(y)[view.cols.clone()].iter().zip(&grid.terrain_row(y)[view.cols.clone()]);
+        for (x, (&cell, &cost)) in view.cols.clone().zip(cells) {
+            let color = match cell {
+                CellType::Obstacle => continue,
+                CellType::ChargingStation => STATION_COLOR,
+                CellType::Empty => match terrain_color(cost as usize) {
+                    Some(color) => color,
+                    None => continue,
+                },
+            };
+            fill(Rect::new(x as f32 * cell_size, py, cell_size, cell_size), color);
+        }
+    }
+}
+
+/// Desenha as células de obstáculo, as estações de recarga e o terreno visíveis
+pub fn draw_cells(grid: &Grid, view: &Viewport, cell_size: f32) {
+    fill_cells(grid, view, cell_size, |rect, color| draw_rectangle(rect.x, rect.y, rect.w, rect.h, color));
+}
+
+/// Cor translúcida do que a pincelada vai deixar na célula
+pub fn stroke_preview_color(cell_type: CellType) -> Color {
+    match cell_type {
+        CellType::Empty => Color::new(1.0, 0.4, 0.4, 0.5), // Vermelho claro: vai apagar
+        CellType::Obstacle => Color::new(0.0, 0.0, 0.0, 0.6), // Preto translúcido: vai virar obstáculo
+        CellType::ChargingStation => Color::new(STATION_COLOR.r, STATION_COLOR.g, STATION_COLOR.b, 0.6),
+    }
+}
+
+/// Prévia da pincelada em andamento (o grid só muda quando o botão é solto)
+pub fn draw_stroke_preview(cells: &[(usize, usize)], cell_type: CellType, cell_size: f32) {
+    let color = stroke_preview_color(cell_type);
+    for &(x, y) in cells {
+        draw_rectangle(x as f32 * cell_size, y as f32 * cell_size, cell_size, cell_size, color);
+    }
+}
+
+/// Desenha os agentes (círculos) visíveis. A cor base é definida pela AgentFactory.
+pub fn draw_agents(agents: &[Box<dyn AgentComponent>], view: &Viewport) {
+    for agent in agents {
+        let pos = agent.get_pos();
+        if !view.contains_circle(pos, agent.get_detection_radius().max(agent.get_physical_radius())) {
+            continue;
+        }
+
+        // Pede a cor dinâmica ao agente (que passará pelo Decorator)
+        let detection_color = agent.get_detection_color();
+
+        draw_circle_lines(
+            pos.x,
+            pos.y,
+            agent.get_detection_radius(),
+            2.0,
+            detection_color,
+        );
+
+        let radius = agent.get_physical_radius();
+        draw_circle(pos.x, pos.y, radius, agent.get_color());
+        draw_energy_bar(agent.as_ref(), pos, radius);
+    }
+}
+
+/// Barra de energia acima do agente (só aparece com o tanque incompleto)
+pub fn draw_energy_bar(agent: &dyn AgentComponent, pos: Vec2, radius: f32) {
+    let capacity = agent.get_fuel_capacity();
+    let fuel = agent.get_fuel();
+    if capacity <= 0.0 || fuel >= capacity {
+        return;
+    }
+    let fraction = (fuel / capacity).clamp(0.0, 1.0);
+    let width = radius * 2.0;
+    let color = if fraction < crate::energy::LOW_ENERGY_FRACTION { RED } else { GREEN };
+    draw_rectangle(pos.x - radius, pos.y - radius - 6.0, width, 3.0, DARKGRAY);
+    draw_rectangle(pos.x - radius, pos.y - radius - 6.0, width * fraction, 3.0, color);
+}
+
+/// Cor dos alvos e do retângulo de seleção da alocação de tarefas
+pub const SELECTION_COLOR: Color = Color::new(0.3, 0.9, 1.0, 1.0);
+pub const TARGET_COLOR: Color = Color::new(1.0, 0.3, 1.0, 1.0);
+
+/// Agentes selecionados (anel), alvos marcados (X) e o retângulo sendo arrastado.
+/// Recebe posições em pixels, então serve para os dois renderizadores.
+pub fn draw_allocation(agents: &[Box<dyn AgentComponent>], selected: &[usize], targets: &[Vec2], drag: Option<(Vec2, Vec2)>) {
+    for agent in selected.iter().filter_map(|&id| agents.get(id)) {
+        let pos = agent.get_pos();
+        draw_circle_lines(pos.x, pos.y, agent.get_physical_radius() + 4.0, 2.0, SELECTION_COLOR);
+    }
+    for target in targets {
+        draw_line(target.x - 5.0, target.y - 5.0, target.x + 5.0, target.y + 5.0, 2.0, TARGET_COLOR);
+        draw_line(target.x - 5.0, target.y + 5.0, target.x + 5.0, target.y - 5.0, 2.0, TARGET_COLOR);
+    }
+    if let Some((from, to)) = drag {
+        let min = from.min(to);
+        let size = (from - to).abs();
+        draw_rectangle(min.x, min.y, size.x, size.y, Color::new(SELECTION_COLOR.r, SELECTION_COLOR.g, SELECTION_COLOR.b, 0.15));
+        draw_rectangle_lines(min.x, min.y, size.x, size.y, 1.0, SELECTION_COLOR);
+    }
+}
+
+/// Desenha um feedback visual (um "cursor") que segue o mouse,
+/// indicando o modo de entrada atual.
+pub fn draw_input_feedback(
+    mode: &InputMode,
+    pending_start: Option<(usize, usize)>,
+    mouse_grid_pos: (usize, usize),
+    cell_size: f32,
+    mouse_over_obstacle: bool,
+) {
+    let x = mouse_grid_pos.0 as f32 * cell_size;
+    let y = mouse_grid_pos.1 as f32 * cell_size;
+    let color = match mode {
+        InputMode::DrawObstacle => {
+            // Vermelho se estiver sobre obstáculo (apagando), cinza se estiver desenhando
+            if mouse_over_obstacle {
+                RED
+            } else {
+                Color::new(0.3, 0.3, 0.3, 0.8)
+            }
+        }
+        InputMode::EraseObstacle => Color::new(1.0, 0.4, 0.4, 0.6),
+        InputMode::DrawStation => stroke_preview_color(CellType::ChargingStation),
+        InputMode::Select => Color::new(SELECTION_COLOR.r, SELECTION_COLOR.g, SELECTION_COLOR.b, 0.3),
+        InputMode::Targets => Color::new(TARGET_COLOR.r, TARGET_COLOR.g, TARGET_COLOR.b, 0.4),
+        InputMode::SetStart => {
+            // Verde (para "início") ou Vermelho se for inválido (sobre obstáculo)
+            if mouse_over_obstacle {
+                RED
+            } else {
+                Color::new(0.0, 1.0, 0.0, 0.5)
+            }
+        }
+        InputMode::SetEnd => {
+            // Desenha o ponto inicial pendente (verde sólido)
+            if let Some(start) = pending_start {
+                draw_rectangle(
+                    start.0 as f32 * cell_size,
+                    start.1 as f32 * cell_size,
+                    cell_size,
+                    cell_size,
+                    Color::new(0.0, 1.0, 0.0, 0.8), // Verde
+                );
+            }
+            // Vermelho (para "destino") ou Vermelho sólido se for inválido
+            if mouse_over_obstacle {
+                RED
+            } else {
+                Color::new(1.0, 0.0, 0.0, 0.5)
+            }
+        }
+    };
+
+    // Desenha o "cursor" do grid
+    draw_rectangle(x, y, cell_size, cell_size, color);
+}
@@ -0,0 +1,607 @@
+use crate::agent::{Agent, AgentArchetype, DETECTION_RADIUS, FUEL_CAPACITY, PHYSICAL_RADIUS};
+use crate::agent_decorator::AgentComponent;
+use crate::command::{Command, CommandManager, CommandRecord};
+use crate::grid::{CellType, Grid};
+use crate::hex::{DEFAULT_HEX_SIZE, HexLayout, HexOrientation};
+use crate::path_manager::PathManager;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Versão do formato do log. Muda sempre que o formato deixar de ser compatível.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Primeira linha do log: tudo que é preciso para reconstruir o início da execução
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReplayHeader {
+    pub version: u32,
+    /// Semente do gerador aleatório (só existe nas execuções headless)
+    pub seed: Option<u64>,
+    /// Cenário e número de agentes pedidos no headless (permitem `--verify`)
+    pub scenario: Option<String>,
+    pub requested_agents: Option<usize>,
+    pub avoidance: String,
+    pub grid_mode: String,
+    pub width: usize,
+    pub height: usize,
+    /// Obstáculos do grid no início da gravação
+    pub obstacles: Vec<(usize, usize)>,
+    /// Estações de recarga no início da gravação
+    #[serde(default)]
+    pub charging_stations: Vec<(usize, usize)>,
+    /// Passo fixo (só existe nas execuções headless)
+    pub dt: Option<f32>,
+    /// Planejador das rotas (logs antigos não têm: eram todos `grid`)
+    #[serde(default)]
+    pub planner: Option<String>,
+    /// Layout hexagonal (logs antigos não têm: eram todos pointy de raio 15)
+    #[serde(default)]
+    pub hex_orientation: Option<String>,
+    #[serde(default)]
+    pub hex_size: Option<f32>,
+    /// Gerador do mapa do headless (só aparece quando não é o grid vazio)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map: Option<String>,
+}
+
+impl ReplayHeader {
+    /// Cabeçalho com o grid atual; os campos do headless ficam vazios
+    pub fn new(grid: &Grid, grid_mode: crate::GridMode, avoidance: &str) -> Self {
+        let mut obstacles = Vec::new();
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                if grid.is_obstacle(x, y) {
+                    obstacles.push((x, y));
+                }
+            }
+        }
+
+        Self {
+            version: REPLAY_VERSION,
+            seed: None,
+            scenario: None,
+            requested_agents: None,
+            avoidance: avoidance.to_string(),
+            grid_mode: format!("{:?}", grid_mode),
+            width: grid.width,
+            height: grid.height,
+            obstacles,
+            charging_stations: grid.charging_stations(),
+            dt: None,
+            planner: Some(PathManager::instance().planner().name().to_string()),
+            hex_orientation: Some(HexLayout::current().orientation.name().to_string()),
+            hex_size: Some(HexLayout::current().size),
+            map: None,
+        }
+    }
+
+    /// Layout hexagonal da gravação
+    pub fn hex_layout(&self) -> Result<HexLayout, String> {
+        let orientation = match &self.hex_orientation {
+            Some(name) => HexOrientation::parse(name)
+                .ok_or_else(|| format!("Orientação hexagonal desconhecida: '{}'", name))?,
+            None => HexOrientation::PointyTop,
+        };
+        Ok(HexLayout::new(
+            orientation,
+            self.hex_size.unwrap_or(DEFAULT_HEX_SIZE),
+        ))
+    }
+
+    /// Grid no estado do início da gravação
+    pub fn build_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.width, self.height);
+        for &(x, y) in &self.obstacles {
+            grid.set_cell(x, y, CellType::Obstacle);
+        }
+        for &(x, y) in &self.charging_stations {
+            grid.set_cell(x, y, CellType::ChargingStation);
+        }
+        grid
+    }
+}
+
+/// Demais linhas do log, sempre em ordem de tick
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayEntry {
+    /// Agente que apareceu na simulação (posição inicial, cor base e tamanho)
+    Spawn {
+        tick: u64,
+        id: usize,
+        pos: [f32; 2],
+        color: [f32; 4],
+        #[serde(default = "default_radius")]
+        radius: f32,
+        #[serde(default = "default_detection_radius")]
+        detection_radius: f32,
+        #[serde(default = "default_fuel_capacity")]
+        fuel_capacity: f32,
+    },
+    /// Comando executado pelo `CommandManager`
+    Command { tick: u64, command: CommandRecord },
+    /// Comando executado na hora, fora da fila (pinceladas no grid)
+    Execute { tick: u64, command: CommandRecord },
+    /// `undo_last` chamado pelo usuário
+    Undo { tick: u64 },
+    /// `redo_last` chamado pelo usuário
+    Redo { tick: u64 },
+    /// Agentes e grid foram limpos (teclas C, 1, 2, 3)
+    Clear { tick: u64 },
+    /// Fim da gravação: número total de ticks
+    End { tick: u64 },
+}
+
+// Logs gravados antes dos arquétipos só tinham o agente padrão
+fn default_radius() -> f32 {
+    PHYSICAL_RADIUS
+}
+fn default_detection_radius() -> f32 {
+    DETECTION_RADIUS
+}
+fn default_fuel_capacity() -> f32 {
+    FUEL_CAPACITY
+}
+
+impl ReplayEntry {
+    pub fn tick(&self) -> u64 {
+        match self {
+            ReplayEntry::Spawn { tick, .. }
+            | ReplayEntry::Command { tick, .. }
+            | ReplayEntry::Execute { tick, .. }
+            | ReplayEntry::Undo { tick }
+            | ReplayEntry::Redo { tick }
+            | ReplayEntry::Clear { tick }
+            | ReplayEntry::End { tick } => *tick,
+        }
+    }
+}
+
+/// Grava o log em JSON Lines enquanto a simulação roda.
+/// Fica dentro do `CommandManager`, que é por onde passam todos os comandos.
+pub struct ReplayRecorder {
+    writer: BufWriter<File>,
+    /// Quantos agentes já foram registrados (os ids são os índices no vetor)
+    known_agents: usize,
+}
+
+impl ReplayRecorder {
+    pub fn create(path: &Path, header: &ReplayHeader) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, header)?;
+        writeln!(writer)?;
+
+        Ok(Self {
+            writer,
+            known_agents: 0,
+        })
+    }
+
+    fn write(&mut self, entry: &ReplayEntry) {
+        let result = serde_json::to_writer(&mut self.writer, entry)
+            .map_err(io::Error::from)
+            .and_then(|_| writeln!(self.writer));
+        if let Err(e) = result {
+            eprintln!("Erro ao gravar o replay: {}", e);
+        }
+    }
+
+    /// Registra os agentes criados desde a última chamada
+    pub fn record_spawns(&mut self, tick: u64, agents: &[Box<dyn AgentComponent>]) {
+        for agent in agents.iter().skip(self.known_agents) {
+            let pos = agent.get_pos();
+            let color = agent.get_color();
+            self.write(&ReplayEntry::Spawn {
+                tick,
+                id: agent.get_id(),
+                pos: [pos.x, pos.y],
+                color: [color.r, color.g, color.b, color.a],
+                radius: agent.get_physical_radius(),
+                detection_radius: agent.get_detection_radius(),
+                fuel_capacity: agent.get_fuel_capacity(),
+            });
+        }
+        self.known_agents = self.known_agents.max(agents.len());
+    }
+
+    pub fn record_command(&mut self, tick: u64, command: &dyn Command) {
+        self.write(&ReplayEntry::Command {
+            tick,
+            command: command.record(),
+        });
+    }
+
+    pub fn record_execute(&mut self, tick: u64, command: &dyn Command) {
+        self.write(&ReplayEntry::Execute {
+            tick,
+            command: command.record(),
+        });
+    }
+
+    pub fn record_undo(&mut self, tick: u64) {
+        self.write(&ReplayEntry::Undo { tick });
+    }
+
+    pub fn record_redo(&mut self, tick: u64) {
+        self.write(&ReplayEntry::Redo { tick });
+    }
+
+    pub fn record_clear(&mut self, tick: u64) {
+        self.known_agents = 0;
+        self.write(&ReplayEntry::Clear { tick });
+    }
+
+    /// Fecha a gravação com o número total de ticks
+    pub fn finish(mut self, tick: u64) -> io::Result<()> {
+        self.write(&ReplayEntry::End { tick });
+        self.writer.flush()
+    }
+}
+
+/// Log carregado do disco
+pub struct ReplayLog {
+    pub header: ReplayHeader,
+    pub entries: Vec<ReplayEntry>,
+}
+
+impl ReplayLog {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut lines = BufReader::new(file).lines();
+
+        let first = lines
+            .next()
+            .ok_or_else(|| format!("{}: arquivo vazio", path.display()))?
+            .map_err(|e| e.to_string())?;
+        let header: ReplayHeader =
+            serde_json::from_str(&first).map_err(|e| format!("Cabeçalho inválido: {}", e))?;
+
+        if header.version != REPLAY_VERSION {
+            return Err(format!(
+                "Versão do replay não suportada: {} (esperada {})",
+                header.version, REPLAY_VERSION
+            ));
+        }
+
+        let mut entries = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let line = line.map_err(|e| e.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line)
+                .map_err(|e| format!("Linha {} inválida: {}", i + 2, e))?;
+            entries.push(entry);
+        }
+
+        Ok(Self { header, entries })
+    }
+
+    /// Número de ticks gravados
+    pub fn total_ticks(&self) -> u64 {
+        self.entries.iter().map(|e| e.tick()).max().unwrap_or(0)
+    }
+
+    /// Compara com outro log e descreve a primeira diferença encontrada
+    pub fn first_difference(&self, other: &ReplayLog) -> Option<String> {
+        if self.header != other.header {
+            return Some(format!(
+                "Cabeçalhos diferentes:\n  {:?}\n  {:?}",
+                self.header, other.header
+            ));
+        }
+
+        for (i, (a, b)) in self.entries.iter().zip(&other.entries).enumerate() {
+            if a != b {
+                return Some(format!(
+                    "Diferença na entrada {} (tick {}):\n  {:?}\n  {:?}",
+                    i,
+                    a.tick(),
+                    a,
+                    b
+                ));
+            }
+        }
+
+        if self.entries.len() != other.entries.len() {
+            return Some(format!(
+                "Número de entradas diferente: {} e {}",
+                self.entries.len(),
+                other.entries.len()
+            ));
+        }
+
+        None
+    }
+}
+
+/// Reproduz um log tick a tick, sem rodar a prevenção de colisão nem o A*:
+/// os agentes só se movem pelos comandos gravados.
+pub struct ReplayPlayer {
+    log: ReplayLog,
+    pub agents: Vec<Box<dyn AgentComponent>>,
+    pub grid: Grid,
+    command_manager: CommandManager,
+    /// Próximo tick a ser executado
+    tick: u64,
+    next_entry: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(log: ReplayLog) -> Self {
+        let grid = log.header.build_grid();
+        Self {
+            log,
+            agents: Vec::new(),
+            grid,
+            command_manager: CommandManager::new(),
+            tick: 0,
+            next_entry: 0,
+        }
+    }
+
+    pub fn header(&self) -> &ReplayHeader {
+        &self.log.header
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn total_ticks(&self) -> u64 {
+        self.log.total_ticks()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.total_ticks()
+    }
+
+    /// Volta ao início da gravação
+    pub fn restart(&mut self) {
+        self.agents.clear();
+        self.grid = self.log.header.build_grid();
+        self.command_manager = CommandManager::new();
+        self.tick = 0;
+        self.next_entry = 0;
+    }
+
+    /// Executa todas as entradas do tick atual e avança um tick
+    pub fn step_forward(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+
+        self.apply_entries();
+
+        // Mesma sequência do `simulation::step`: as ações do tick vêm antes do passo,
+        // e os comandos gravados no passo (movimentos e chegadas) são um tick do histórico
+        self.command_manager.begin_tick(&self.agents);
+        self.command_manager
+            .process_commands(&mut self.agents, &mut self.grid);
+        self.tick += 1;
+
+        // Ações feitas depois do último passo (um undo antes de fechar a gravação) ficam no tick do fim
+        if self.is_finished() {
+            self.apply_entries();
+        }
+    }
+
+    /// Aplica as entradas gravadas no tick atual: agentes criados, ações fora do passo
+    /// e os comandos que entram na fila do passo
+    fn apply_entries(&mut self) {
+        while let Some(entry) = self.log.entries.get(self.next_entry) {
+            if entry.tick() != self.tick {
+                break;
+            }
+
+            match entry.clone() {
+                ReplayEntry::Spawn {
+                    id,
+                    pos,
+                    color,
+                    radius,
+                    detection_radius,
+                    fuel_capacity,
+                    ..
+                } => {
+                    let archetype = AgentArchetype {
+                        radius,
+                        detection_radius,
+                        fuel_capacity,
+                        max_speed: 0.0,
+                        ..AgentArchetype::standard(
+                            "replay",
+                            Color::new(color[0], color[1], color[2], color[3]),
+                        )
+                    };
+                    let agent =
+                        Agent::from_archetype(id, vec2(pos[0], pos[1]), Vec::new(), &archetype);
+                    self.agents.push(Box::new(agent));
+                }
+                ReplayEntry::Command { command, .. } => {
+                    self.command_manager.add_command(command.into_command());
+                }
+                ReplayEntry::Execute { command, .. } => {
+                    self.command_manager.execute_now(
+                        command.into_command(),
+                        &mut self.agents,
+                        &mut self.grid,
+                    );
+                }
+                ReplayEntry::Undo { .. } => {
+                    self.command_manager
+                        .undo_last(&mut self.agents, &mut self.grid);
+                }
+                ReplayEntry::Redo { .. } => {
+                    self.command_manager
+                        .redo_last(&mut self.agents, &mut self.grid);
+                }
+                ReplayEntry::Clear { .. } => {
+                    self.command_manager.clear();
+                    self.agents.clear();
+                    self.grid.clear();
+                }
+                ReplayEntry::End { .. } => {}
+            }
+            self.next_entry += 1;
+        }
+    }
+
+    /// Vai para um tick qualquer. Voltar no tempo reconstrói a partir do início.
+    pub fn seek(&mut self, tick: u64) {
+        if tick < self.tick {
+            self.restart();
+        }
+        while self.tick < tick && !self.is_finished() {
+            self.step_forward();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridMode;
+    use crate::collision_avoidance::NoAvoidanceStrategy;
+    use crate::simulation;
+
+    /// Log temporário de um teste (um arquivo por teste e por processo)
+    fn log_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("replay_{}_{}.jsonl", name, std::process::id()))
+    }
+
+    /// Grava uma sessão: `session` roda a simulação com o gravador ligado
+    fn record(
+        name: &str,
+        grid: &mut Grid,
+        agents: &mut Vec<Box<dyn AgentComponent>>,
+        session: impl FnOnce(&mut Vec<Box<dyn AgentComponent>>, &mut Grid, &mut CommandManager),
+    ) -> ReplayLog {
+        let path = log_path(name);
+        let header = ReplayHeader::new(grid, GridMode::Cardinal, "none");
+        let mut manager = CommandManager::new();
+        manager.start_recording(ReplayRecorder::create(&path, &header).unwrap());
+        session(agents, grid, &mut manager);
+        manager.stop_recording().unwrap();
+
+        let log = ReplayLog::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        log
+    }
+
+    fn run(
+        agents: &mut Vec<Box<dyn AgentComponent>>,
+        grid: &mut Grid,
+        manager: &mut CommandManager,
+        ticks: usize,
+    ) {
+        for _ in 0..ticks {
+            simulation::step(
+                agents,
+                grid,
+                manager,
+                &mut NoAvoidanceStrategy,
+                GridMode::Cardinal,
+                1.0 / 60.0,
+            );
+        }
+    }
+
+    /// Reproduz o log até o fim e compara com os agentes da execução gravada
+    fn assert_replays_to(log: ReplayLog, agents: &[Box<dyn AgentComponent>]) {
+        let mut player = ReplayPlayer::new(log);
+        player.seek(player.total_ticks());
+        assert_eq!(player.agents.len(), agents.len());
+        for (replayed, live) in player.agents.iter().zip(agents) {
+            assert_eq!(
+                replayed.get_pos(),
+                live.get_pos(),
+                "agente {}",
+                live.get_id()
+            );
+            assert_eq!(
+                replayed.get_fuel(),
+                live.get_fuel(),
+                "agente {}",
+                live.get_id()
+            );
+            assert_eq!(
+                replayed.is_finished(),
+                live.is_finished(),
+                "agente {}",
+                live.get_id()
+            );
+        }
+    }
+
+    #[test]
+    fn replay_ends_where_the_recording_ended() {
+        let mut grid = Grid::new(12, 6);
+        let mut agents: Vec<Box<dyn AgentComponent>> = vec![
+            Box::new(Agent::from_archetype(
+                0,
+                vec2(30.0, 30.0),
+                vec![vec2(330.0, 30.0)],
+                &AgentArchetype::carrier(),
+            )),
+            Box::new(Agent::from_archetype(
+                1,
+                vec2(30.0, 150.0),
+                vec![vec2(190.0, 150.0)],
+                &AgentArchetype::standard("a", RED),
+            )),
+        ];
+        let log = record(
+            "round_trip",
+            &mut grid,
+            &mut agents,
+            |agents, grid, manager| {
+                run(agents, grid, manager, 40);
+                manager.undo_last(agents, grid);
+                manager.undo_last(agents, grid);
+                run(agents, grid, manager, 20);
+            },
+        );
+        assert_replays_to(log, &agents);
+    }
+
+    #[test]
+    fn undo_of_an_arrival_only_tick_replays_the_same() {
+        let mut grid = Grid::new(12, 4);
+        let path = vec![vec2(110.0, 30.0), vec2(190.0, 30.0)];
+        let mut agents: Vec<Box<dyn AgentComponent>> = vec![Box::new(Agent::from_archetype(
+            0,
+            vec2(30.0, 30.0),
+            path,
+            &AgentArchetype::carrier(),
+        ))];
+        let log = record(
+            "arrival_undo",
+            &mut grid,
+            &mut agents,
+            |agents, grid, manager| {
+                while !agents[0].is_finished() {
+                    run(agents, grid, manager, 1);
+                }
+                // O tick da chegada não tem movimento: o undo desfaz só a chegada
+                let arrived_at = agents[0].get_pos();
+                manager.undo_last(agents, grid);
+                assert!(!agents[0].is_finished());
+                assert_eq!(agents[0].get_pos(), arrived_at);
+            },
+        );
+        assert!(log.entries.iter().any(|e| matches!(
+            e,
+            ReplayEntry::Command {
+                command: CommandRecord::SetFinished { .. },
+                ..
+            }
+        )));
+        assert_replays_to(log, &agents);
+    }
+}
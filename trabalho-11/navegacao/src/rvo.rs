@@ -0,0 +1,415 @@
+use crate::obstacle::ObstacleEdge;
+use macroquad::prelude::*;
+
+/// Configurações do algoritmo RVO
+const NEIGHBOR_DIST: f32 = 60.0; // Distância de visão
+const TIME_HORIZON: f32 = 2.5;   // Tempo de antecipação
+const RADIUS_MARGIN: f32 = 2.0;  // Margem pessoal padrão
+/// Horizonte mínimo (s) dos obstáculos estáticos: nenhuma velocidade escolhida leva o centro
+/// do agente para dentro de um obstáculo antes disso. Agentes com aceleração finita olham
+/// também o tempo que levam para parar (ver `RvoManager::obstacle_horizon`)
+pub const OBSTACLE_TIME_HORIZON: f32 = 0.1;
+/// Antecipação (s) da folga do corpo em relação às paredes
+pub const OBSTACLE_LOOKAHEAD: f32 = 0.25;
+/// Peso da folga das paredes, em fração da velocidade máxima do agente
+const OBSTACLE_WEIGHT: f32 = 0.5;
+
+/// Máximo de células por eixo da grade de vizinhos (agentes muito espalhados usam células maiores)
+const MAX_NEIGHBOR_CELLS: usize = 1024;
+
+/// Posição, velocidade e intenção que um agente anuncia aos vizinhos (uma linha de `RvoStates`)
+#[derive(Clone, Copy, Debug)]
+pub struct RvoAgent {
+    pub id: usize,
+    pub pos: Vec2,
+    pub velocity: Vec2,
+    pub radius: f32,
+    pub max_speed: f32,
+    pub max_acceleration: f32,
+    pub pref_velocity: Vec2,
+}
+
+/// Posição, velocidade e intenção que cada agente anuncia aos vizinhos, guardadas como
+/// estrutura de arrays (o laço de vizinhos percorre posições e velocidades contíguas).
+/// O índice de um agente é a posição dele em todos os vetores.
+#[derive(Clone, Default)]
+pub struct RvoStates {
+    pub ids: Vec<usize>,
+    pub positions: Vec<Vec2>,
+    pub velocities: Vec<Vec2>,
+    pub radii: Vec<f32>,
+    pub max_speeds: Vec<f32>,
+    pub max_accelerations: Vec<f32>,
+    pub pref_velocities: Vec<Vec2>,
+}
+
+impl RvoStates {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ids: Vec::with_capacity(capacity),
+            positions: Vec::with_capacity(capacity),
+            velocities: Vec::with_capacity(capacity),
+            radii: Vec::with_capacity(capacity),
+            max_speeds: Vec::with_capacity(capacity),
+            max_accelerations: Vec::with_capacity(capacity),
+            pref_velocities: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, agent: RvoAgent) {
+        self.ids.push(agent.id);
+        self.positions.push(agent.pos);
+        self.velocities.push(agent.velocity);
+        self.radii.push(agent.radius);
+        self.max_speeds.push(agent.max_speed);
+        self.max_accelerations.push(agent.max_acceleration);
+        self.pref_velocities.push(agent.pref_velocity);
+    }
+
+    /// O agente `index` inteiro (para quem só olha para ele, como as restrições dos obstáculos)
+    pub fn agent(&self, index: usize) -> RvoAgent {
+        RvoAgent {
+            id: self.ids[index],
+            pos: self.positions[index],
+            velocity: self.velocities[index],
+            radius: self.radii[index],
+            max_speed: self.max_speeds[index],
+            max_acceleration: self.max_accelerations[index],
+            pref_velocity: self.pref_velocities[index],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+/// Agentes agrupados (por ordenação por contagem) em células de pelo menos `NEIGHBOR_DIST`
+/// (ou do alcance passado a `with_range`): os vizinhos de um agente só podem estar nas 3x3
+/// células em volta da dele.
+/// Montada uma vez por passo e só lida depois, então pode ser consultada de várias threads.
+pub struct NeighborGrid {
+    origin: Vec2,
+    cell_size: f32,
+    cols: usize,
+    rows: usize,
+    /// A célula `c` ocupa `entries[cell_start[c]..cell_start[c + 1]]`
+    cell_start: Vec<usize>,
+    /// Índices dos agentes ordenados por célula (crescentes dentro de cada célula)
+    entries: Vec<usize>,
+}
+
+impl NeighborGrid {
+    pub fn build(positions: &[Vec2]) -> Self {
+        Self::with_range(positions, NEIGHBOR_DIST)
+    }
+
+    /// Grade para consultas até `range` px (`nearby`)
+    pub fn with_range(positions: &[Vec2], range: f32) -> Self {
+        let (min, max) = positions
+            .iter()
+            .fold((Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)), |(min, max), &p| (min.min(p), max.max(p)));
+        let (origin, extent) = if positions.is_empty() { (Vec2::ZERO, Vec2::ZERO) } else { (min, max - min) };
+        let cell_size = range
+            .max(extent.x / MAX_NEIGHBOR_CELLS as f32)
+            .max(extent.y / MAX_NEIGHBOR_CELLS as f32);
+        let mut grid = Self {
+            origin,
+            cell_size,
+            cols: ((extent.x / cell_size) as usize + 1).min(MAX_NEIGHBOR_CELLS + 1),
+            rows: ((extent.y / cell_size) as usize + 1).min(MAX_NEIGHBOR_CELLS + 1),
+            cell_start: Vec::new(),
+            entries: vec![0; positions.len()],
+        };
+
+        let cells: Vec<usize> = positions.iter().map(|&p| grid.cell_index(grid.cell_of(p))).collect();
+        let mut start = vec![0; grid.cols * grid.rows + 1];
+        for &cell in &cells {
+            start[cell + 1] += 1;
+        }
+        for c in 1..start.len() {
+            start[c] += start[c - 1];
+        }
+        let mut next = start.clone();
+        for (i, &cell) in cells.iter().enumerate() {
+            grid.entries[next[cell]] = i;
+            next[cell] += 1;
+        }
+        grid.cell_start = start;
+        grid
+    }
+
+    fn cell_of(&self, pos: Vec2) -> (usize, usize) {
+        let rel = (pos - self.origin) / self.cell_size;
+        ((rel.x.max(0.0) as usize).min(self.cols - 1), (rel.y.max(0.0) as usize).min(self.rows - 1))
+    }
+
+    fn cell_index(&self, (x, y): (usize, usize)) -> usize {
+        y * self.cols + x
+    }
+
+    /// Vizinhos do agente `index` dentro de `NEIGHBOR_DIST`, em ordem crescente de índice
+    /// (a mesma em que a lista inteira seria percorrida, então as penalidades somam igual)
+    pub fn neighbors(&self, states: &RvoStates, index: usize, out: &mut Vec<usize>) {
+        let pos = states.positions[index];
+        self.nearby(pos, out);
+        out.retain(|&other| {
+            states.ids[other] != states.ids[index] && pos.distance_squared(states.positions[other]) <= NEIGHBOR_DIST * NEIGHBOR_DIST
+        });
+    }
+
+    /// Índices (crescentes) de todos os agentes nas 3x3 células em volta de `pos`: inclui todos
+    /// a menos do alcance da grade, e a distância exata fica para quem consulta
+    pub fn nearby(&self, pos: Vec2, out: &mut Vec<usize>) {
+        out.clear();
+        let (cx, cy) = self.cell_of(pos);
+        for y in cy.saturating_sub(1)..=(cy + 1).min(self.rows - 1) {
+            for x in cx.saturating_sub(1)..=(cx + 1).min(self.cols - 1) {
+                let cell = self.cell_index((x, y));
+                out.extend_from_slice(&self.entries[self.cell_start[cell]..self.cell_start[cell + 1]]);
+            }
+        }
+        out.sort_unstable();
+    }
+}
+
+pub struct RvoManager;
+
+impl RvoManager {
+    /// Candidata de menor penalidade (a primeira, em caso de empate); parado se não houver nenhuma
+    pub fn best_velocity(scored: &[(Vec2, f32)]) -> Vec2 {
+        let mut best_velocity = Vec2::ZERO;
+        let mut min_penalty = f32::MAX;
+        for &(velocity, penalty) in scored {
+            if penalty < min_penalty {
+                min_penalty = penalty;
+                best_velocity = velocity;
+            }
+        }
+        best_velocity
+    }
+
+    /// Horizonte dos obstáculos para um agente a `speed`: pelo menos o passo da simulação e o
+    /// tempo que ele leva para parar com a aceleração que tem (zero com aceleração infinita)
+    pub fn obstacle_horizon(speed: f32, max_acceleration: f32, dt: f32) -> f32 {
+        OBSTACLE_TIME_HORIZON.max(dt).max(speed / max_acceleration)
+    }
+
+    /// Velocidades candidatas e a penalidade de cada uma (a velocidade segura é a de menor penalidade).
+    /// `obstacles` são as arestas de obstáculos perto do agente e `dt` o passo da simulação.
+    /// `neighbors` são os índices dos vizinhos do agente `index` (ver `NeighborGrid::neighbors`)
+    pub fn score_candidates(
+        states: &RvoStates,
+        index: usize,
+        neighbors: &[usize],
+        obstacles: &[ObstacleEdge],
+        dt: f32,
+    ) -> Vec<(Vec2, f32)> {
+        let agent = states.agent(index);
+        let walls = Self::walls_ahead(agent.pos, obstacles);
+        Self::candidates(&agent)
+            .into_iter()
+            .map(|cand_vel| {
+                let penalty = Self::evaluate_velocity(states, index, cand_vel, neighbors, f32::INFINITY)
+                    + Self::evaluate_obstacles(&agent, cand_vel, &walls, dt);
+                (cand_vel, penalty)
+            })
+            .collect()
+    }
+
+    /// Mesma escolha de `best_velocity(&score_candidates(...))` sem guardar as penalidades.
+    /// Todas as parcelas são positivas, então a soma só cresce: a avaliação de uma candidata
+    /// para assim que passa da melhor até agora, porque ela já não pode ganhar
+    pub fn choose_velocity(
+        states: &RvoStates,
+        index: usize,
+        neighbors: &[usize],
+        obstacles: &[ObstacleEdge],
+        dt: f32,
+    ) -> Vec2 {
+        let agent = states.agent(index);
+        let walls = Self::walls_ahead(agent.pos, obstacles);
+        let mut best_velocity = Vec2::ZERO;
+        let mut min_penalty = f32::MAX;
+        for cand_vel in Self::candidates(&agent) {
+            let penalty = Self::evaluate_velocity(states, index, cand_vel, neighbors, min_penalty);
+            if penalty >= min_penalty {
+                continue;
+            }
+            let penalty = penalty + Self::evaluate_obstacles(&agent, cand_vel, &walls, dt);
+            if penalty < min_penalty {
+                min_penalty = penalty;
+                best_velocity = cand_vel;
+            }
+        }
+        best_velocity
+    }
+
+    /// Candidatas na ordem de preferência (a primeira ganha os empates); nenhuma se a intenção é ficar parado
+    fn candidates(agent: &RvoAgent) -> Vec<Vec2> {
+        let pref_velocity = agent.pref_velocity;
+        // Se a intenção é ficar parado, não há o que escolher
+        if pref_velocity.length_squared() < 0.01 {
+            return Vec::new();
+        }
+
+        // --- GERAÇÃO DE CANDIDATOS ---
+        let mut candidates = Vec::with_capacity(38);
+
+        // 1. Otimista (Velocidade desejada)
+        candidates.push(pref_velocity);
+
+        let speed = pref_velocity.length().min(agent.max_speed);
+        let base_angle = pref_velocity.y.atan2(pref_velocity.x);
+
+        // Gera ângulos de desvio
+        let angles = [
+            10.0f32, -10.0,
+            25.0, -25.0,
+            45.0, -45.0, 
+            70.0, -70.0, 
+            90.0, -90.0,
+            110.0, -110.0 // Tenta voltar um pouco se estiver muito bloqueado
+        ];
+
+        for &deg in &angles {
+            let rad = deg.to_radians();
+            let new_angle = base_angle + rad;
+            let direction = vec2(new_angle.cos(), new_angle.sin());
+            
+            // Velocidade total
+            candidates.push(direction * speed);
+            // Meia velocidade (frear para manobrar)
+            candidates.push(direction * (speed * 0.5));
+             // Velocidade muito baixa (quase parando para esperar)
+            candidates.push(direction * (speed * 0.1));
+        }
+
+        // Ficar parado é a última opção
+        candidates.push(Vec2::ZERO);
+        candidates
+    }
+
+    /// Arestas à frente do agente com a distância atual até cada uma (não depende da candidata).
+    /// Atrás da aresta (já empurrado para fora do lado livre) só interessa voltar
+    fn walls_ahead(pos: Vec2, obstacles: &[ObstacleEdge]) -> Vec<(ObstacleEdge, f32)> {
+        obstacles
+            .iter()
+            .filter(|edge| (pos - edge.a).dot(edge.normal) >= 0.0)
+            .map(|edge| (*edge, edge.distance_to(pos)))
+            .collect()
+    }
+
+    /// Restrições estáticas (como os polígonos de obstáculo do RVO2): entrar em um obstáculo
+    /// dentro do horizonte é inaceitável; chegar mais perto de uma parede do que o raio custa
+    /// proporcionalmente ao quanto o corpo invade a folga. `walls` são as arestas à frente
+    /// do agente com a distância atual até cada uma.
+    /// A entrada é testada com a velocidade que o agente alcança de fato neste passo (o passo
+    /// limita a mudança de velocidade pela aceleração), mantida até ele conseguir parar
+    fn evaluate_obstacles(agent: &RvoAgent, cand_vel: Vec2, walls: &[(ObstacleEdge, f32)], dt: f32) -> f32 {
+        let mut penalty = 0.0;
+        let pos = agent.pos;
+        let current = agent.velocity;
+        let reachable = current + (cand_vel - current).clamp_length_max(agent.max_acceleration * dt);
+        let horizon = Self::obstacle_horizon(reachable.length(), agent.max_acceleration, dt);
+        let step_end = pos + reachable * horizon;
+        let lookahead_end = pos + cand_vel * OBSTACLE_LOOKAHEAD;
+        let clearance = agent.radius + RADIUS_MARGIN;
+
+        let reach = clearance + cand_vel.length().max(reachable.length()) * OBSTACLE_LOOKAHEAD.max(horizon);
+
+        for &(edge, dist_now) in walls {
+            if dist_now > reach {
+                continue;
+            }
+            if edge.enters(pos, step_end) {
+                return 100000.0; // Inaceitável
+            }
+
+            // Só penaliza quem se aproxima: um agente que já está encostado pode seguir ao longo da parede
+            let dist_min = edge.distance_to_segment(pos, lookahead_end);
+            if dist_min < clearance && dist_min < dist_now - 0.01 {
+                penalty += OBSTACLE_WEIGHT * agent.max_speed * (clearance - dist_min) / clearance;
+            }
+        }
+        penalty
+    }
+
+    /// Desvio da intenção mais o custo de colisão com os vizinhos. Para de somar (e devolve a soma
+    /// parcial) assim que ela chega a `bound`
+    fn evaluate_velocity(states: &RvoStates, index: usize, cand_vel: Vec2, neighbors: &[usize], bound: f32) -> f32 {
+        let pos = states.positions[index];
+        // 1. Custo base: Desvio da intenção original
+        let dist_to_pref = states.pref_velocities[index].distance(cand_vel);
+        let mut penalty = dist_to_pref; 
+
+        // Penaliza ficar parado se o objetivo é andar (evita inércia excessiva)
+        if cand_vel.length_squared() < 0.1 {
+            penalty += 50.0;
+        }
+
+        // 2. Custo de Colisão (os vizinhos já vêm filtrados pela distância de visão)
+        for &other in neighbors {
+            if penalty >= bound {
+                return penalty;
+            }
+            let other_pos = states.positions[other];
+            let other_velocity = states.velocities[other];
+
+            // --- TRATAMENTO DE AGENTES PARADOS ---
+            // Se o outro está parado (speed < 0.1), ele age como uma parede.
+            // Aumenta a margem de segurança para forçar o desvio mais cedo.
+            let other_is_static = other_velocity.length_squared() < 0.1;
+            
+            let effective_margin = if other_is_static {
+                RADIUS_MARGIN * 2.5 // Margem muito maior se o outro estiver parado
+            } else {
+                RADIUS_MARGIN
+            };
+
+            let combined_radius = states.radii[index] + states.radii[other] + effective_margin;
+            
+            let rel_pos = other_pos - pos;
+            let rel_vel = cand_vel - other_velocity; 
+            
+            let dist_curr = rel_pos.length();
+
+            // Colisão Imediata (Sobreposição)
+            if dist_curr < combined_radius {
+                penalty += 100000.0; // Inaceitável
+                continue;
+            }
+
+            // Previsão de Colisão (Ray Casting no Cone de Velocidade)
+            let rel_vel_sq = rel_vel.length_squared();
+
+            if rel_vel_sq > 0.0001 {
+                // Tempo até o ponto mais próximo
+                let t = rel_pos.dot(rel_vel) / rel_vel_sq;
+
+                // Só nos importamos se a colisão for no futuro próximo
+                if t > 0.0 && t < TIME_HORIZON {
+                    let closest_point = rel_vel * t;
+                    let dist_line = (rel_pos - closest_point).length();
+
+                    if dist_line < combined_radius {
+                        // Penalidade baseada no tempo.
+                        // Se for bater AGORA (t pequeno), penalidade gigante.
+                        // Se for bater daqui a pouco, penalidade alta.
+                        
+                        let impact_penalty = if other_is_static {
+                            // Se o outro está parado, penaliza dobrado qualquer rota de colisão
+                            10000.0 / (t + 0.05)
+                        } else {
+                            5000.0 / (t + 0.1)
+                        };
+
+                        penalty += impact_penalty;
+                    }
+                }
+            }
+        }
+
+        penalty
+    }
+}
\ No newline at end of file
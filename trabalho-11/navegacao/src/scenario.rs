@@ -0,0 +1,640 @@
+use crate::abstract_factory::SimulationFactory;
+use crate::agent::AgentArchetype;
+use crate::allocation::{self, AllocationMethod, CostModel};
+use crate::agent_decorator::{
+    AgentComponent, DirectionDeviateDecorator, FormationDecorator, SpeedBoostDecorator, VisualAlertDecorator,
+};
+use crate::agent_factory::{AgentFactory, CustomAgentFactory};
+use crate::collision_avoidance::AvoidanceMode;
+use crate::energy::{self, Route};
+use crate::formation::{Formation, FormationShape};
+use crate::grid::{CellType, Grid};
+use crate::grid_factory::MapGenerator;
+use crate::hex::{DEFAULT_HEX_SIZE, HexLayout, HexOrientation};
+use crate::initialization::init_system_with_map;
+use crate::navmesh::PathPlanner;
+use crate::observer::RespawnHandler;
+use crate::path_manager::{PathKey, PathManager};
+use crate::{GRID_HEIGHT, GRID_WIDTH, GridMode};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Cenário descrito em arquivo (TOML ou JSON, escolhido pela extensão).
+/// Substitui as funções fixas do `benchmark` quando se quer montar outro experimento
+/// sem recompilar: grid, obstáculos, grupos de agentes, método, semente e duração.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    /// Nome mostrado no HUD e usado nos arquivos de saída (padrão: nome do arquivo)
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub grid: GridSpec,
+    /// Prevenção de colisão: rvo, pheromone, none ou off
+    #[serde(default = "default_avoidance")]
+    pub avoidance: String,
+    #[serde(default = "default_seed")]
+    pub seed: u64,
+    /// Duração da execução em segundos de simulação
+    #[serde(default = "default_duration")]
+    pub duration: f32,
+    #[serde(default)]
+    pub agents: Vec<AgentGroup>,
+
+    /// Pasta do arquivo (o mapa é procurado relativo a ela)
+    #[serde(skip)]
+    base_dir: PathBuf,
+    /// Obstáculos lidos do arquivo de mapa
+    #[serde(skip)]
+    map_obstacles: Vec<(usize, usize)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GridSpec {
+    #[serde(default = "default_width")]
+    pub width: usize,
+    #[serde(default = "default_height")]
+    pub height: usize,
+    /// Mapa em texto (`#`, `@`, `O`, `T` e `W` são obstáculos); define o tamanho do grid
+    #[serde(default)]
+    pub map: Option<PathBuf>,
+    /// Vizinhança do A*: cardinal, diagonal ou hexagonal
+    #[serde(default = "default_grid_mode")]
+    pub mode: String,
+    /// Planejador das rotas: grid (A* nas células) ou navmesh (malha de navegação)
+    #[serde(default = "default_planner")]
+    pub planner: String,
+    /// Mapa gerado antes dos obstáculos do arquivo: empty, maze, caves, rooms, rectangles ou terrain
+    /// (usa a semente do cenário)
+    #[serde(default = "default_generator")]
+    pub generator: String,
+    /// Só no hexagonal: orientação (pointy ou flat) e raio dos hexágonos em pixels
+    #[serde(default = "default_hex_orientation")]
+    pub hex_orientation: String,
+    #[serde(default = "default_hex_size")]
+    pub hex_size: f32,
+    /// Obstáculos extras, por célula `[x, y]` ou retângulo `{ x, y, width, height }`
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleSpec>,
+    /// Estações de recarga, no mesmo formato dos obstáculos
+    #[serde(default)]
+    pub stations: Vec<ObstacleSpec>,
+}
+
+impl Default for GridSpec {
+    fn default() -> Self {
+        Self {
+            width: GRID_WIDTH,
+            height: GRID_HEIGHT,
+            map: None,
+            mode: default_grid_mode(),
+            planner: default_planner(),
+            generator: default_generator(),
+            hex_orientation: default_hex_orientation(),
+            hex_size: default_hex_size(),
+            obstacles: Vec::new(),
+            stations: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ObstacleSpec {
+    Cell([usize; 2]),
+    Rect(Region),
+}
+
+/// Retângulo de células (inclusive o canto `x`, `y`)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+pub struct Region {
+    pub x: usize,
+    pub y: usize,
+    #[serde(default = "default_one")]
+    pub width: usize,
+    #[serde(default = "default_one")]
+    pub height: usize,
+}
+
+impl ObstacleSpec {
+    fn apply(&self, grid: &mut Grid, cell_type: CellType) {
+        match self {
+            ObstacleSpec::Cell([x, y]) => grid.set_cell(*x, *y, cell_type),
+            ObstacleSpec::Rect(region) => {
+                for y in region.y..region.y + region.height {
+                    for x in region.x..region.x + region.width {
+                        grid.set_cell(x, y, cell_type);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Region {
+    /// Células livres da região, em ordem de linha
+    fn empty_cells(&self, grid: &Grid) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for y in self.y..(self.y + self.height).min(grid.height) {
+            for x in self.x..(self.x + self.width).min(grid.width) {
+                if !grid.is_obstacle(x, y) {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Grupo de agentes criados pela mesma fábrica e com a mesma stack de Decorators
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AgentGroup {
+    /// Fábrica de agentes: blue, red, scout ou carrier
+    #[serde(default = "default_factory")]
+    pub factory: String,
+    pub count: usize,
+    /// Regiões de início e destino (padrão: o grid inteiro)
+    #[serde(default)]
+    pub start: Option<Region>,
+    #[serde(default)]
+    pub goal: Option<Region>,
+    /// `random` sorteia células nas regiões; `ordered` pega a i-ésima célula
+    /// livre de cada região (fileiras que atravessam o grid, como nas teclas 1 e 2)
+    #[serde(default)]
+    pub placement: Placement,
+    /// Valores que trocam os do arquétipo da fábrica (padrão: os do arquétipo)
+    #[serde(default)]
+    pub speed: Option<f32>,
+    #[serde(default)]
+    pub radius: Option<f32>,
+    #[serde(default)]
+    pub detection_radius: Option<f32>,
+    #[serde(default)]
+    pub acceleration: Option<f32>,
+    #[serde(default)]
+    pub fuel_capacity: Option<f32>,
+    /// Decorators do mais interno para o mais externo
+    #[serde(default = "DecoratorSpec::default_stack")]
+    pub decorators: Vec<DecoratorSpec>,
+    /// Alocação de tarefas: escolhe `count` inícios e `count` destinos (como em `placement`)
+    /// e distribui os destinos com `hungarian`, `greedy` ou `auction` em vez de formar os pares na ordem
+    #[serde(default)]
+    pub assignment: Option<String>,
+    /// Custo da alocação: `path` (comprimento do caminho A*) ou `heuristic` (linha reta)
+    #[serde(default = "default_cost_model")]
+    pub cost: String,
+    /// Formação do grupo: `line`, `wedge` ou `column`. O primeiro agente é o líder e vai até o
+    /// destino; os outros saem das demais células de início e seguem o líder
+    #[serde(default)]
+    pub formation: Option<String>,
+    /// Distância entre vagas vizinhas da formação (padrão: 4 raios do agente)
+    #[serde(default)]
+    pub spacing: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    #[default]
+    Random,
+    Ordered,
+}
+
+/// Decorators disponíveis no arquivo: `"direction_deviate"`, `{ speed_boost = 2.0 }`, `"visual_alert"`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DecoratorSpec {
+    DirectionDeviate,
+    SpeedBoost(f32),
+    VisualAlert,
+}
+
+impl DecoratorSpec {
+    /// A stack usada na janela e nos benchmarks
+    pub fn default_stack() -> Vec<DecoratorSpec> {
+        vec![
+            DecoratorSpec::DirectionDeviate,
+            DecoratorSpec::SpeedBoost(2.0),
+            DecoratorSpec::VisualAlert,
+        ]
+    }
+
+    fn wrap(&self, component: Box<dyn AgentComponent>) -> Box<dyn AgentComponent> {
+        match *self {
+            DecoratorSpec::DirectionDeviate => Box::new(DirectionDeviateDecorator::new(component)),
+            DecoratorSpec::SpeedBoost(multiplier) => {
+                Box::new(SpeedBoostDecorator::new(component, multiplier))
+            }
+            DecoratorSpec::VisualAlert => Box::new(VisualAlertDecorator::new(component)),
+        }
+    }
+}
+
+impl AgentGroup {
+    /// Fábrica do grupo, com os valores do arquivo aplicados sobre o arquétipo
+    fn agent_factory(&self, factories: &dyn SimulationFactory) -> Box<dyn AgentFactory> {
+        let factory = factories
+            .create_agent_factory(&self.factory)
+            .unwrap_or_else(|| factories.create_blue_agent_factory());
+
+        let base = factory.archetype();
+        let archetype = AgentArchetype {
+            max_speed: self.speed.unwrap_or(base.max_speed),
+            radius: self.radius.unwrap_or(base.radius),
+            detection_radius: self.detection_radius.unwrap_or(base.detection_radius),
+            acceleration: self.acceleration.unwrap_or(base.acceleration),
+            fuel_capacity: self.fuel_capacity.unwrap_or(base.fuel_capacity),
+            ..base
+        };
+        if archetype == base {
+            factory
+        } else {
+            Box::new(CustomAgentFactory::new(archetype))
+        }
+    }
+}
+
+/// Planeja o caminho (com paradas de recarga) na velocidade que o agente terá
+/// depois dos Decorators: o `SpeedBoost` aumenta o gasto de energia por pixel.
+pub fn plan_agent_route(
+    grid: &Grid,
+    start: (usize, usize),
+    goal: (usize, usize),
+    grid_mode: GridMode,
+    factory: &dyn AgentFactory,
+    decorators: &[DecoratorSpec],
+) -> Option<Route> {
+    energy::plan_route(grid, start, goal, grid_mode, &boosted_archetype(factory, decorators))
+}
+
+/// Mesmo planejamento para vários pares (início, destino) em lote (`energy::plan_routes`)
+pub fn plan_agent_routes(
+    grid: &Grid,
+    queries: &[PathKey],
+    grid_mode: GridMode,
+    factory: &dyn AgentFactory,
+    decorators: &[DecoratorSpec],
+) -> Vec<Option<Route>> {
+    energy::plan_routes(grid, queries, grid_mode, &boosted_archetype(factory, decorators))
+}
+
+fn boosted_archetype(factory: &dyn AgentFactory, decorators: &[DecoratorSpec]) -> AgentArchetype {
+    let base = factory.archetype();
+    let boost: f32 = decorators
+        .iter()
+        .map(|spec| match *spec {
+            DecoratorSpec::SpeedBoost(multiplier) => multiplier,
+            _ => 1.0,
+        })
+        .product();
+    AgentArchetype { max_speed: base.max_speed * boost, ..base }
+}
+
+/// Monta o agente base e aplica os Decorators na ordem dada.
+/// O Observer de respawn/logs fica sempre na camada mais externa.
+pub fn build_agent_stack(
+    factory: &dyn AgentFactory,
+    start: Vec2,
+    route: Route,
+    id: usize,
+    decorators: &[DecoratorSpec],
+) -> Box<dyn AgentComponent> {
+    let agent = factory.create_agent(start, route.waypoints, id).with_charging_stops(route.charging_stops);
+    let base: Box<dyn AgentComponent> = Box::new(agent);
+    let mut agent = decorators.iter().fold(base, |component, spec| spec.wrap(component));
+    agent.add_observer(Box::new(RespawnHandler));
+    agent
+}
+
+fn default_avoidance() -> String { AvoidanceMode::Rvo.name().to_string() }
+fn default_seed() -> u64 { 42 }
+fn default_duration() -> f32 { 30.0 }
+fn default_width() -> usize { GRID_WIDTH }
+fn default_height() -> usize { GRID_HEIGHT }
+fn default_grid_mode() -> String { "cardinal".to_string() }
+fn default_planner() -> String { PathPlanner::Grid.name().to_string() }
+fn default_generator() -> String { MapGenerator::Empty.name().to_string() }
+fn default_hex_orientation() -> String { HexOrientation::PointyTop.name().to_string() }
+fn default_hex_size() -> f32 { DEFAULT_HEX_SIZE }
+fn default_one() -> usize { 1 }
+fn default_factory() -> String { "blue".to_string() }
+fn default_cost_model() -> String { "path".to_string() }
+
+/// Resultado da montagem de um cenário
+pub struct BuiltScenario {
+    pub grid: Grid,
+    pub agents: Vec<Box<dyn AgentComponent>>,
+}
+
+impl ScenarioFile {
+    /// Lê e valida o arquivo; o formato vem da extensão (`.toml` ou `.json`)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        let mut scenario: ScenarioFile = match extension.as_str() {
+            "toml" => toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?,
+            "json" => serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?,
+            _ => return Err(format!("{}: use um arquivo .toml ou .json", path.display())),
+        };
+
+        scenario.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        if scenario.name.is_none() {
+            scenario.name = path.file_stem().map(|s| s.to_string_lossy().into_owned());
+        }
+        if let Some(map) = &scenario.grid.map {
+            let map = TextMap::load(&scenario.base_dir.join(map))?;
+            scenario.grid.width = map.width;
+            scenario.grid.height = map.height;
+            scenario.map_obstacles = map.obstacles;
+        }
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.grid.width == 0 || self.grid.height == 0 {
+            return Err("O grid precisa ter largura e altura positivas".to_string());
+        }
+        GridMode::parse(&self.grid.mode).ok_or_else(|| format!("Grid desconhecido: '{}'", self.grid.mode))?;
+        PathPlanner::parse(&self.grid.planner)
+            .ok_or_else(|| format!("Planejador desconhecido: '{}' (use grid ou navmesh)", self.grid.planner))?;
+        MapGenerator::parse(&self.grid.generator).ok_or_else(|| {
+            format!("Gerador de mapa desconhecido: '{}' (use empty, maze, caves, rooms, rectangles ou terrain)", self.grid.generator)
+        })?;
+        HexOrientation::parse(&self.grid.hex_orientation)
+            .ok_or_else(|| format!("Orientação hexagonal desconhecida: '{}' (use pointy ou flat)", self.grid.hex_orientation))?;
+        if self.grid.hex_size <= 0.0 {
+            return Err("hex_size precisa ser positivo".to_string());
+        }
+        AvoidanceMode::parse(&self.avoidance)
+            .ok_or_else(|| format!("Modo de prevenção desconhecido: '{}'", self.avoidance))?;
+        if self.duration <= 0.0 {
+            return Err("A duração precisa ser positiva".to_string());
+        }
+        for group in &self.agents {
+            if !matches!(group.factory.as_str(), "blue" | "red" | "scout" | "carrier") {
+                return Err(format!(
+                    "Fábrica de agentes desconhecida: '{}' (use blue, red, scout ou carrier)",
+                    group.factory
+                ));
+            }
+            if let Some(method) = &group.assignment {
+                AllocationMethod::parse(method)
+                    .ok_or_else(|| format!("Alocação desconhecida: '{}' (use hungarian, greedy ou auction)", method))?;
+            }
+            if let Some(shape) = &group.formation {
+                FormationShape::parse(shape)
+                    .ok_or_else(|| format!("Formação desconhecida: '{}' (use line, wedge ou column)", shape))?;
+                if group.assignment.is_some() {
+                    return Err(format!("O grupo '{}' não pode ter formação e alocação ao mesmo tempo", group.factory));
+                }
+            }
+            CostModel::parse(&group.cost)
+                .ok_or_else(|| format!("Custo de alocação desconhecido: '{}' (use path ou heuristic)", group.cost))?;
+            let values =
+                [group.speed, group.radius, group.detection_radius, group.acceleration, group.fuel_capacity, group.spacing];
+            if values.iter().flatten().any(|&v| v <= 0.0) {
+                return Err(format!("Os valores do grupo '{}' precisam ser positivos", group.factory));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("cenario")
+    }
+
+    pub fn grid_mode(&self) -> GridMode {
+        GridMode::parse(&self.grid.mode).unwrap_or(GridMode::Cardinal)
+    }
+
+    pub fn planner(&self) -> PathPlanner {
+        PathPlanner::parse(&self.grid.planner).unwrap_or(PathPlanner::Grid)
+    }
+
+    pub fn map_generator(&self) -> MapGenerator {
+        MapGenerator::parse(&self.grid.generator).unwrap_or_default()
+    }
+
+    pub fn hex_layout(&self) -> HexLayout {
+        let orientation = HexOrientation::parse(&self.grid.hex_orientation).unwrap_or(HexOrientation::PointyTop);
+        HexLayout::new(orientation, self.grid.hex_size)
+    }
+
+    pub fn avoidance(&self) -> AvoidanceMode {
+        AvoidanceMode::parse(&self.avoidance).unwrap_or(AvoidanceMode::Rvo)
+    }
+
+    /// Total de agentes pedidos (alguns podem não ter caminho até o destino)
+    pub fn agent_count(&self) -> usize {
+        self.agents.iter().map(|g| g.count).sum()
+    }
+
+    /// Monta grid e agentes. Semente e vizinhança vêm de fora para que a linha
+    /// de comando (e o `--verify`) possam trocá-las sem editar o arquivo.
+    pub fn build(&self, grid_mode: GridMode, seed: u64) -> BuiltScenario {
+        rand::srand(seed);
+        PathManager::instance().clear_cache();
+
+        let init_ctx = init_system_with_map(self.grid.width, self.grid.height, self.map_generator(), seed);
+        let mut grid = init_ctx.grid.expect("Grid error");
+        let factory = init_ctx.factory.expect("Factory error");
+
+        for &(x, y) in &self.map_obstacles {
+            grid.set_cell(x, y, CellType::Obstacle);
+        }
+        for obstacle in &self.grid.obstacles {
+            obstacle.apply(&mut grid, CellType::Obstacle);
+        }
+        for station in &self.grid.stations {
+            station.apply(&mut grid, CellType::ChargingStation);
+        }
+
+        let whole_grid = Region { x: 0, y: 0, width: grid.width, height: grid.height };
+        let mut agents: Vec<Box<dyn AgentComponent>> = Vec::new();
+
+        for group in &self.agents {
+            let creator = group.agent_factory(factory.as_ref());
+            let starts = group.start.unwrap_or(whole_grid).empty_cells(&grid);
+            let goals = group.goal.unwrap_or(whole_grid).empty_cells(&grid);
+            if starts.is_empty() || goals.is_empty() {
+                println!("Grupo sem células livres no início ou no destino, ignorado.");
+                continue;
+            }
+
+            let mut spawned = 0;
+            if let Some(shape) = group.formation.as_deref().and_then(FormationShape::parse) {
+                let (starts, goal) = match group.placement {
+                    Placement::Ordered => (starts.into_iter().take(group.count).collect(), goals[0]),
+                    Placement::Random => (sample_cells(starts, group.count), goals[rand::gen_range(0, goals.len())]),
+                };
+                spawned = spawn_formation(&grid, &mut agents, creator.as_ref(), group, grid_mode, &starts, goal);
+                println!(
+                    "Grupo '{}': formação {} com {} de {} agentes.",
+                    group.factory,
+                    shape.name(),
+                    spawned,
+                    group.count
+                );
+                continue;
+            }
+            if let Some(method) = group.assignment.as_deref().and_then(AllocationMethod::parse) {
+                let (starts, goals) = match group.placement {
+                    Placement::Ordered => (
+                        starts.into_iter().take(group.count).collect(),
+                        goals.into_iter().take(group.count).collect(),
+                    ),
+                    Placement::Random => (sample_cells(starts, group.count), sample_cells(goals, group.count)),
+                };
+                let model = CostModel::parse(&group.cost).unwrap_or(CostModel::PathLength);
+                let radii = vec![creator.archetype().radius; starts.len()];
+                let costs = allocation::cost_matrix(&grid, &starts, &radii, &goals, grid_mode, model);
+                let assignment = allocation::assign(&costs, method);
+                println!(
+                    "Grupo '{}': alocação {}, custo total {:.0} px",
+                    group.factory,
+                    method.name(),
+                    allocation::total_cost(&costs, &assignment)
+                );
+                let queries: Vec<PathKey> = starts
+                    .iter()
+                    .zip(assignment)
+                    .filter_map(|(&start, goal)| Some((start, goals[goal?])))
+                    .collect();
+                spawned = spawn_agents(&grid, &mut agents, creator.as_ref(), group, grid_mode, &queries);
+                println!("Grupo '{}': {} de {} agentes criados.", group.factory, spawned, group.count);
+                continue;
+            }
+            match group.placement {
+                Placement::Ordered => {
+                    let queries: Vec<PathKey> = starts.iter().copied().zip(goals.iter().copied()).take(group.count).collect();
+                    spawned = spawn_agents(&grid, &mut agents, creator.as_ref(), group, grid_mode, &queries);
+                }
+                Placement::Random => {
+                    let mut attempts = 0;
+                    while spawned < group.count && attempts < group.count * 10 {
+                        // Sorteia de uma vez os pares que faltam, na mesma ordem em que um por vez os sortearia;
+                        // pares em componentes diferentes nem chegam ao planejamento
+                        let mut queries = Vec::new();
+                        while spawned + queries.len() < group.count && attempts < group.count * 10 {
+                            attempts += 1;
+                            let start = starts[rand::gen_range(0, starts.len())];
+                            let goal = goals[rand::gen_range(0, goals.len())];
+                            if start != goal && grid.is_reachable(start, goal, grid_mode) {
+                                queries.push((start, goal));
+                            }
+                        }
+                        spawned += spawn_agents(&grid, &mut agents, creator.as_ref(), group, grid_mode, &queries);
+                    }
+                }
+            }
+            println!("Grupo '{}': {} de {} agentes criados.", group.factory, spawned, group.count);
+        }
+
+        BuiltScenario { grid, agents }
+    }
+}
+
+/// Sorteia até `count` células diferentes (Fisher-Yates parcial)
+pub fn sample_cells(mut cells: Vec<(usize, usize)>, count: usize) -> Vec<(usize, usize)> {
+    let count = count.min(cells.len());
+    for i in 0..count {
+        let j = rand::gen_range(i, cells.len());
+        cells.swap(i, j);
+    }
+    cells.truncate(count);
+    cells
+}
+
+/// Cria os agentes do grupo dos pares que têm caminho (rotas planejadas em lote), na ordem dos pares
+fn spawn_agents(
+    grid: &Grid,
+    agents: &mut Vec<Box<dyn AgentComponent>>,
+    factory: &dyn AgentFactory,
+    group: &AgentGroup,
+    grid_mode: GridMode,
+    queries: &[PathKey],
+) -> usize {
+    let routes = plan_agent_routes(grid, queries, grid_mode, factory, &group.decorators);
+    let mut spawned = 0;
+    for (&(start, _), route) in queries.iter().zip(routes) {
+        let Some(route) = route else {
+            continue;
+        };
+        let start_pos = crate::grid_to_screen_center(start, grid_mode);
+        let id = agents.len();
+        agents.push(build_agent_stack(factory, start_pos, route, id, &group.decorators));
+        spawned += 1;
+    }
+    spawned
+}
+
+/// Cria uma formação: o agente da primeira célula é o líder, com caminho até o destino;
+/// os seguidores começam sem caminho próprio e ocupam as vagas em volta dele
+fn spawn_formation(
+    grid: &Grid,
+    agents: &mut Vec<Box<dyn AgentComponent>>,
+    factory: &dyn AgentFactory,
+    group: &AgentGroup,
+    grid_mode: GridMode,
+    starts: &[(usize, usize)],
+    goal: (usize, usize),
+) -> usize {
+    let shape = group.formation.as_deref().and_then(FormationShape::parse).unwrap_or(FormationShape::Column);
+    let Some((&leader_start, followers)) = starts.split_first() else {
+        return 0;
+    };
+    let Some(route) = plan_agent_route(grid, leader_start, goal, grid_mode, factory, &group.decorators) else {
+        return 0;
+    };
+    let radius = factory.archetype().radius;
+    let formation = Formation::new(shape, group.spacing.unwrap_or(4.0 * radius), starts.len(), radius);
+
+    let routes = std::iter::once(route).chain(followers.iter().map(|_| Route::default()));
+    for (slot, (&start, route)) in starts.iter().zip(routes).enumerate() {
+        let start_pos = crate::grid_to_screen_center(start, grid_mode);
+        let member = build_agent_stack(factory, start_pos, route, agents.len(), &group.decorators);
+        agents.push(Box::new(FormationDecorator::new(member, formation.clone(), slot)));
+    }
+    starts.len()
+}
+
+/// Mapa em texto já lido do disco
+struct TextMap {
+    width: usize,
+    height: usize,
+    obstacles: Vec<(usize, usize)>,
+}
+
+impl TextMap {
+    /// Aceita o formato dos mapas do Moving AI
+    /// (cabeçalho `type`/`height`/`width` seguido de `map`) ou só as linhas do mapa.
+    fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut lines: Vec<&str> = text.lines().collect();
+        if let Some(i) = lines.iter().position(|l| l.trim() == "map") {
+            lines.drain(..=i);
+        }
+        let rows: Vec<&str> = lines.into_iter().map(str::trim_end).filter(|l| !l.is_empty()).collect();
+
+        let height = rows.len();
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        if width == 0 || height == 0 {
+            return Err(format!("{}: mapa vazio", path.display()));
+        }
+
+        let mut obstacles = Vec::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if matches!(c, '#' | '@' | 'O' | 'T' | 'W') {
+                    obstacles.push((x, y));
+                }
+            }
+        }
+        Ok(Self { width, height, obstacles })
+    }
+}
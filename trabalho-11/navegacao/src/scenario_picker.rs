@@ -0,0 +1,95 @@
+use crate::scenario::ScenarioFile;
+use macroquad::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Pasta onde a tecla F procura os cenários
+pub const SCENARIO_DIR: &str = "scenarios";
+
+/// O que aconteceu no seletor neste frame
+pub enum PickerAction {
+    None,
+    Cancel,
+    Loaded(Box<ScenarioFile>),
+}
+
+/// Seletor de arquivos de cenário desenhado na própria janela:
+/// `↑`/`↓` escolhem, `Enter` carrega e `Esc` fecha.
+pub struct ScenarioPicker {
+    dir: PathBuf,
+    files: Vec<PathBuf>,
+    selected: usize,
+    error: Option<String>,
+}
+
+impl ScenarioPicker {
+    /// Lista os arquivos `.toml` e `.json` da pasta, em ordem alfabética
+    pub fn open(dir: &Path) -> Self {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| {
+                        matches!(p.extension().and_then(|e| e.to_str()), Some("toml") | Some("json"))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+
+        let error = files
+            .is_empty()
+            .then(|| format!("Nenhum cenário (.toml ou .json) em {}/", dir.display()));
+
+        Self {
+            dir: dir.to_path_buf(),
+            files,
+            selected: 0,
+            error,
+        }
+    }
+
+    /// Trata o teclado; erros de leitura ficam na tela e o seletor continua aberto
+    pub fn update(&mut self) -> PickerAction {
+        if is_key_pressed(KeyCode::Escape) {
+            return PickerAction::Cancel;
+        }
+        if self.files.is_empty() {
+            return PickerAction::None;
+        }
+        if is_key_pressed(KeyCode::Down) {
+            self.selected = (self.selected + 1) % self.files.len();
+        }
+        if is_key_pressed(KeyCode::Up) {
+            self.selected = (self.selected + self.files.len() - 1) % self.files.len();
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            match ScenarioFile::load(&self.files[self.selected]) {
+                Ok(scenario) => return PickerAction::Loaded(Box::new(scenario)),
+                Err(e) => self.error = Some(e),
+            }
+        }
+        PickerAction::None
+    }
+
+    pub fn draw(&self) {
+        let width = 520.0;
+        let height = 90.0 + self.files.len() as f32 * 24.0;
+        let x = (screen_width() - width) / 2.0;
+        let y = 80.0;
+
+        draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.85));
+        draw_rectangle_lines(x, y, width, height, 2.0, WHITE);
+        draw_text(&format!("Cenários em {}/", self.dir.display()), x + 15.0, y + 28.0, 24.0, YELLOW);
+
+        for (i, file) in self.files.iter().enumerate() {
+            let name = file.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+            let color = if i == self.selected { GREEN } else { WHITE };
+            let marker = if i == self.selected { ">" } else { " " };
+            draw_text(&format!("{} {}", marker, name), x + 15.0, y + 55.0 + i as f32 * 24.0, 22.0, color);
+        }
+
+        let footer = self.error.as_deref().unwrap_or("[Enter] Carregar | [Esc] Fechar");
+        let color = if self.error.is_some() { RED } else { GRAY };
+        draw_text(footer, x + 15.0, y + height - 12.0, 18.0, color);
+    }
+}
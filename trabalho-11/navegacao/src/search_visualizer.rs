@@ -0,0 +1,197 @@
+use crate::grid_adapter::GridAdapter;
+use crate::pathfinding_adapter::{SearchAlgorithm, SearchEvent, SearchStepper};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+/// Velocidades da animação, em eventos por segundo (0 = só avança com a seta)
+const SPEEDS: [f32; 6] = [0.0, 2.0, 10.0, 50.0, 250.0, 1000.0];
+
+/// Situação de uma célula no visualizador, montada só a partir dos eventos da busca
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepCell {
+    /// Na fronteira
+    Open,
+    /// Já expandida; o número é a ordem de expansão
+    Closed(usize),
+}
+
+/// Modo da janela (tecla B) que anima a busca um evento por vez no grid atual.
+/// Clique esquerdo marca o início e, depois, o destino; `Tab` troca entre A*, Dijkstra e BFS,
+/// `→` avança um evento, `↑`/`↓` mudam a velocidade e `Home` recomeça.
+pub struct SearchVisualizer {
+    algorithm: SearchAlgorithm,
+    start: Option<(usize, usize)>,
+    goal: Option<(usize, usize)>,
+    stepper: Option<SearchStepper>,
+    /// Revisão do grid em que a busca começou: se o grid muda, ela recomeça
+    revision: u64,
+    /// Vizinhança mudou (tipo de grid, orientação dos hexágonos): recomeça no próximo quadro
+    stale: bool,
+    speed: usize,
+    /// Eventos devidos pela animação que ainda não saíram (fração de evento)
+    budget: f32,
+    cells: HashMap<(usize, usize), StepCell>,
+    expansions: usize,
+    events: usize,
+    last_event: Option<SearchEvent>,
+    path: Vec<(usize, usize)>,
+}
+
+impl SearchVisualizer {
+    pub fn new() -> Self {
+        Self {
+            algorithm: SearchAlgorithm::AStar,
+            start: None,
+            goal: None,
+            stepper: None,
+            revision: 0,
+            stale: false,
+            speed: 2,
+            budget: 0.0,
+            cells: HashMap::new(),
+            expansions: 0,
+            events: 0,
+            last_event: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Teclas e cliques do modo, e os eventos que a animação deve mostrar neste quadro.
+    /// `mouse_cell` é a célula sob o cursor (se estiver no mapa) e `revision` a revisão atual do grid.
+    pub fn update(&mut self, adapter: &dyn GridAdapter, revision: u64, mouse_cell: Option<(usize, usize)>, dt: f32) {
+        if is_mouse_button_pressed(MouseButton::Left)
+            && let Some(cell) = mouse_cell.filter(|&cell| adapter.is_valid_position(cell))
+        {
+            if self.start.is_none() || self.goal.is_some() {
+                self.start = Some(cell);
+                self.goal = None;
+            } else {
+                self.goal = Some(cell);
+            }
+            self.restart(adapter, revision);
+        }
+        if is_key_pressed(KeyCode::Tab) {
+            self.algorithm = self.algorithm.next();
+            self.restart(adapter, revision);
+        }
+        if is_key_pressed(KeyCode::Home) || self.stale || (self.stepper.is_some() && revision != self.revision) {
+            self.restart(adapter, revision);
+        }
+        if is_key_pressed(KeyCode::Up) {
+            self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+        }
+        if is_key_pressed(KeyCode::Down) {
+            self.speed = self.speed.saturating_sub(1);
+        }
+
+        let mut due = 0;
+        if is_key_pressed(KeyCode::Right) {
+            // Avançar na mão pausa a animação
+            self.speed = 0;
+            due = 1;
+        } else if SPEEDS[self.speed] > 0.0 {
+            self.budget += SPEEDS[self.speed] * dt;
+            due = self.budget as usize;
+            self.budget -= due as f32;
+        }
+        if let Some(stepper) = &mut self.stepper {
+            let events: Vec<SearchEvent> = stepper.events(adapter).take(due).collect();
+            for event in events {
+                self.apply(event);
+            }
+        }
+    }
+
+    /// Começa a busca de novo do início marcado (precisa de início e destino)
+    fn restart(&mut self, adapter: &dyn GridAdapter, revision: u64) {
+        self.cells.clear();
+        self.path.clear();
+        self.last_event = None;
+        self.expansions = 0;
+        self.events = 0;
+        self.budget = 0.0;
+        self.revision = revision;
+        self.stale = false;
+        self.stepper = match (self.start, self.goal) {
+            (Some(start), Some(goal)) => Some(SearchStepper::new(self.algorithm, adapter, start, goal)),
+            _ => None,
+        };
+    }
+
+    /// A vizinhança das células mudou sem o grid mudar (troca de tipo de grid ou de orientação)
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    fn apply(&mut self, event: SearchEvent) {
+        match &event {
+            SearchEvent::Pop { cell, .. } => {
+                self.cells.insert(*cell, StepCell::Closed(self.expansions));
+                self.expansions += 1;
+            }
+            SearchEvent::Push { cell, .. } | SearchEvent::Relax { cell, .. } => {
+                self.cells.insert(*cell, StepCell::Open);
+            }
+            SearchEvent::Found { path } => {
+                if let Some(&goal) = path.last() {
+                    self.cells.insert(goal, StepCell::Closed(self.expansions));
+                    self.expansions += 1;
+                }
+                self.path = path.clone();
+            }
+            SearchEvent::Exhausted => {}
+        }
+        self.events += 1;
+        self.last_event = Some(event);
+    }
+
+    pub fn cells(&self) -> &HashMap<(usize, usize), StepCell> {
+        &self.cells
+    }
+
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+    pub fn start(&self) -> Option<(usize, usize)> {
+        self.start
+    }
+
+    pub fn goal(&self) -> Option<(usize, usize)> {
+        self.goal
+    }
+
+    pub fn last_event(&self) -> Option<&SearchEvent> {
+        self.last_event.as_ref()
+    }
+
+    pub fn path(&self) -> &[(usize, usize)] {
+        &self.path
+    }
+
+    /// Linha do HUD: algoritmo, último evento e contadores
+    pub fn status(&self) -> String {
+        let event = match &self.last_event {
+            _ if self.start.is_none() => "clique no início".to_string(),
+            _ if self.goal.is_none() => "clique no destino".to_string(),
+            None => "pronto".to_string(),
+            Some(SearchEvent::Pop { cell, g }) => format!("pop {:?} g={}", cell, g),
+            Some(SearchEvent::Push { cell, g, f, .. }) => format!("push {:?} g={} f={}", cell, g, f),
+            Some(SearchEvent::Relax { cell, from, g, f }) => format!("relax {:?} por {:?} g={} f={}", cell, from, g, f),
+            Some(SearchEvent::Found { path }) => format!("achou: {} células", path.len()),
+            Some(SearchEvent::Exhausted) => "sem caminho".to_string(),
+        };
+        let speed = match SPEEDS[self.speed] {
+            0.0 => "manual".to_string(),
+            speed => format!("{}/s", speed),
+        };
+        format!(
+            "Busca {}: {} | evento {} | expandidas {} | velocidade {} | [Clique] Início/destino [→] Passo [↑/↓] Velocidade [Tab] Algoritmo [Home] Recomeça [B] Sai",
+            self.algorithm.name(),
+            event,
+            self.events,
+            self.expansions,
+            speed
+        )
+    }
+}
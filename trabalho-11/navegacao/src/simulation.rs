@@ -0,0 +1,252 @@
+use crate::GridMode;
+use crate::agent_decorator::AgentComponent;
+use crate::collision_avoidance::CollisionAvoidanceStrategy;
+use crate::command::{CommandManager, MoveCommand};
+use crate::debug_overlay::DebugCapture;
+use crate::energy;
+use crate::grid::Grid;
+use crate::observer::AgentEvent;
+use crate::rvo::NeighborGrid;
+use macroquad::prelude::*;
+use std::collections::HashSet;
+
+/// Velocidade que leva o agente direto ao próximo waypoint do A*.
+/// Perto do alvo, limita a velocidade à que ainda dá para frear até ele
+/// (sem efeito para aceleração infinita, como no agente padrão).
+fn preferred_velocity(agent: &dyn AgentComponent) -> Vec2 {
+    if agent.is_finished() {
+        return Vec2::ZERO;
+    }
+    match agent.get_next_step_target() {
+        Some(target) => {
+            let diff = target - agent.get_pos();
+            if diff.length() > 0.1 {
+                let stopping_speed = (2.0 * agent.get_max_acceleration() * diff.length()).sqrt();
+                diff.normalize() * agent.get_max_speed().min(stopping_speed)
+            } else {
+                Vec2::ZERO
+            }
+        }
+        None => Vec2::ZERO,
+    }
+}
+
+/// Executa um passo completo da simulação com um `dt` fixo.
+/// É o mesmo código usado pela janela e pelo benchmark headless,
+/// por isso não pode depender do contexto do macroquad.
+/// Só a Strategy de prevenção de colisão muda entre os métodos comparados.
+pub fn step(
+    agents: &mut Vec<Box<dyn AgentComponent>>,
+    grid: &mut Grid,
+    command_manager: &mut CommandManager,
+    strategy: &mut dyn CollisionAvoidanceStrategy,
+    grid_mode: GridMode,
+    dt: f32,
+) {
+    // Estado de antes do passo, para desfazer o tick inteiro
+    command_manager.begin_tick(agents);
+
+    // --- 0. Agentes olham o grid (formações medem o corredor) ---
+    for agent in agents.iter_mut() {
+        agent.perceive(grid, grid_mode, dt);
+    }
+
+    // --- 1. Atualiza estado interno dos agentes ---
+    for agent in agents.iter_mut() {
+        agent.update(dt);
+    }
+
+    // --- 2. Velocidade desejada (A* puro, ignorando os outros) ---
+    let preferred: Vec<Vec2> = agents.iter().map(|a| preferred_velocity(a.as_ref())).collect();
+    let capture = DebugCapture::instance();
+    capture.record_preferred(&preferred);
+    capture.clear_candidates();
+
+    // --- 3. Velocidade segura calculada pela Strategy ---
+    let velocities = strategy.compute_velocities(agents, &preferred, grid, grid_mode, dt);
+
+    for (agent, safe_velocity) in agents.iter_mut().zip(velocities) {
+        if agent.is_finished() {
+            agent.set_velocity(Vec2::ZERO);
+            continue;
+        }
+
+        // Inércia: a velocidade só muda o que a aceleração do agente permite neste passo
+        let current_velocity = agent.get_velocity();
+        let max_change = agent.get_max_acceleration() * dt;
+        let mut velocity = current_velocity + (safe_velocity - current_velocity).clamp_length_max(max_change);
+
+        let current_pos = agent.get_pos();
+        let mut new_pos = current_pos + velocity * dt;
+
+        // Nenhum passo termina dentro de um obstáculo: se a velocidade levaria até lá, o agente
+        // desliza pela parede (fica só com um dos eixos) ou, se nem isso der, para.
+        // Quem já está dentro (como depois de uma pincelada em cima dele) pode sair
+        let blocked = |pos: Vec2| {
+            let (x, y) = crate::screen_to_grid(pos.x, pos.y, grid_mode);
+            grid.is_obstacle(x, y)
+        };
+        if blocked(new_pos) && !blocked(current_pos) {
+            velocity = [vec2(velocity.x, 0.0), vec2(0.0, velocity.y)]
+                .into_iter()
+                .find(|&slide| !blocked(current_pos + slide * dt))
+                .unwrap_or(Vec2::ZERO);
+            new_pos = current_pos + velocity * dt;
+        }
+        agent.set_velocity(velocity);
+
+        // Energia do passo: gasto pela distância e velocidade, menos a recarga se parar em uma estação
+        let (cell_x, cell_y) = crate::screen_to_grid(new_pos.x, new_pos.y, grid_mode);
+        let mut energy = energy::movement_cost(current_pos.distance(new_pos), dt);
+        if grid.is_charging_station(cell_x, cell_y) {
+            energy -= energy::RECHARGE_RATE * dt;
+        }
+
+        // Envia comando de movimento
+        let move_cmd = MoveCommand::new(agent.get_id(), current_pos, new_pos, energy);
+        command_manager.add_command(Box::new(move_cmd));
+    }
+
+    // --- 4. Executa os Comandos ---
+    command_manager.process_commands(agents, grid);
+}
+
+/// Sensor global de contatos entre agentes.
+/// Só notifica quando um par *entra* em contato, para não repetir o
+/// mesmo evento a cada frame enquanto os agentes continuam sobrepostos.
+pub struct CollisionMonitor {
+    in_detection: HashSet<(usize, usize)>,
+    in_contact: HashSet<(usize, usize)>,
+}
+
+impl CollisionMonitor {
+    pub fn new() -> Self {
+        Self {
+            in_detection: HashSet::new(),
+            in_contact: HashSet::new(),
+        }
+    }
+
+    /// Verifica os pares próximos, dispara `ProximityAlert`/`CollisionHit`
+    /// e retorna os pares (ids) que colidiram pela primeira vez neste passo.
+    /// Só os agentes a menos do maior alcance (contato ou detecção) são comparados, pela
+    /// grade de vizinhos do RVO, na mesma ordem de pares da lista inteira.
+    pub fn update(&mut self, agents: &[Box<dyn AgentComponent>]) -> Vec<(usize, usize)> {
+        let mut detection = HashSet::new();
+        let mut contact = HashSet::new();
+        let mut new_collisions = Vec::new();
+
+        let active: Vec<usize> = (0..agents.len()).filter(|&i| !agents[i].is_finished()).collect();
+        let positions: Vec<Vec2> = active.iter().map(|&i| agents[i].get_pos()).collect();
+        let range = active.iter().fold(0.0f32, |range, &i| {
+            let a = &agents[i];
+            range.max(2.0 * a.get_physical_radius()).max(a.get_detection_radius())
+        });
+        let neighbor_grid = NeighborGrid::with_range(&positions, range);
+        let mut nearby = Vec::new();
+
+        for (k, &i) in active.iter().enumerate() {
+            let a = &agents[i];
+            neighbor_grid.nearby(positions[k], &mut nearby);
+            for &other in nearby.iter().filter(|&&other| other > k) {
+                let b = &agents[active[other]];
+
+                let pair = (a.get_id(), b.get_id());
+                let dist = a.get_pos().distance(b.get_pos());
+
+                if dist < a.get_physical_radius() + b.get_physical_radius() {
+                    if !self.in_contact.contains(&pair) {
+                        a.notify(AgentEvent::CollisionHit(pair.1));
+                        b.notify(AgentEvent::CollisionHit(pair.0));
+                        new_collisions.push(pair);
+                    }
+                    contact.insert(pair);
+                }
+
+                if dist < a.get_detection_radius().max(b.get_detection_radius()) {
+                    if !self.in_detection.contains(&pair) {
+                        a.notify(AgentEvent::ProximityAlert(pair.1));
+                        b.notify(AgentEvent::ProximityAlert(pair.0));
+                    }
+                    detection.insert(pair);
+                }
+            }
+        }
+
+        self.in_detection = detection;
+        self.in_contact = contact;
+        new_collisions
+    }
+
+    /// Número de pares que estão sobrepostos agora
+    pub fn active_contacts(&self) -> usize {
+        self.in_contact.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.in_detection.clear();
+        self.in_contact.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{Agent, AgentArchetype};
+    use crate::collision_avoidance::{NoAvoidanceStrategy, RvoStrategy};
+    use crate::grid::CellType;
+
+    /// Grid de 16x6 células com uma parede inteira na coluna 8 (x de 160 a 180 px)
+    fn walled_grid() -> Grid {
+        let mut grid = Grid::new(16, 6);
+        for y in 0..grid.height {
+            grid.set_cell(8, y, CellType::Obstacle);
+        }
+        grid
+    }
+
+    fn cell_of(agent: &dyn AgentComponent) -> (usize, usize) {
+        let pos = agent.get_pos();
+        crate::screen_to_grid(pos.x, pos.y, GridMode::Cardinal)
+    }
+
+    #[test]
+    fn finite_acceleration_never_ends_a_step_inside_a_wall() {
+        let strategies: [fn() -> Box<dyn CollisionAvoidanceStrategy>; 2] =
+            [|| Box::new(NoAvoidanceStrategy), || Box::new(RvoStrategy::new())];
+        // De frente e em diagonal (o agente desliza pela parede)
+        for target in [vec2(300.0, 50.0), vec2(300.0, 110.0)] {
+            for make_strategy in strategies {
+                let mut grid = walled_grid();
+                let mut strategy = make_strategy();
+                let mut manager = CommandManager::new();
+                let agent = Agent::from_archetype(0, vec2(30.0, 50.0), vec![target], &AgentArchetype::carrier());
+                let mut agents: Vec<Box<dyn AgentComponent>> = vec![Box::new(agent)];
+
+                for _ in 0..600 {
+                    step(&mut agents, &mut grid, &mut manager, strategy.as_mut(), GridMode::Cardinal, 1.0 / 60.0);
+                    let (x, y) = cell_of(agents[0].as_ref());
+                    assert!(!grid.is_obstacle(x, y), "{} terminou o passo em ({}, {})", strategy.name(), x, y);
+                }
+                assert!(agents[0].get_pos().x > 140.0, "{} nem chegou perto da parede", strategy.name());
+            }
+        }
+    }
+
+    #[test]
+    fn agent_inside_a_painted_cell_can_leave() {
+        let mut grid = walled_grid();
+        let agent = Agent::from_archetype(0, vec2(90.0, 50.0), vec![vec2(30.0, 50.0)], &AgentArchetype::carrier());
+        let mut agents: Vec<Box<dyn AgentComponent>> = vec![Box::new(agent)];
+        let mut manager = CommandManager::new();
+        grid.set_cell(4, 2, CellType::Obstacle);
+        assert!(grid.is_obstacle(4, 2));
+
+        for _ in 0..120 {
+            step(&mut agents, &mut grid, &mut manager, &mut NoAvoidanceStrategy, GridMode::Cardinal, 1.0 / 60.0);
+        }
+        let (x, y) = cell_of(agents[0].as_ref());
+        assert!(!grid.is_obstacle(x, y));
+        assert!(agents[0].get_pos().x < 80.0);
+    }
+}
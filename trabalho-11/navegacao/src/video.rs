@@ -0,0 +1,161 @@
+use crate::frame_export::{Frame, FrameFormat, FrameStyle, Overlays};
+use plotters::coord::Shift;
+use plotters::prelude::{BitMapBackend, DrawingArea, IntoDrawingArea};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Como o vídeo é gravado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoFormat {
+    /// GIF animado em um arquivo só
+    Gif,
+    /// Sequência numerada de PNGs em uma pasta (para montar o vídeo com outra ferramenta)
+    PngSequence,
+}
+
+impl VideoFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "gif" => Some(VideoFormat::Gif),
+            "png" | "frames" => Some(VideoFormat::PngSequence),
+            _ => None,
+        }
+    }
+}
+
+/// Quadros por segundo e trecho gravado, em tempo simulado: o mesmo cenário e a mesma semente
+/// dão o mesmo vídeo, qualquer que seja a velocidade da máquina
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoOptions {
+    pub format: VideoFormat,
+    pub fps: f32,
+    /// Início e fim do trecho (s); sem fim, grava até parar
+    pub start: f32,
+    pub end: Option<f32>,
+}
+
+impl Default for VideoOptions {
+    fn default() -> Self {
+        Self { format: VideoFormat::Gif, fps: 10.0, start: 0.0, end: None }
+    }
+}
+
+pub const USAGE: &str = "Imagens e vídeo (no --benchmark e na janela):
+  --video <gif|png>              grava um GIF ou uma sequência de PNGs (janela: tecla I)
+  --video-fps <N>                quadros por segundo simulado (padrão: 10)
+  --video-from <s> --video-to <s>  trecho gravado, em tempo simulado
+  --overlays <lista|all|none>    camadas desenhadas: grid,pheromones,paths,detection (padrão: all)
+  --caption <on|off>             legenda com cenário, método, semente e tempo (padrão: on)
+  --frame-scale <fator>          pixels da imagem por pixel do mundo (padrão: 1)";
+
+/// Trata as opções de vídeo e de desenho comuns à janela e ao headless.
+/// Devolve `false` se a opção não é de vídeo (quem chamou continua procurando).
+pub fn parse_option(flag: &str, value: &str, video: &mut VideoOptions, style: &mut FrameStyle) -> Result<bool, String> {
+    let number = |value: &str| value.parse::<f32>().map_err(|_| format!("Valor inválido para '{}': '{}'", flag, value));
+    match flag {
+        "--video" => {
+            video.format =
+                VideoFormat::parse(value).ok_or_else(|| format!("Formato de vídeo desconhecido: '{}'", value))?;
+        }
+        "--video-fps" => video.fps = number(value)?,
+        "--video-from" => video.start = number(value)?,
+        "--video-to" => video.end = Some(number(value)?),
+        "--overlays" => style.overlays = Overlays::parse(value)?,
+        "--caption" => {
+            style.caption = match value {
+                "on" => true,
+                "off" => false,
+                _ => return Err(format!("Use --caption on|off, não '{}'", value)),
+            };
+        }
+        "--frame-scale" => style.scale = number(value)?,
+        _ => return Ok(false),
+    }
+
+    if video.fps <= 0.0 {
+        return Err("--video-fps precisa ser positivo".to_string());
+    }
+    if style.scale <= 0.0 {
+        return Err("--frame-scale precisa ser positivo".to_string());
+    }
+    if let Some(end) = video.end
+        && end <= video.start
+    {
+        return Err("--video-to precisa ser depois de --video-from".to_string());
+    }
+    Ok(true)
+}
+
+enum VideoTarget {
+    Gif(DrawingArea<BitMapBackend<'static>, Shift>),
+    PngSequence,
+}
+
+/// Grava quadros a intervalos fixos de tempo simulado. Se um passo pula mais de um intervalo
+/// (janela lenta), o mesmo estado é repetido para o vídeo manter a duração certa.
+pub struct VideoRecorder {
+    options: VideoOptions,
+    target: VideoTarget,
+    path: PathBuf,
+    /// Tempo simulado do primeiro quadro e do próximo
+    first_time: f32,
+    next_time: f32,
+    frames: usize,
+}
+
+impl VideoRecorder {
+    /// `path` é o arquivo `.gif` ou a pasta dos PNGs; `size` é o tamanho dos quadros
+    /// (fixo no GIF) e `now` o tempo simulado atual (o primeiro quadro sai dele em diante)
+    pub fn create(path: &Path, options: VideoOptions, size: (u32, u32), now: f32) -> Result<Self, String> {
+        let target = match options.format {
+            VideoFormat::Gif => {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+                }
+                let delay_ms = (1000.0 / options.fps).round() as u32;
+                let backend = BitMapBackend::gif(path, size, delay_ms).map_err(|e| e.to_string())?;
+                VideoTarget::Gif(backend.into_drawing_area())
+            }
+            VideoFormat::PngSequence => {
+                fs::create_dir_all(path).map_err(|e| e.to_string())?;
+                VideoTarget::PngSequence
+            }
+        };
+        let first_time = options.start.max(now);
+        Ok(Self { options, target, path: path.to_path_buf(), first_time, next_time: first_time, frames: 0 })
+    }
+
+    /// Grava os quadros que vencem até `time` (nenhum fora do trecho pedido)
+    pub fn capture(&mut self, time: f32, frame: &Frame) -> Result<(), String> {
+        // Folga para o erro de arredondamento de somar o dt a cada passo
+        while self.next_time <= time + 1e-4 && !self.is_past_end(self.next_time) {
+            match &self.target {
+                VideoTarget::Gif(root) => {
+                    frame.draw(root).map_err(|e| e.to_string())?;
+                    root.present().map_err(|e| e.to_string())?;
+                }
+                VideoTarget::PngSequence => {
+                    let path = self.path.join(format!("frame_{:06}.png", self.frames));
+                    frame.save(&path, FrameFormat::Png)?;
+                }
+            }
+            self.frames += 1;
+            // Multiplicado a partir do primeiro quadro, sem acumular erro
+            self.next_time = self.first_time + self.frames as f32 / self.options.fps;
+        }
+        Ok(())
+    }
+
+    /// O trecho pedido já acabou (a janela encerra a gravação sozinha)
+    pub fn is_past_end(&self, time: f32) -> bool {
+        self.options.end.is_some_and(|end| time > end + 1e-4)
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}